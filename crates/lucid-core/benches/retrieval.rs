@@ -92,6 +92,7 @@ fn bench_retrieval_no_spreading(c: &mut Criterion) {
 					working_memory_boosts: &working_memory_boosts,
 					associations: &[],
 					current_time_ms: current_time,
+					reconsolidation: None,
 				};
 				bench.iter(|| retrieve(black_box(&input), black_box(&config)));
 			},
@@ -139,6 +140,53 @@ fn bench_retrieval_with_spreading(c: &mut Criterion) {
 					working_memory_boosts: &working_memory_boosts,
 					associations: &associations,
 					current_time_ms: current_time,
+					reconsolidation: None,
+				};
+				bench.iter(|| retrieve(black_box(&input), black_box(&config)));
+			},
+		);
+	}
+
+	group.finish();
+}
+
+fn bench_retrieval_prenormalized(c: &mut Criterion) {
+	let mut group = c.benchmark_group("retrieval_prenormalized");
+	let current_time = 1_000_000_000.0;
+	let dim = 1024;
+	let memory_count = 1000;
+
+	let probe = generate_embeddings(1, dim)
+		.pop()
+		.expect("should have probe");
+	let memories = generate_embeddings(memory_count, dim);
+	let access_histories = generate_access_histories(memory_count, current_time);
+	let emotional_weights: Vec<f64> = (0..memory_count).map(|_| 0.5).collect();
+	let decay_rates: Vec<f64> = (0..memory_count).map(|_| 0.5).collect();
+	let working_memory_boosts: Vec<f64> = (0..memory_count).map(|_| 1.0).collect();
+
+	for prenormalized in [false, true] {
+		let config = RetrievalConfig {
+			spreading_depth: 0,
+			embeddings_prenormalized: prenormalized,
+			..Default::default()
+		};
+
+		let _ = group.throughput(Throughput::Elements(memory_count as u64));
+		let _ = group.bench_with_input(
+			BenchmarkId::new("prenormalized", prenormalized),
+			&prenormalized,
+			|bench, _| {
+				let input = RetrievalInput {
+					probe_embedding: &probe,
+					memory_embeddings: &memories,
+					access_histories_ms: &access_histories,
+					emotional_weights: &emotional_weights,
+					decay_rates: &decay_rates,
+					working_memory_boosts: &working_memory_boosts,
+					associations: &[],
+					current_time_ms: current_time,
+					reconsolidation: None,
 				};
 				bench.iter(|| retrieve(black_box(&input), black_box(&config)));
 			},
@@ -176,6 +224,7 @@ fn bench_retrieval_varying_dimensions(c: &mut Criterion) {
 				working_memory_boosts: &working_memory_boosts,
 				associations: &[],
 				current_time_ms: current_time,
+				reconsolidation: None,
 			};
 			bench.iter(|| retrieve(black_box(&input), black_box(&config)));
 		});
@@ -222,6 +271,7 @@ fn bench_retrieval_varying_association_density(c: &mut Criterion) {
 					working_memory_boosts: &working_memory_boosts,
 					associations: &associations,
 					current_time_ms: current_time,
+					reconsolidation: None,
 				};
 				bench.iter(|| retrieve(black_box(&input), black_box(&config)));
 			},
@@ -264,6 +314,7 @@ fn bench_retrieval_spreading_depth(c: &mut Criterion) {
 				working_memory_boosts: &working_memory_boosts,
 				associations: &associations,
 				current_time_ms: current_time,
+				reconsolidation: None,
 			};
 			bench.iter(|| retrieve(black_box(&input), black_box(&config)));
 		});
@@ -276,6 +327,7 @@ criterion_group!(
 	benches,
 	bench_retrieval_no_spreading,
 	bench_retrieval_with_spreading,
+	bench_retrieval_prenormalized,
 	bench_retrieval_varying_dimensions,
 	bench_retrieval_varying_association_density,
 	bench_retrieval_spreading_depth,
@@ -64,6 +64,7 @@ fn main() {
 		working_memory_boosts: &working_memory_boosts,
 		associations: &associations,
 		current_time_ms,
+		reconsolidation: None,
 	};
 
 	// Use default config
@@ -104,6 +104,7 @@ fn main() {
 		working_memory_boosts: &working_memory_boosts,
 		associations: &[], // No associations
 		current_time_ms,
+		reconsolidation: None,
 	};
 
 	let config_no_spread = RetrievalConfig {
@@ -133,6 +134,7 @@ fn main() {
 		working_memory_boosts: &working_memory_boosts,
 		associations: &associations,
 		current_time_ms,
+		reconsolidation: None,
 	};
 
 	let config_spread = RetrievalConfig {
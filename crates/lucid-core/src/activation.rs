@@ -11,8 +11,13 @@
 //! it ensures weakly matching traces contribute minimally
 //! while strong matches dominate.
 
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
 use serde::{Deserialize, Serialize};
 
+use crate::spreading::Association;
+
 /// Configuration for activation calculations.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActivationConfig {
@@ -98,19 +103,118 @@ pub fn compute_base_level(
 	sum.ln()
 }
 
+/// Compute base-level activation over at most the `max_history` most recent
+/// accesses.
+///
+/// Histories are expected oldest first, as [`record_access`] keeps them, so
+/// only the last `max_history` timestamps are summed and the cost is bounded
+/// by the cap rather than the history length. Older accesses are dropped.
+///
+/// Each dropped access is at least as old as the oldest kept one (age `t_k`
+/// seconds), so it contributes at most `t_k^(-d)` to the sum. With `n - k`
+/// dropped accesses and `S_k` the sum over the kept ones, the capped result
+/// understates B by at most:
+///
+/// ```text
+/// ln(1 + (n - k) · t_k^(-d) / S_k)
+/// ```
+///
+/// `None`, or a cap at or above the history length, gives exactly
+/// [`compute_base_level`]. A cap of 0 is treated as 1.
+#[must_use]
+pub fn compute_base_level_capped(
+	access_timestamps_ms: &[f64],
+	current_time_ms: f64,
+	decay_rate: f64,
+	max_history: Option<usize>,
+) -> f64 {
+	let start = max_history.map_or(0, |cap| {
+		access_timestamps_ms.len().saturating_sub(cap.max(1))
+	});
+	compute_base_level(&access_timestamps_ms[start..], current_time_ms, decay_rate)
+}
+
 /// Batch compute base-level activation for multiple memories.
+///
+/// `max_history` caps how many recent accesses per memory are summed; see
+/// [`compute_base_level_capped`] for the error this introduces.
 #[must_use]
 pub fn compute_base_level_batch(
 	memories: &[Vec<f64>],
 	current_time_ms: f64,
 	decay_rate: f64,
+	max_history: Option<usize>,
 ) -> Vec<f64> {
 	memories
 		.iter()
-		.map(|timestamps| compute_base_level(timestamps, current_time_ms, decay_rate))
+		.map(|timestamps| {
+			compute_base_level_capped(timestamps, current_time_ms, decay_rate, max_history)
+		})
+		.collect()
+}
+
+/// Milliseconds in a day.
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// Sample the forgetting curve of a memory accessed once.
+///
+/// Evaluates [`compute_base_level`] for a single access at
+/// `single_access_ms` at `steps` evenly spaced moments from
+/// `current_time_ms` to `horizon_days` later, both ends included. Each entry
+/// is `(days after current_time_ms, base-level activation)`, suitable for
+/// plotting. `steps` of 0 gives an empty curve and 1 gives just the current
+/// moment.
+#[must_use]
+pub fn preview_base_level_decay(
+	single_access_ms: f64,
+	current_time_ms: f64,
+	decay_rate: f64,
+	steps: usize,
+	horizon_days: f64,
+) -> Vec<(f64, f64)> {
+	#[allow(clippy::cast_precision_loss)]
+	let intervals = steps.saturating_sub(1).max(1) as f64;
+
+	(0..steps)
+		.map(|step| {
+			#[allow(clippy::cast_precision_loss)]
+			let days = horizon_days * step as f64 / intervals;
+			let activation = compute_base_level(
+				&[single_access_ms],
+				days.mul_add(MS_PER_DAY, current_time_ms),
+				decay_rate,
+			);
+			(days, activation)
+		})
 		.collect()
 }
 
+/// Append an access to a history, keeping at most `max_history` entries.
+///
+/// When the history overflows, the oldest timestamps are dropped, so the
+/// returned history is what the caller should store in place of the old one.
+/// This bounds storage the way [`compute_base_level_capped`] bounds compute:
+/// B computed from a trimmed history is lower than it would be from the full
+/// one.
+///
+/// The returned history is always sorted oldest first, which is the order
+/// [`compute_base_level_capped`] relies on when it keeps only the last
+/// entries, so an unsorted input history is sorted too. A cap of 0 is
+/// treated as 1.
+#[must_use]
+pub fn record_access(history: &[f64], timestamp_ms: f64, max_history: usize) -> Vec<f64> {
+	let mut updated = Vec::with_capacity(history.len() + 1);
+	updated.extend_from_slice(history);
+	updated.push(timestamp_ms);
+	updated.sort_by(f64::total_cmp);
+
+	let cap = max_history.max(1);
+	if updated.len() > cap {
+		let _ = updated.drain(..updated.len() - cap);
+	}
+	updated
+}
+
 // ============================================================================
 // Vector Similarity
 // ============================================================================
@@ -160,28 +264,149 @@ pub fn cosine_similarity_batch(probe: &[f64], traces: &[Vec<f64>]) -> Vec<f64> {
 
 	traces
 		.iter()
-		.map(|trace| {
-			if trace.len() != probe.len() {
-				return 0.0;
-			}
+		.map(|trace| cosine_with_probe_norm(probe, probe_norm, trace))
+		.collect()
+}
 
-			let (dot_product, trace_norm_sq) = probe
-				.iter()
-				.zip(trace.iter())
-				.fold((0.0, 0.0), |(dot, tn), (&pi, &ti)| {
-					(pi.mul_add(ti, dot), ti.mul_add(ti, tn))
-				});
+/// Cosine similarity of a trace to a probe whose (non-zero) norm is known.
+#[inline]
+fn cosine_with_probe_norm(probe: &[f64], probe_norm: f64, trace: &[f64]) -> f64 {
+	if trace.len() != probe.len() {
+		return 0.0;
+	}
 
-			let trace_norm = trace_norm_sq.sqrt();
-			if trace_norm == 0.0 {
-				0.0
-			} else {
-				dot_product / (probe_norm * trace_norm)
-			}
-		})
+	let (dot_product, trace_norm_sq) = probe
+		.iter()
+		.zip(trace.iter())
+		.fold((0.0, 0.0), |(dot, tn), (&pi, &ti)| {
+			(pi.mul_add(ti, dot), ti.mul_add(ti, tn))
+		});
+
+	let trace_norm = trace_norm_sq.sqrt();
+	if trace_norm == 0.0 {
+		0.0
+	} else {
+		dot_product / (probe_norm * trace_norm)
+	}
+}
+
+/// The `k` traces most cosine-similar to the probe, as `(index, similarity)`
+/// pairs, most similar first.
+///
+/// Equivalent to sorting [`cosine_similarity_batch`] and truncating to `k`
+/// (ties keep the lower index first), but only a `k`-sized heap is kept,
+/// so large stores don't allocate a full score vector.
+#[must_use]
+pub fn cosine_similarity_top_k(probe: &[f64], traces: &[Vec<f64>], k: usize) -> Vec<(usize, f64)> {
+	/// Orders by similarity, then prefers the lower index.
+	#[derive(PartialEq)]
+	struct Ranked(f64, usize);
+
+	impl Eq for Ranked {}
+
+	impl PartialOrd for Ranked {
+		fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+			Some(self.cmp(other))
+		}
+	}
+
+	impl Ord for Ranked {
+		fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+			self.0.total_cmp(&other.0).then(other.1.cmp(&self.1))
+		}
+	}
+
+	if k == 0 {
+		return Vec::new();
+	}
+
+	let probe_norm: f64 = probe.iter().map(|x| x * x).sum::<f64>().sqrt();
+	let similarity = |trace: &[f64]| {
+		if probe_norm == 0.0 {
+			0.0
+		} else {
+			cosine_with_probe_norm(probe, probe_norm, trace)
+		}
+	};
+
+	// Min-heap of the best k so far; its top is the weakest kept trace
+	let mut heap = BinaryHeap::with_capacity(k.min(traces.len()) + 1);
+	for (i, trace) in traces.iter().enumerate() {
+		let ranked = Reverse(Ranked(similarity(trace), i));
+		if heap.len() < k {
+			heap.push(ranked);
+		} else if heap.peek().is_some_and(|weakest| ranked < *weakest) {
+			let _ = heap.pop();
+			heap.push(ranked);
+		}
+	}
+
+	heap.into_sorted_vec()
+		.into_iter()
+		.map(|Reverse(Ranked(similarity, i))| (i, similarity))
 		.collect()
 }
 
+/// How probe-trace similarity is measured before MINERVA 2 cubing.
+///
+/// Every metric yields a value in [-1, 1] so the cubing and WM boost steps
+/// behave the same regardless of the choice.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SimilarityMetric {
+	/// Cosine similarity (magnitude-invariant)
+	#[default]
+	Cosine,
+	/// Raw dot product, clamped to [-1, 1]. Keeps magnitude information for
+	/// models meant to be compared by dot product; vectors should be roughly
+	/// unit scale or larger products saturate at 1.
+	DotProduct,
+	/// `1 / (1 + ‖p - t‖₂)`, so identical vectors score 1
+	Euclidean,
+	/// `1 / (1 + ‖p - t‖₁)`, so identical vectors score 1
+	Manhattan,
+}
+
+/// Batch compute probe-trace similarity with the given metric.
+///
+/// Traces whose length differs from the probe score 0, as in
+/// [`cosine_similarity_batch`].
+#[must_use]
+pub fn similarity_batch(probe: &[f64], traces: &[Vec<f64>], metric: SimilarityMetric) -> Vec<f64> {
+	let per_trace = |score: fn(&[f64], &[f64]) -> f64| -> Vec<f64> {
+		traces
+			.iter()
+			.map(|trace| {
+				if trace.len() == probe.len() {
+					score(probe, trace)
+				} else {
+					0.0
+				}
+			})
+			.collect()
+	};
+
+	match metric {
+		SimilarityMetric::Cosine => cosine_similarity_batch(probe, traces),
+		SimilarityMetric::DotProduct => per_trace(|p, t| {
+			p.iter()
+				.zip(t)
+				.fold(0.0, |dot, (&pi, &ti)| pi.mul_add(ti, dot))
+				.clamp(-1.0, 1.0)
+		}),
+		SimilarityMetric::Euclidean => per_trace(|p, t| {
+			let distance_sq = p.iter().zip(t).fold(0.0, |sum, (&pi, &ti)| {
+				let diff = pi - ti;
+				diff.mul_add(diff, sum)
+			});
+			1.0 / (1.0 + distance_sq.sqrt())
+		}),
+		SimilarityMetric::Manhattan => per_trace(|p, t| {
+			let distance: f64 = p.iter().zip(t).map(|(pi, ti)| (pi - ti).abs()).sum();
+			1.0 / (1.0 + distance)
+		}),
+	}
+}
+
 // ============================================================================
 // MINERVA 2 Activation
 // ============================================================================
@@ -210,6 +435,18 @@ pub fn nonlinear_activation_batch(similarities: &[f64]) -> Vec<f64> {
 // Combined Activation
 // ============================================================================
 
+/// How base-level, probe and spreading activation are combined into a total.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CombineMode {
+	/// `(probe × emotional × (1 + recency_boost)) + spreading`; recency only
+	/// amplifies relevance, so an irrelevant memory can't surface on recency
+	#[default]
+	Multiplicative,
+	/// Classic ACT-R sum `base + (probe × emotional) + spreading`; recently or
+	/// frequently accessed memories can surface with little similarity
+	Additive,
+}
+
 /// Combine all activation sources into total activation.
 ///
 /// Uses MULTIPLICATIVE combination where similarity is the primary signal
@@ -228,8 +465,52 @@ pub fn combine_activations(
 	spreading_activation: f64,
 	emotional_weight: f64,
 ) -> ActivationBreakdown {
-	// Emotional weight modulates probe activation (range: 0.5 to 1.5)
-	let emotional_multiplier = 1.0 + (emotional_weight - 0.5);
+	combine_activations_with_mode(
+		base_level,
+		probe_activation,
+		spreading_activation,
+		emotional_weight,
+		CombineMode::Multiplicative,
+	)
+}
+
+/// Combine all activation sources using the given [`CombineMode`].
+///
+/// The breakdown is the same in both modes; only `total` differs.
+#[must_use]
+pub fn combine_activations_with_mode(
+	base_level: f64,
+	probe_activation: f64,
+	spreading_activation: f64,
+	emotional_weight: f64,
+	mode: CombineMode,
+) -> ActivationBreakdown {
+	combine_activations_with_emotional_scale(
+		base_level,
+		probe_activation,
+		spreading_activation,
+		emotional_weight,
+		mode,
+		1.0,
+	)
+}
+
+/// Combine all activation sources, scaling how strongly emotional weight
+/// modulates the probe activation.
+///
+/// The emotional multiplier is `1 + scale × (emotional_weight - 0.5)`, so a
+/// scale of 1.0 gives the usual [0.5, 1.5] range, 2.0 widens it to [0, 2]
+/// and 0.0 turns emotional modulation off.
+#[must_use]
+pub fn combine_activations_with_emotional_scale(
+	base_level: f64,
+	probe_activation: f64,
+	spreading_activation: f64,
+	emotional_weight: f64,
+	mode: CombineMode,
+	emotional_multiplier_scale: f64,
+) -> ActivationBreakdown {
+	let emotional_multiplier = emotional_multiplier_scale.mul_add(emotional_weight - 0.5, 1.0);
 
 	// Handle -infinity base level
 	let effective_base = if base_level.is_finite() {
@@ -246,10 +527,15 @@ pub fn combine_activations(
 	// Apply emotional modulation to probe activation
 	let modulated_probe = probe_activation * emotional_multiplier;
 
-	// MULTIPLICATIVE combination: similarity is primary, recency is boost
-	// This ensures low-similarity items can't be rescued by recency alone
-	let probe_with_recency = modulated_probe * (1.0 + recency_boost);
-	let total = probe_with_recency + spreading_activation;
+	let total = match mode {
+		// MULTIPLICATIVE combination: similarity is primary, recency is boost
+		// This ensures low-similarity items can't be rescued by recency alone
+		CombineMode::Multiplicative => {
+			let probe_with_recency = modulated_probe * (1.0 + recency_boost);
+			probe_with_recency + spreading_activation
+		}
+		CombineMode::Additive => effective_base + modulated_probe + spreading_activation,
+	};
 
 	ActivationBreakdown {
 		base_level: effective_base,
@@ -264,6 +550,9 @@ pub fn combine_activations(
 // Retrieval Probability
 // ============================================================================
 
+/// Smallest noise parameter [`retrieval_probability`] divides by.
+pub const MIN_NOISE_PARAMETER: f64 = 1e-6;
+
 /// Compute probability of successful retrieval.
 ///
 /// `P(recall) = 1 / (1 + e^((τ - A) / s))`
@@ -275,6 +564,9 @@ pub fn combine_activations(
 ///
 /// This is a logistic function centered on the threshold.
 /// Higher activation = higher probability.
+///
+/// `s` is floored at [`MIN_NOISE_PARAMETER`], so a zero noise parameter (as
+/// from an uninitialized config) gives a near-step function instead of NaN.
 #[inline]
 #[must_use]
 pub fn retrieval_probability(
@@ -282,11 +574,18 @@ pub fn retrieval_probability(
 	activation_threshold: f64,
 	noise_parameter: f64,
 ) -> f64 {
-	let exponent = (activation_threshold - total_activation) / noise_parameter;
+	debug_assert!(
+		noise_parameter >= 0.0,
+		"noise parameter must be non-negative, got {noise_parameter}"
+	);
+	let exponent =
+		(activation_threshold - total_activation) / noise_parameter.max(MIN_NOISE_PARAMETER);
 	1.0 / (1.0 + exponent.exp())
 }
 
 /// Batch compute retrieval probabilities.
+///
+/// The noise parameter is floored as in [`retrieval_probability`].
 #[must_use]
 pub fn retrieval_probability_batch(
 	activations: &[f64],
@@ -534,6 +833,8 @@ pub struct AssociationDecayConfig {
 	pub reinforcement_boost: f64,
 	/// Associations below this strength are candidates for pruning
 	pub prune_threshold: f64,
+	/// Strength given to both directions of a newly created association
+	pub initial_strength: f64,
 }
 
 impl Default for AssociationDecayConfig {
@@ -545,6 +846,7 @@ impl Default for AssociationDecayConfig {
 			tau_reconsolidating_days: 7.0, // 7 days
 			reinforcement_boost: 0.05,
 			prune_threshold: 0.1,
+			initial_strength: 0.5,
 		}
 	}
 }
@@ -594,6 +896,51 @@ pub fn reinforce_association(current_strength: f64, config: &AssociationDecayCon
 	(current_strength + config.reinforcement_boost).min(1.0)
 }
 
+/// Reinforce every association among a set of co-accessed memories.
+///
+/// Edges with both endpoints in `coaccessed` get the co-access boost in both
+/// directions. Co-accessed pairs with no edge in either direction gain a new
+/// one at `initial_strength`, appended in index order. Duplicate indices and
+/// self-loops are ignored.
+pub fn reinforce_coaccess(
+	associations: &mut Vec<Association>,
+	coaccessed: &[usize],
+	config: &AssociationDecayConfig,
+) {
+	let mut nodes = coaccessed.to_vec();
+	nodes.sort_unstable();
+	nodes.dedup();
+
+	let mut linked = HashSet::new();
+	for assoc in associations.iter_mut() {
+		if assoc.source == assoc.target
+			|| nodes.binary_search(&assoc.source).is_err()
+			|| nodes.binary_search(&assoc.target).is_err()
+		{
+			continue;
+		}
+		assoc.forward_strength = reinforce_association(assoc.forward_strength, config);
+		assoc.backward_strength = reinforce_association(assoc.backward_strength, config);
+		let _ = linked.insert((
+			assoc.source.min(assoc.target),
+			assoc.source.max(assoc.target),
+		));
+	}
+
+	for (i, &source) in nodes.iter().enumerate() {
+		for &target in &nodes[i + 1..] {
+			if !linked.contains(&(source, target)) {
+				associations.push(Association {
+					source,
+					target,
+					forward_strength: config.initial_strength,
+					backward_strength: config.initial_strength,
+				});
+			}
+		}
+	}
+}
+
 /// Check if an association should be pruned.
 #[inline]
 #[must_use]
@@ -601,6 +948,40 @@ pub fn should_prune_association(strength: f64, config: &AssociationDecayConfig)
 	strength < config.prune_threshold
 }
 
+/// Decay a whole association graph ahead of retrieval.
+///
+/// `days_since_reinforced[i]` and `states[i]` describe `associations[i]`;
+/// both directions of the edge decay with the same τ. An edge is dropped once
+/// both strengths fall below `prune_threshold`. Slices are zipped, so edges
+/// past the shortest one are dropped too.
+#[must_use]
+pub fn decay_associations(
+	associations: &[Association],
+	days_since_reinforced: &[f64],
+	states: &[AssociationState],
+	config: &AssociationDecayConfig,
+) -> Vec<Association> {
+	associations
+		.iter()
+		.zip(days_since_reinforced.iter().zip(states))
+		.filter_map(|(assoc, (&days, &state))| {
+			let forward_strength =
+				compute_association_decay(assoc.forward_strength, days, state, config);
+			let backward_strength =
+				compute_association_decay(assoc.backward_strength, days, state, config);
+
+			let prune = should_prune_association(forward_strength, config)
+				&& should_prune_association(backward_strength, config);
+			(!prune).then_some(Association {
+				source: assoc.source,
+				target: assoc.target,
+				forward_strength,
+				backward_strength,
+			})
+		})
+		.collect()
+}
+
 // ============================================================================
 // Reconsolidation (Nader et al. 2000, Lee 2009)
 // ============================================================================
@@ -715,8 +1096,12 @@ pub fn pe_zone(pe_abs: f64, theta_low_eff: f64, theta_high_eff: f64) -> &'static
 pub fn rank_by_activation(activations: &[f64], top_k: usize) -> Vec<usize> {
 	let mut indexed: Vec<(usize, f64)> = activations.iter().copied().enumerate().collect();
 
-	// Sort by activation descending
-	indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+	// Sort by activation descending, ties by index
+	indexed.sort_by(|a, b| {
+		b.1.partial_cmp(&a.1)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.0.cmp(&b.0))
+	});
 
 	indexed.into_iter().take(top_k).map(|(i, _)| i).collect()
 }
@@ -867,6 +1252,64 @@ mod tests {
 		assert!(!should_prune_association(0.15, &config));
 	}
 
+	#[test]
+	fn test_decay_associations_prunes_weak_edges() {
+		let config = AssociationDecayConfig::default();
+		let edge = |source, target| Association {
+			source,
+			target,
+			forward_strength: 1.0,
+			backward_strength: 0.5,
+		};
+		let associations = [edge(0, 1), edge(1, 2), edge(2, 3)];
+		let states = [
+			AssociationState::Consolidated, // 30 days = 1 τ → survives at ~0.37
+			AssociationState::Fresh,        // 1 day = 24 τ → pruned
+			AssociationState::Consolidated, // Reinforced today → unchanged
+		];
+
+		let decayed = decay_associations(&associations, &[30.0, 1.0, 0.0], &states, &config);
+
+		assert_eq!(decayed.len(), 2);
+		assert_eq!((decayed[0].source, decayed[0].target), (0, 1));
+		assert!((decayed[0].forward_strength - 0.368).abs() < 0.01);
+		assert!((decayed[0].backward_strength - 0.184).abs() < 0.01);
+		assert_eq!((decayed[1].source, decayed[1].target), (2, 3));
+		assert_eq!(decayed[1].forward_strength, 1.0);
+	}
+
+	#[test]
+	fn test_reinforce_coaccess_boosts_and_links() {
+		let config = AssociationDecayConfig::default();
+		let mut associations = vec![
+			Association {
+				source: 2,
+				target: 0,
+				forward_strength: 0.4,
+				backward_strength: 0.2,
+			},
+			Association {
+				source: 1,
+				target: 3,
+				forward_strength: 0.6,
+				backward_strength: 0.6,
+			},
+		];
+
+		reinforce_coaccess(&mut associations, &[0, 2, 1, 2], &config);
+
+		// Existing 2↔0 edge boosted both ways, 1↔3 untouched (3 not co-accessed)
+		assert!((associations[0].forward_strength - 0.45).abs() < 1e-9);
+		assert!((associations[0].backward_strength - 0.25).abs() < 1e-9);
+		assert_eq!(associations[1].forward_strength, 0.6);
+
+		// 0↔1 and 1↔2 created at initial strength
+		assert_eq!(associations.len(), 4);
+		assert_eq!((associations[2].source, associations[2].target), (0, 1));
+		assert_eq!((associations[3].source, associations[3].target), (1, 2));
+		assert_eq!(associations[3].forward_strength, config.initial_strength);
+	}
+
 	// Reconsolidation tests
 
 	#[test]
@@ -964,6 +1407,32 @@ mod tests {
 		assert!(cosine_similarity(&a, &b).abs() < 1e-10);
 	}
 
+	#[test]
+	fn test_cosine_similarity_top_k_matches_full_sort() {
+		use rand::{Rng, SeedableRng};
+
+		let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+		let mut random_vec = || {
+			(0..16)
+				.map(|_| rng.gen_range(-1.0..1.0))
+				.collect::<Vec<f64>>()
+		};
+		let probe = random_vec();
+		let mut traces: Vec<Vec<f64>> = (0..200).map(|_| random_vec()).collect();
+		traces.push(traces[3].clone()); // A tie, to check tie order too
+
+		let mut expected: Vec<(usize, f64)> = cosine_similarity_batch(&probe, &traces)
+			.into_iter()
+			.enumerate()
+			.collect();
+		expected.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+		for k in [0, 1, 10, 200, 500] {
+			let top = cosine_similarity_top_k(&probe, &traces, k);
+			assert_eq!(top, expected[..k.min(expected.len())].to_vec());
+		}
+	}
+
 	#[test]
 	fn test_nonlinear_activation() {
 		assert!((nonlinear_activation(0.5) - 0.125).abs() < 1e-10);
@@ -971,6 +1440,15 @@ mod tests {
 		assert!((nonlinear_activation(-0.5) - (-0.125)).abs() < 1e-10);
 	}
 
+	#[test]
+	fn test_zero_noise_is_a_step_function() {
+		let probabilities = retrieval_probability_batch(&[0.2, 0.3, 0.4], 0.3, 0.0);
+		assert!(probabilities.iter().all(|p| !p.is_nan()));
+		assert!(probabilities[0] < 1e-9);
+		assert!((probabilities[1] - 0.5).abs() < 1e-12);
+		assert!(probabilities[2] > 1.0 - 1e-9);
+	}
+
 	#[test]
 	fn test_retrieval_probability() {
 		// At threshold, probability should be 0.5
@@ -993,4 +1471,113 @@ mod tests {
 
 		assert!(recent_activation > old_activation);
 	}
+
+	#[test]
+	fn test_decay_preview_decreases_with_age() {
+		let now = 10.0 * MS_PER_DAY;
+		let curve = preview_base_level_decay(now - 60_000.0, now, 0.5, 30, 90.0);
+
+		assert_eq!(curve.len(), 30);
+		assert!(curve[0].0.abs() < f64::EPSILON);
+		assert!((curve[29].0 - 90.0).abs() < 1e-9);
+		for pair in curve.windows(2) {
+			assert!(pair[1].0 > pair[0].0);
+			assert!(pair[1].1 < pair[0].1);
+		}
+
+		assert!(preview_base_level_decay(0.0, now, 0.5, 0, 90.0).is_empty());
+		assert_eq!(preview_base_level_decay(0.0, now, 0.5, 1, 90.0).len(), 1);
+	}
+
+	#[test]
+	fn test_base_level_capped_sums_recent_slice() {
+		let hour_ms = 3_600_000.0;
+		let now = 5_000.0 * hour_ms;
+		// 1000 accesses about an hour apart with some jitter, oldest first
+		let history: Vec<f64> = (1..=1000)
+			.rev()
+			.map(|k| {
+				let jitter = f64::from((k * 7919) % 13) / 13.0 - 0.5;
+				now - (f64::from(k) + 0.4 * jitter) * hour_ms
+			})
+			.collect();
+
+		let exact = compute_base_level(&history, now, 0.5);
+		let capped = compute_base_level_capped(&history, now, 0.5, Some(50));
+		assert_eq!(capped, compute_base_level(&history[950..], now, 0.5));
+
+		// Documented bound from the kept sum and the oldest kept age
+		let oldest_kept_s = (now - history[950]) / 1000.0;
+		let kept_sum: f64 = history[950..]
+			.iter()
+			.map(|t| ((now - t) / 1000.0).powf(-0.5))
+			.sum();
+		let bound = (950.0 * oldest_kept_s.powf(-0.5) / kept_sum).ln_1p();
+
+		assert!(capped < exact);
+		assert!(exact - capped <= bound);
+	}
+
+	#[test]
+	fn test_base_level_capped_edge_caps() {
+		let now = 10_000_000.0;
+		let history = vec![
+			now - 3_000_000.0,
+			now - 900_000.0,
+			now - 20_000.0,
+			now - 5_000.0,
+		];
+		let exact = compute_base_level(&history, now, 0.5);
+
+		assert_eq!(compute_base_level_capped(&history, now, 0.5, None), exact);
+		assert_eq!(
+			compute_base_level_capped(&history, now, 0.5, Some(4)),
+			exact
+		);
+		assert_eq!(
+			compute_base_level_capped(&history, now, 0.5, Some(0)),
+			compute_base_level(&history[3..], now, 0.5)
+		);
+	}
+
+	#[test]
+	fn test_record_access_trims_oldest() {
+		let history = vec![3000.0, 1000.0, 4000.0, 2000.0];
+
+		assert_eq!(
+			record_access(&history, 5000.0, 10),
+			vec![1000.0, 2000.0, 3000.0, 4000.0, 5000.0]
+		);
+		assert_eq!(
+			record_access(&[1000.0, 3000.0], 2000.0, 10),
+			vec![1000.0, 2000.0, 3000.0]
+		);
+		assert_eq!(
+			record_access(&history, 5000.0, 3),
+			vec![3000.0, 4000.0, 5000.0]
+		);
+		assert_eq!(record_access(&history, 5000.0, 0), vec![5000.0]);
+		assert_eq!(record_access(&[], 5000.0, 3), vec![5000.0]);
+	}
+
+	#[test]
+	fn test_similarity_batch_metrics() {
+		let probe = [1.0, 0.0];
+		let traces = vec![vec![1.0, 0.0], vec![3.0, 4.0], vec![1.0]];
+
+		let cosine = similarity_batch(&probe, &traces, SimilarityMetric::Cosine);
+		assert_eq!(cosine, cosine_similarity_batch(&probe, &traces));
+
+		let dot = similarity_batch(&probe, &traces, SimilarityMetric::DotProduct);
+		assert!((dot[0] - 1.0).abs() < 1e-12);
+		assert!((dot[1] - 1.0).abs() < 1e-12); // 3.0, clamped
+		assert!(dot[2].abs() < 1e-12);
+
+		let euclidean = similarity_batch(&probe, &traces, SimilarityMetric::Euclidean);
+		assert!((euclidean[0] - 1.0).abs() < 1e-12);
+		assert!((euclidean[1] - 1.0 / (1.0 + 20.0_f64.sqrt())).abs() < 1e-12);
+
+		let manhattan = similarity_batch(&probe, &traces, SimilarityMetric::Manhattan);
+		assert!((manhattan[1] - 1.0 / 7.0).abs() < 1e-12);
+	}
 }
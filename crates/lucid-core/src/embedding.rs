@@ -8,7 +8,7 @@ use ort::session::Session;
 use ort::value::Tensor;
 use parking_lot::Mutex;
 use std::path::PathBuf;
-use tokenizers::Tokenizer;
+use tokenizers::{Encoding, Tokenizer, TruncationParams};
 
 /// Default model directory: `~/.lucid/models`
 fn default_model_dir() -> PathBuf {
@@ -25,6 +25,10 @@ pub struct EmbeddingModelConfig {
 	pub model_path: Option<PathBuf>,
 	/// Path to the tokenizer.json file.
 	pub tokenizer_path: Option<PathBuf>,
+	/// Longest token sequence fed to the model, special tokens included.
+	/// Longer inputs are truncated, so only their first ~`max_sequence_length`
+	/// tokens shape the embedding. 0 disables truncation.
+	pub max_sequence_length: usize,
 }
 
 impl Default for EmbeddingModelConfig {
@@ -33,6 +37,7 @@ impl Default for EmbeddingModelConfig {
 		Self {
 			model_path: Some(dir.join("bge-base-en-v1.5-fp16.onnx")),
 			tokenizer_path: Some(dir.join("bge-base-en-v1.5-tokenizer.json")),
+			max_sequence_length: 512, // BGE's position embedding limit
 		}
 	}
 }
@@ -69,6 +74,9 @@ pub enum EmbeddingError {
 impl EmbeddingModel {
 	/// Load the ONNX model and tokenizer from disk.
 	///
+	/// The tokenizer is set to truncate at `config.max_sequence_length`,
+	/// replacing any truncation configured in tokenizer.json.
+	///
 	/// # Errors
 	///
 	/// Returns an error if the model or tokenizer files cannot be loaded.
@@ -103,8 +111,9 @@ impl EmbeddingModel {
 
 		let session = Session::builder()?.commit_from_file(model_path)?;
 
-		let tokenizer = Tokenizer::from_file(tokenizer_path)
+		let mut tokenizer = Tokenizer::from_file(tokenizer_path)
 			.map_err(|e| EmbeddingError::Tokenizer(e.to_string()))?;
+		set_max_sequence_length(&mut tokenizer, config.max_sequence_length)?;
 
 		Ok(Self {
 			session: Mutex::new(session),
@@ -166,6 +175,8 @@ impl EmbeddingModel {
 
 	/// Embed a batch of texts. Pads to max length in the batch for a single ONNX run.
 	///
+	/// Texts longer than the configured `max_sequence_length` are truncated.
+	///
 	/// # Errors
 	///
 	/// Returns an error if tokenization or inference fails.
@@ -174,39 +185,56 @@ impl EmbeddingModel {
 			return Ok(vec![]);
 		}
 
-		let encodings = self
-			.tokenizer
-			.encode_batch(texts.to_vec(), true)
-			.map_err(|e| EmbeddingError::Tokenizer(e.to_string()))?;
+		let encodings = self.tokenize(texts)?;
+		let refs: Vec<&Encoding> = encodings.iter().collect();
+		self.embed_encodings(&refs)
+	}
 
-		let max_len = encodings
-			.iter()
-			.map(|e| e.get_ids().len())
-			.max()
-			.unwrap_or(0);
-		let batch_size = encodings.len();
+	/// Embed a batch of texts in sub-batches of at most `max_batch_tokens`
+	/// padded tokens each, returning results in input order.
+	///
+	/// `embed_batch` pads every text to the longest one, so a single long text
+	/// inflates the whole batch. Here texts are grouped by length and each
+	/// group runs as its own ONNX call, which bounds padding waste and peak
+	/// memory. A text longer than `max_batch_tokens` on its own still runs,
+	/// alone in its sub-batch.
+	///
+	/// # Errors
+	///
+	/// Returns an error if tokenization or any inference call fails.
+	pub fn embed_batch_chunked(
+		&self,
+		texts: &[&str],
+		max_batch_tokens: usize,
+	) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+		if texts.is_empty() {
+			return Ok(vec![]);
+		}
 
-		// Build padded input tensors
-		let mut input_ids = vec![0i64; batch_size * max_len];
-		let mut attention_mask = vec![0i64; batch_size * max_len];
-		let token_type_ids = vec![0i64; batch_size * max_len];
-
-		for (i, enc) in encodings.iter().enumerate() {
-			let ids = enc.get_ids();
-			let mask = enc.get_attention_mask();
-			let offset = i * max_len;
-			for (j, &id) in ids.iter().enumerate() {
-				input_ids[offset + j] = i64::from(id);
-			}
-			for (j, &m) in mask.iter().enumerate() {
-				attention_mask[offset + j] = i64::from(m);
+		let encodings = self.tokenize(texts)?;
+		let lengths: Vec<usize> = encodings.iter().map(token_count).collect();
+
+		let mut results: Vec<Vec<f32>> = vec![Vec::new(); texts.len()];
+		for group in plan_token_batches(&lengths, max_batch_tokens) {
+			let refs: Vec<&Encoding> = group.iter().map(|&i| &encodings[i]).collect();
+			for (i, embedding) in group.into_iter().zip(self.embed_encodings(&refs)?) {
+				results[i] = embedding;
 			}
 		}
 
-		let input_ids_arr = Array2::from_shape_vec([batch_size, max_len], input_ids)?;
-		let attention_mask_arr =
-			Array2::from_shape_vec([batch_size, max_len], attention_mask.clone())?;
-		let token_type_arr = Array2::from_shape_vec([batch_size, max_len], token_type_ids)?;
+		Ok(results)
+	}
+
+	fn tokenize(&self, texts: &[&str]) -> Result<Vec<Encoding>, EmbeddingError> {
+		self.tokenizer
+			.encode_batch(texts.to_vec(), true)
+			.map_err(|e| EmbeddingError::Tokenizer(e.to_string()))
+	}
+
+	/// Pad, run and pool one batch of encodings.
+	fn embed_encodings(&self, encodings: &[&Encoding]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+		let batch_size = encodings.len();
+		let (input_ids_arr, attention_mask_arr, token_type_arr) = padded_inputs(encodings)?;
 
 		let input_ids_tensor = Tensor::from_array(input_ids_arr)?;
 		let attention_mask_tensor = Tensor::from_array(attention_mask_arr)?;
@@ -220,11 +248,7 @@ impl EmbeddingModel {
 		let mut results = Vec::with_capacity(batch_size);
 
 		for i in 0..batch_size {
-			let seq_len = encodings[i]
-				.get_attention_mask()
-				.iter()
-				.filter(|&&m| m == 1)
-				.count();
+			let seq_len = token_count(encodings[i]);
 
 			let mut pooled = vec![0.0f32; hidden_dim];
 			for t in 0..seq_len {
@@ -267,6 +291,89 @@ impl EmbeddingModel {
 	}
 }
 
+/// Truncate tokenized inputs to `max_length` tokens (0 = never truncate).
+fn set_max_sequence_length(
+	tokenizer: &mut Tokenizer,
+	max_length: usize,
+) -> Result<(), EmbeddingError> {
+	let truncation = (max_length > 0).then(|| TruncationParams {
+		max_length,
+		..TruncationParams::default()
+	});
+	let _ = tokenizer
+		.with_truncation(truncation)
+		.map_err(|e| EmbeddingError::Tokenizer(e.to_string()))?;
+	Ok(())
+}
+
+/// Input ids, attention mask and token type ids, each `[batch, sequence]`.
+type ModelInputs = (Array2<i64>, Array2<i64>, Array2<i64>);
+
+/// Build the `[batch, sequence]` input id, attention mask and token type
+/// arrays for one batch.
+///
+/// The sequence dimension is the longest real sequence in the batch,
+/// ignoring any padding the tokenizer applied across the whole input.
+fn padded_inputs(encodings: &[&Encoding]) -> Result<ModelInputs, EmbeddingError> {
+	let max_len = encodings.iter().map(|e| token_count(e)).max().unwrap_or(0);
+	let batch_size = encodings.len();
+
+	let mut input_ids = vec![0i64; batch_size * max_len];
+	let mut attention_mask = vec![0i64; batch_size * max_len];
+	let token_type_ids = vec![0i64; batch_size * max_len];
+
+	for (i, enc) in encodings.iter().enumerate() {
+		let ids = enc.get_ids();
+		let mask = enc.get_attention_mask();
+		let offset = i * max_len;
+		for (j, (&id, &m)) in ids.iter().zip(mask).take(token_count(enc)).enumerate() {
+			input_ids[offset + j] = i64::from(id);
+			attention_mask[offset + j] = i64::from(m);
+		}
+	}
+
+	Ok((
+		Array2::from_shape_vec([batch_size, max_len], input_ids)?,
+		Array2::from_shape_vec([batch_size, max_len], attention_mask)?,
+		Array2::from_shape_vec([batch_size, max_len], token_type_ids)?,
+	))
+}
+
+/// Number of real (unpadded) tokens in an encoding. Padding is on the right.
+fn token_count(encoding: &Encoding) -> usize {
+	encoding
+		.get_attention_mask()
+		.iter()
+		.filter(|&&m| m == 1)
+		.count()
+}
+
+/// Group texts (by token length) into sub-batches for `embed_batch_chunked`.
+///
+/// Indices are sorted by length so each group pads little, then packed
+/// greedily while `group size × longest length` stays within
+/// `max_batch_tokens`. Every index appears in exactly one group.
+fn plan_token_batches(lengths: &[usize], max_batch_tokens: usize) -> Vec<Vec<usize>> {
+	let mut order: Vec<usize> = (0..lengths.len()).collect();
+	order.sort_by_key(|&i| lengths[i]);
+
+	let mut groups: Vec<Vec<usize>> = Vec::new();
+	let mut current: Vec<usize> = Vec::new();
+	for i in order {
+		// Sorted ascending, so this text is the longest in the group so far
+		let padded = (current.len() + 1) * lengths[i];
+		if !current.is_empty() && padded > max_batch_tokens {
+			groups.push(std::mem::take(&mut current));
+		}
+		current.push(i);
+	}
+	if !current.is_empty() {
+		groups.push(current);
+	}
+
+	groups
+}
+
 /// Check if the default model files are available.
 pub fn is_model_available() -> bool {
 	EmbeddingModel::is_available(&EmbeddingModelConfig::default())
@@ -293,15 +400,69 @@ mod tests {
 		assert!(tokenizer_path.to_string_lossy().contains("tokenizer"));
 	}
 
+	#[test]
+	fn test_plan_token_batches_mixed_lengths() {
+		let lengths = [4, 500, 3, 6, 480, 5];
+		let groups = plan_token_batches(&lengths, 512);
+
+		// Every input lands in exactly one group
+		let mut seen: Vec<usize> = groups.iter().flatten().copied().collect();
+		seen.sort_unstable();
+		assert_eq!(seen, (0..lengths.len()).collect::<Vec<_>>());
+
+		// Each group fits the budget (or is a single oversized text)
+		for group in &groups {
+			let longest = group.iter().map(|&i| lengths[i]).max().unwrap();
+			assert!(group.len() == 1 || group.len() * longest <= 512);
+		}
+
+		// The long texts don't share a batch with the short ones
+		let long_group = groups.iter().find(|g| g.contains(&1)).unwrap();
+		assert!(!long_group.contains(&2));
+	}
+
 	#[test]
 	fn test_is_available_false_without_files() {
 		let config = EmbeddingModelConfig {
 			model_path: Some(PathBuf::from("/nonexistent/model.onnx")),
 			tokenizer_path: Some(PathBuf::from("/nonexistent/tokenizer.json")),
+			..Default::default()
 		};
 		assert!(!EmbeddingModel::is_available(&config));
 	}
 
+	#[test]
+	fn test_long_input_sequence_dimension_is_capped() {
+		use std::collections::HashMap;
+		use tokenizers::models::wordlevel::WordLevel;
+		use tokenizers::pre_tokenizers::whitespace::Whitespace;
+
+		let vocab = HashMap::from([("[UNK]".to_string(), 0), ("memory".to_string(), 1)]);
+		let model = WordLevel::builder()
+			.vocab(vocab)
+			.unk_token("[UNK]".to_string())
+			.build()
+			.unwrap();
+		let mut tokenizer = Tokenizer::new(model);
+		let _ = tokenizer.with_pre_tokenizer(Some(Whitespace));
+		set_max_sequence_length(&mut tokenizer, 512).unwrap();
+
+		let long = "memory ".repeat(2000);
+		let encodings = tokenizer
+			.encode_batch(vec![long.as_str(), "memory memory"], true)
+			.unwrap();
+		let refs: Vec<&Encoding> = encodings.iter().collect();
+		let (input_ids, attention_mask, _) = padded_inputs(&refs).unwrap();
+
+		assert_eq!(input_ids.shape(), &[2, 512]);
+		assert_eq!(attention_mask.row(1).sum(), 2);
+
+		// 0 leaves long inputs whole
+		set_max_sequence_length(&mut tokenizer, 0).unwrap();
+		let encoding = tokenizer.encode(long.as_str(), true).unwrap();
+		assert_eq!(token_count(&encoding), 2000);
+	}
+
 	// Integration tests require actual model files — run with:
 	// cargo test --features embedding -- --ignored
 	#[test]
@@ -338,4 +499,31 @@ mod tests {
 			.sum();
 		assert!(dot < 0.99, "Different texts should have similarity < 0.99");
 	}
+
+	#[test]
+	#[ignore = "requires model files on disk"]
+	fn test_embed_batch_chunked_preserves_order() {
+		let model =
+			EmbeddingModel::load(&EmbeddingModelConfig::default()).expect("Failed to load model");
+		let long = "memory ".repeat(300);
+		let texts = [
+			"hi",
+			long.as_str(),
+			"Rust",
+			"a short sentence",
+			long.as_str(),
+			"ok",
+		];
+		let chunked = model
+			.embed_batch_chunked(&texts, 64)
+			.expect("Failed to embed chunked");
+
+		assert_eq!(chunked.len(), texts.len());
+		for (text, embedding) in texts.iter().zip(&chunked) {
+			assert_eq!(embedding.len(), 768);
+			let single = model.embed(text).expect("Failed to embed");
+			let dot: f32 = single.iter().zip(embedding).map(|(a, b)| a * b).sum();
+			assert!(dot > 0.999, "chunked result out of order for {text:?}");
+		}
+	}
 }
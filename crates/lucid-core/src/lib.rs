@@ -50,10 +50,7 @@
 //! ## Example
 //!
 //! ```rust
-//! use lucid_core::{
-//!     retrieval::{retrieve, RetrievalConfig, RetrievalInput},
-//!     spreading::Association,
-//! };
+//! use lucid_core::retrieval::{retrieve, RetrievalConfig, RetrievalInput};
 //!
 //! // Your memory embeddings (from any embedding model)
 //! let memories = vec![
@@ -65,16 +62,12 @@
 //! // Probe embedding (what you're looking for)
 //! let probe = vec![0.9, 0.1, 0.0];
 //!
-//! let input = RetrievalInput {
-//!     probe_embedding: &probe,
-//!     memory_embeddings: &memories,
-//!     access_histories_ms: &[vec![1000.0], vec![500.0], vec![100.0]],
-//!     emotional_weights: &[0.5, 0.5, 0.5],
-//!     decay_rates: &[0.5, 0.5, 0.5],
-//!     working_memory_boosts: &[1.0, 1.0, 1.0],  // 1.0 = no boost, up to 2.0
-//!     associations: &[],  // Optional: links between memories
-//!     current_time_ms: 2000.0,
-//! };
+//! let histories = [vec![1000.0], vec![500.0], vec![100.0]];
+//! let input = RetrievalInput::new(&probe, &memories, &histories, 2000.0)
+//!     .with_emotional_weights(&[0.5, 0.5, 0.5])
+//!     .with_decay_rates(&[0.5, 0.5, 0.5])
+//!     .with_working_memory_boosts(&[1.0, 1.0, 1.0]);  // 1.0 = no boost, up to 2.0
+//! // Optional: `.with_associations(..)` to link memories
 //!
 //! let config = RetrievalConfig::default();
 //! let results = retrieve(&input, &config);
@@ -116,14 +109,18 @@ pub mod activation;
 pub mod embedding;
 pub mod location;
 pub mod retrieval;
+pub mod session;
 pub mod spreading;
 pub mod visual;
 
 pub use activation::{
 	combine_activations,
+	combine_activations_with_emotional_scale,
+	combine_activations_with_mode,
 	// Association Decay
 	compute_association_decay,
 	compute_base_level,
+	compute_base_level_capped,
 	// Reconsolidation
 	compute_effective_thresholds,
 	// Instance Noise
@@ -136,55 +133,90 @@ pub use activation::{
 	compute_working_memory_boost,
 	compute_working_memory_boost_batch,
 	cosine_similarity,
+	cosine_similarity_top_k,
+	decay_associations,
 	get_decay_tau,
 	nonlinear_activation,
 	pe_zone,
+	preview_base_level_decay,
 	reconsolidation_probability,
+	record_access,
 	reinforce_association,
+	reinforce_coaccess,
 	retrieval_probability,
 	should_prune_association,
+	similarity_batch,
 	ActivationBreakdown,
 	ActivationConfig,
 	AssociationDecayConfig,
 	AssociationState,
+	CombineMode,
 	InstanceNoiseConfig,
 	ReconsolidationConfig,
+	SimilarityMetric,
 	WorkingMemoryConfig,
 	BETA_RECON,
+	MIN_NOISE_PARAMETER,
 	THETA_HIGH,
 	THETA_LOW,
 };
-pub use retrieval::{retrieve, RetrievalCandidate, RetrievalConfig, RetrievalInput};
+pub use retrieval::{
+	estimate_retrieval_cost, retrieval_result_to_json, retrieve, retrieve_checked,
+	retrieve_full_scores, retrieve_multi_probe, retrieve_with_diagnostics, retrieve_with_session,
+	retrieve_with_trace, DimensionMismatchError, FullScoreTable, LengthMismatch, MultiProbeMode,
+	OwnedRetrievalInput, ReconsolidationInput, RetrievalCandidate, RetrievalConfig,
+	RetrievalCostEstimate, RetrievalDiagnostics, RetrievalInput, RetrievalInputError,
+	SpreadingNormalization, SpreadingSeedMode, TracedCandidate,
+};
+pub use session::RetrievalSession;
 pub use spreading::{
+	compact_graph,
 	// Temporal Spreading (Episodic Memory)
 	compute_temporal_link_strength,
+	consolidate_episode,
 	create_episode_links,
+	detect_cycles,
 	find_temporal_neighbors,
+	graph_stats,
+	merge_associations,
+	merge_associations_with_mode,
 	spread_activation,
+	spread_activation_traced,
 	spread_temporal_activation,
+	spread_temporal_activation_from_seeds,
 	spread_temporal_activation_multi,
 	Association,
+	AssociationMergeMode,
+	EpisodeConsolidation,
+	FanNormalization,
+	GraphStats,
 	SpreadingConfig,
 	SpreadingResult,
 	TemporalLink,
 	TemporalSpreadingConfig,
 	TemporalSpreadingResult,
+	REMOVED_NODE,
 };
 
 // Location Intuitions (spatial memory)
 pub use location::{
-	compute_association_strength, compute_batch_decay, compute_decayed_familiarity,
-	compute_familiarity, get_associated_locations, infer_activity_type, initial_familiarity,
-	is_well_known, spread_location_activation, ActivityInference, ActivityType, InferenceSource,
-	LocationAssociation, LocationConfig, LocationIntuition,
+	compute_association_strength, compute_batch_decay, compute_batch_decay_with_transitions,
+	compute_decayed_familiarity, compute_familiarity, get_associated_locations,
+	increment_familiarity, infer_activity_type, infer_activity_type_with_keywords,
+	initial_familiarity, is_well_known, spread_location_activation, ActivityInference,
+	ActivityType, InferenceSource, LocationAssociation, LocationConfig, LocationIntuition,
 };
 
 // Visual Memory
 pub use visual::{
-	compute_pruning_candidates, compute_tag_strength, prepare_frame_description_prompt,
-	prepare_synthesis_prompt, retrieve_visual, select_frames_for_description, should_prune,
-	should_tag, ConsolidationState, ConsolidationWindow, EmotionalContext, FrameCandidate,
-	FrameDescriptionConfig, FrameDescriptionResult, PruningCandidate, PruningReason, TagReason,
+	compute_decayed_significance, compute_pruning_candidates, compute_tag_strength,
+	consolidation_duration_for, infer_emotional_prior, prepare_frame_description_prompt,
+	prepare_synthesis_prompt, retrieve_multimodal, retrieve_visual, select_frames_for_description,
+	select_frames_for_description_with_config, should_prune, should_prune_with_scene_change,
+	should_tag, visual_memories_to_retrieval_input, visual_retrieval_result_to_json,
+	ConsolidationState, ConsolidationWindow, EmotionalContext, FrameCandidate,
+	FrameDescriptionConfig, FrameDescriptionResult, FrameSelectionConfig, Modality,
+	MultimodalCandidate, PruningBreakdown, PruningCandidate, PruningReason, TagReason,
 	TranscriptSegment, VisualConfig, VisualConsolidationState, VisualMemory,
 	VisualRetrievalCandidate, VisualRetrievalConfig, VisualRetrievalInput, VisualSource, VisualTag,
 };
@@ -221,6 +253,7 @@ mod tests {
 			working_memory_boosts: &[1.0, 1.0, 1.0],
 			associations: &[],
 			current_time_ms: now,
+			reconsolidation: None,
 		};
 
 		let config = RetrievalConfig {
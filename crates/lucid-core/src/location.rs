@@ -139,6 +139,10 @@ pub struct LocationConfig {
 
 	/// Backward association strength factor (relative to forward)
 	pub backward_strength_factor: f64,
+
+	/// Decay rate multiplier for pinned locations, relative to `max_decay_rate`
+	/// (0 = pinned locations never decay)
+	pub pinned_decay_multiplier: f64,
 }
 
 impl Default for LocationConfig {
@@ -156,6 +160,7 @@ impl Default for LocationConfig {
 			time_same_activity_multiplier: 2.0,
 			time_diff_activity_multiplier: 1.0,
 			backward_strength_factor: 0.7,
+			pinned_decay_multiplier: 0.0,
 		}
 	}
 }
@@ -194,6 +199,33 @@ pub fn compute_familiarity(access_count: u32, config: &LocationConfig) -> f64 {
 	1.0 - 1.0 / config.familiarity_k.mul_add(n, 1.0)
 }
 
+/// Apply one more access to an existing familiarity value.
+///
+/// Inverts the familiarity curve to recover the effective access count,
+/// `n = f / (k * (1 - f))`, then returns `f(n + 1)`. Useful when only the
+/// stored familiarity is known and the access count was never tracked.
+///
+/// # Example
+///
+/// ```rust
+/// use lucid_core::location::{compute_familiarity, increment_familiarity, LocationConfig};
+///
+/// let config = LocationConfig::default();
+/// let after = increment_familiarity(compute_familiarity(9, &config), &config);
+/// assert!((after - compute_familiarity(10, &config)).abs() < 1e-9);
+/// ```
+#[must_use]
+pub fn increment_familiarity(current_familiarity: f64, config: &LocationConfig) -> f64 {
+	if config.familiarity_k <= 0.0 || current_familiarity >= 1.0 {
+		return current_familiarity;
+	}
+
+	// NaN and negative values are treated as never accessed
+	let familiarity = current_familiarity.max(0.0);
+	let effective_count = familiarity / (config.familiarity_k * (1.0 - familiarity));
+	1.0 - 1.0 / config.familiarity_k.mul_add(effective_count + 1.0, 1.0)
+}
+
 /// Compute familiarity for first access (aligns with curve).
 #[inline]
 #[must_use]
@@ -208,7 +240,9 @@ pub fn initial_familiarity(config: &LocationConfig) -> f64 {
 /// Compute decayed familiarity for a single location.
 ///
 /// Continuous decay function - rate decreases as familiarity increases:
-/// - Pinned locations never decay (explicit user protection)
+/// - Pinned locations never decay (explicit user protection), unless
+///   `pinned_decay_multiplier` > 0, in which case they decay at a flat
+///   `maxDecay * pinnedDecayMultiplier` down to the same floor
 /// - High familiarity locations decay slowly (procedural memory is sticky)
 /// - Low familiarity locations decay quickly (weak traces fade)
 /// - Well-known locations have elevated floors
@@ -242,8 +276,8 @@ pub fn compute_decayed_familiarity(
 	is_pinned: bool,
 	config: &LocationConfig,
 ) -> f64 {
-	// Pinned locations never decay
-	if is_pinned {
+	// Pinned locations never decay unless soft pinning is configured
+	if is_pinned && config.pinned_decay_multiplier <= 0.0 {
 		return current_familiarity;
 	}
 
@@ -260,9 +294,13 @@ pub fn compute_decayed_familiarity(
 		return current_familiarity;
 	}
 
-	// Continuous decay rate (decreases with familiarity)
-	let decay_rate =
-		config.max_decay_rate * current_familiarity.mul_add(-config.decay_dampening, 1.0);
+	// Continuous decay rate (decreases with familiarity); soft pins use a flat,
+	// much slower rate
+	let decay_rate = if is_pinned {
+		config.max_decay_rate * config.pinned_decay_multiplier
+	} else {
+		config.max_decay_rate * current_familiarity.mul_add(-config.decay_dampening, 1.0)
+	};
 
 	// Sliding floor (higher for well-known locations)
 	let floor = if current_familiarity > 0.5 {
@@ -304,6 +342,29 @@ pub fn compute_batch_decay(
 		.collect()
 }
 
+/// Batch compute decay, also flagging locations that lost "well-known"
+/// status.
+///
+/// Returns `(familiarity, demoted)` per location in input order, where
+/// `demoted` is true when the location was at or above
+/// `well_known_threshold` before decay and is below it afterwards.
+#[must_use]
+pub fn compute_batch_decay_with_transitions(
+	locations: &[LocationIntuition],
+	current_time_ms: f64,
+	config: &LocationConfig,
+) -> Vec<(f64, bool)> {
+	locations
+		.iter()
+		.zip(compute_batch_decay(locations, current_time_ms, config))
+		.map(|(loc, familiarity)| {
+			let demoted =
+				is_well_known(loc.familiarity, config) && !is_well_known(familiarity, config);
+			(familiarity, demoted)
+		})
+		.collect()
+}
+
 // ============================================================================
 // Activity Type Inference
 // ============================================================================
@@ -344,6 +405,25 @@ pub fn infer_activity_type(
 	context: &str,
 	tool_name: Option<&str>,
 	explicit: Option<ActivityType>,
+) -> ActivityInference {
+	infer_activity_type_with_keywords(context, tool_name, explicit, &[])
+}
+
+/// Confidence of a match on a caller-supplied keyword.
+const CUSTOM_KEYWORD_CONFIDENCE: f64 = 0.9;
+
+/// Infer activity type, consulting caller-supplied keywords first.
+///
+/// `extra_keywords` extends the built-in table with domain vocabulary
+/// (e.g. "deploy" or "migrate"). They are checked in order after explicit
+/// types and before the built-in keywords, and match case-insensitively;
+/// otherwise precedence is as in [`infer_activity_type`].
+#[must_use]
+pub fn infer_activity_type_with_keywords(
+	context: &str,
+	tool_name: Option<&str>,
+	explicit: Option<ActivityType>,
+	extra_keywords: &[(ActivityType, &[&str])],
 ) -> ActivityInference {
 	// 1. Explicit always wins
 	if let Some(activity) = explicit {
@@ -356,9 +436,22 @@ pub fn infer_activity_type(
 		}
 	}
 
-	// 2. Keyword-based inference (intent indicators)
+	// 2. Keyword-based inference (intent indicators), custom keywords first
 	let lower = context.to_lowercase();
 
+	for (activity_type, keywords) in extra_keywords {
+		if keywords
+			.iter()
+			.any(|kw| !kw.is_empty() && lower.contains(&kw.to_lowercase()))
+		{
+			return ActivityInference {
+				activity_type: *activity_type,
+				source: InferenceSource::Keyword,
+				confidence: CUSTOM_KEYWORD_CONFIDENCE,
+			};
+		}
+	}
+
 	let keyword_matches: &[(ActivityType, &[&str], f64)] = &[
 		(
 			ActivityType::Debugging,
@@ -524,7 +617,11 @@ pub fn get_associated_locations(
 		.map(|a| (a.target, a.strength))
 		.collect();
 
-	results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+	results.sort_by(|a, b| {
+		b.1.partial_cmp(&a.1)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.0.cmp(&b.0))
+	});
 	results.truncate(limit);
 	results
 }
@@ -563,6 +660,23 @@ mod tests {
 		assert!(compute_familiarity(1000, &config) < 1.0);
 	}
 
+	#[test]
+	fn increment_familiarity_matches_next_access() {
+		let config = LocationConfig::default();
+
+		for n in [0, 1, 5, 10, 24, 100] {
+			let incremented = increment_familiarity(compute_familiarity(n, &config), &config);
+			assert!((incremented - compute_familiarity(n + 1, &config)).abs() < 1e-9);
+		}
+
+		// Invalid input starts from zero accesses
+		assert!(
+			(increment_familiarity(f64::NAN, &config) - initial_familiarity(&config)).abs() < 1e-9
+		);
+		assert!((increment_familiarity(-0.5, &config) - initial_familiarity(&config)).abs() < 1e-9);
+		assert_eq!(increment_familiarity(1.0, &config), 1.0);
+	}
+
 	#[test]
 	fn decay_respects_stale_threshold() {
 		let config = LocationConfig::default();
@@ -603,6 +717,33 @@ mod tests {
 		assert_eq!(decayed, 0.5);
 	}
 
+	#[test]
+	fn soft_pinned_locations_decay_slowly() {
+		let current_time = 1000.0 * 60.0 * 60.0 * 24.0 * 365.0; // Day 365
+		let very_old = 0.0; // Day 0
+
+		// Multiplier 0.0 keeps pins absolute
+		let hard = LocationConfig {
+			pinned_decay_multiplier: 0.0,
+			..Default::default()
+		};
+		assert_eq!(
+			compute_decayed_familiarity(0.8, very_old, current_time, true, &hard),
+			0.8
+		);
+
+		// Multiplier 0.1 decays slightly, and far less than an unpinned location
+		let soft = LocationConfig {
+			pinned_decay_multiplier: 0.1,
+			..Default::default()
+		};
+		let pinned = compute_decayed_familiarity(0.8, very_old, current_time, true, &soft);
+		let unpinned = compute_decayed_familiarity(0.8, very_old, current_time, false, &soft);
+		assert!(pinned < 0.8);
+		assert!((pinned - 0.8 * (1.0 - 0.01)).abs() < 1e-12);
+		assert!(pinned > unpinned);
+	}
+
 	#[test]
 	fn handles_invalid_timestamps() {
 		let config = LocationConfig::default();
@@ -649,6 +790,34 @@ mod tests {
 		assert_eq!(result.source, InferenceSource::Default);
 	}
 
+	#[test]
+	fn custom_keywords_override_tool_inference() {
+		let custom: &[(ActivityType, &[&str])] =
+			&[(ActivityType::Writing, &["deploy", "Migrate", "provision"])];
+
+		// Without custom keywords the tool decides
+		let result = infer_activity_type("migrate the schema", Some("Read"), None);
+		assert_eq!(result.activity_type, ActivityType::Reading);
+		assert_eq!(result.source, InferenceSource::Tool);
+
+		let result =
+			infer_activity_type_with_keywords("migrate the schema", Some("Read"), None, custom);
+		assert_eq!(result.activity_type, ActivityType::Writing);
+		assert_eq!(result.source, InferenceSource::Keyword);
+
+		// Custom keywords beat built-in ones, but not explicit types
+		let result = infer_activity_type_with_keywords("debug the deploy", None, None, custom);
+		assert_eq!(result.activity_type, ActivityType::Writing);
+		let result = infer_activity_type_with_keywords(
+			"deploy",
+			None,
+			Some(ActivityType::Reviewing),
+			custom,
+		);
+		assert_eq!(result.activity_type, ActivityType::Reviewing);
+		assert_eq!(result.source, InferenceSource::Explicit);
+	}
+
 	#[test]
 	fn task_associations_stronger_than_time() {
 		let config = LocationConfig::default();
@@ -717,6 +886,22 @@ mod tests {
 		assert_eq!(results[2], (3, 0.3));
 	}
 
+	#[test]
+	fn get_associated_breaks_ties_by_target() {
+		let association = |target| LocationAssociation {
+			source: 0,
+			target,
+			strength: 0.5,
+			co_access_count: 1,
+		};
+		let associations = vec![association(7), association(2), association(5)];
+
+		let results = get_associated_locations(0, &associations, 10);
+
+		let targets: Vec<u32> = results.iter().map(|&(target, _)| target).collect();
+		assert_eq!(targets, vec![2, 5, 7]);
+	}
+
 	#[test]
 	fn batch_decay_applies_to_all() {
 		let config = LocationConfig::default();
@@ -747,4 +932,40 @@ mod tests {
 		assert!(decayed[0] < 0.8); // Decayed
 		assert_eq!(decayed[1], 0.5); // Pinned - unchanged
 	}
+
+	#[test]
+	fn batch_decay_flags_demoted_locations() {
+		let config = LocationConfig::default();
+		let current_time = 1000.0 * 60.0 * 60.0 * 24.0 * 100.0;
+		let old_time = current_time - (60.0 * 24.0 * 60.0 * 60.0 * 1000.0); // 60 days ago
+
+		let location = |id, familiarity, is_pinned| LocationIntuition {
+			id,
+			familiarity,
+			access_count: 20,
+			searches_saved: 0,
+			last_accessed_ms: old_time,
+			is_pinned,
+		};
+		let locations = vec![
+			location(0, 0.72, false), // Well-known, decays below the threshold
+			location(1, 0.72, true),  // Well-known but pinned
+			location(2, 0.5, false),  // Never well-known
+		];
+
+		let results = compute_batch_decay_with_transitions(&locations, current_time, &config);
+		let plain = compute_batch_decay(&locations, current_time, &config);
+
+		assert!(results[0].0 < config.well_known_threshold);
+		assert_eq!(
+			results
+				.iter()
+				.map(|&(_, demoted)| demoted)
+				.collect::<Vec<_>>(),
+			vec![true, false, false]
+		);
+		for ((familiarity, _), expected) in results.iter().zip(plain) {
+			assert_eq!(*familiarity, expected);
+		}
+	}
 }
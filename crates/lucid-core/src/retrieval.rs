@@ -11,10 +11,15 @@
 use serde::{Deserialize, Serialize};
 
 use crate::activation::{
-	combine_activations, compute_base_level, cosine_similarity, cosine_similarity_batch,
-	nonlinear_activation_batch, retrieval_probability,
+	combine_activations_with_emotional_scale, compute_base_level_capped,
+	compute_effective_thresholds, cosine_similarity, cosine_similarity_batch,
+	nonlinear_activation_batch, pe_zone, retrieval_latency, retrieval_probability,
+	similarity_batch, CombineMode, ReconsolidationConfig, SimilarityMetric,
+};
+use crate::session::RetrievalSession;
+use crate::spreading::{
+	spread_activation, Association, FanNormalization, SpreadingConfig, SpreadingResult,
 };
-use crate::spreading::{spread_activation, Association, SpreadingConfig, SpreadingResult};
 
 /// A memory candidate with all activation components.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -33,10 +38,144 @@ pub struct RetrievalCandidate {
 	pub total_activation: f64,
 	/// Retrieval probability (0-1)
 	pub probability: f64,
+	/// Estimated retrieval latency in milliseconds (lower for stronger memories)
+	pub latency_ms: f64,
+	/// `total_activation` min-max scaled to 0-1 across the returned
+	/// candidates, when `RetrievalConfig::normalize_activations` is set
+	pub normalized_activation: Option<f64>,
+}
+
+/// How a memory's seed activation for spreading is derived.
+///
+/// Text and visual retrieval used to seed spreading with separately coded
+/// formulas (multiplicative vs. additive). Both paths now share this setting
+/// and its formulas, while keeping their original defaults: `Multiplicative`
+/// for text and `Additive` for visual retrieval.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpreadingSeedMode {
+	/// `probe × emotional × (1 + recency)`, seeding only when the probe
+	/// activation is above the floor (0.1 by default)
+	#[default]
+	Multiplicative,
+	/// `(base + probe) × emotional`, seeding only when the result is above the
+	/// floor (0 by default)
+	Additive,
+}
+
+impl SpreadingSeedMode {
+	/// The seed floor each mode used before it became configurable.
+	#[must_use]
+	pub const fn default_floor(self) -> f64 {
+		match self {
+			Self::Multiplicative => 0.1,
+			Self::Additive => 0.0,
+		}
+	}
+
+	/// Seed activation for one memory, or `None` if it should not seed
+	/// spreading, using the mode's [`default_floor`](Self::default_floor).
+	///
+	/// # Arguments
+	///
+	/// * `base_level` - Base-level activation (non-finite values are treated as -10)
+	/// * `probe_activation` - Probe-trace activation (cubed similarity)
+	/// * `emotional_weight` - Emotional weight (0-1)
+	#[must_use]
+	pub fn seed_activation(
+		self,
+		base_level: f64,
+		probe_activation: f64,
+		emotional_weight: f64,
+	) -> Option<f64> {
+		self.seed_activation_with_floor(
+			base_level,
+			probe_activation,
+			emotional_weight,
+			self.default_floor(),
+		)
+	}
+
+	/// Seed activation like [`seed_activation`](Self::seed_activation), with
+	/// an explicit floor. `Multiplicative` compares the probe activation
+	/// against it; `Additive` compares the combined activation.
+	#[must_use]
+	pub fn seed_activation_with_floor(
+		self,
+		base_level: f64,
+		probe_activation: f64,
+		emotional_weight: f64,
+		floor: f64,
+	) -> Option<f64> {
+		let base = if base_level.is_finite() {
+			base_level
+		} else {
+			-10.0
+		};
+		let emotional_multiplier = 1.0 + (emotional_weight - 0.5);
+
+		match self {
+			Self::Multiplicative => {
+				// Normalize base-level to [0, 1] for multiplicative boost
+				let recency_boost = ((base + 10.0) / 10.0).clamp(0.0, 1.0);
+				(probe_activation > floor)
+					.then_some(probe_activation * emotional_multiplier * (1.0 + recency_boost))
+			}
+			Self::Additive => {
+				let activation = (base + probe_activation) * emotional_multiplier;
+				(activation > floor).then_some(activation)
+			}
+		}
+	}
+}
+
+/// Rescaling applied to spreading activations before they are combined.
+///
+/// Raw spreading grows with graph density and seed strength, so the same
+/// config lets spreading dominate in one corpus and vanish in another.
+/// Normalizing bounds it to `[0, 1]` so its weight in `total_activation` is
+/// comparable across graphs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum SpreadingNormalization {
+	/// Use raw spreading activations
+	#[default]
+	None,
+	/// Divide by the largest spreading activation
+	MaxNormalize,
+	/// Softmax over reached memories at the given temperature, rescaled so
+	/// the strongest gets 1. Lower temperatures favor the strongest memory.
+	SoftmaxTemperature(f64),
+}
+
+impl SpreadingNormalization {
+	/// Normalize spreading activations in place.
+	///
+	/// Memories that received no spreading stay at 0 in every mode.
+	pub fn apply(self, activations: &mut [f64]) {
+		let max = activations.iter().copied().fold(0.0_f64, f64::max);
+		if max <= 0.0 {
+			return;
+		}
+
+		match self {
+			Self::None => {}
+			Self::MaxNormalize => {
+				for a in activations.iter_mut() {
+					*a /= max;
+				}
+			}
+			Self::SoftmaxTemperature(temperature) => {
+				let temperature = temperature.max(f64::MIN_POSITIVE);
+				for a in activations.iter_mut().filter(|a| **a > 0.0) {
+					*a = ((*a - max) / temperature).exp();
+				}
+			}
+		}
+	}
 }
 
 /// Configuration for retrieval.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // Independent opt-in switches, not a state machine
 pub struct RetrievalConfig {
 	/// Decay rate for base-level activation
 	pub decay_rate: f64,
@@ -52,8 +191,59 @@ pub struct RetrievalConfig {
 	pub min_probability: f64,
 	/// Maximum results to return
 	pub max_results: usize,
+	/// Ranked results to skip before taking `max_results`, for paging
+	/// (page `n` is `offset = n × max_results`)
+	pub offset: usize,
 	/// Whether to spread bidirectionally
 	pub bidirectional: bool,
+	/// Multiplier on backward spreading, as in [`SpreadingConfig`]
+	pub backward_decay: f64,
+	/// How seed activations for spreading are computed
+	pub seed_mode: SpreadingSeedMode,
+	/// Number of top-activated memories that seed spreading. Dense graphs may
+	/// need more to reach every relevant cluster; sparse ones can use fewer.
+	pub max_spreading_seeds: usize,
+	/// Memories must be above this to seed spreading; raise it to keep weak
+	/// matches from seeding noise. What it is compared against depends on
+	/// `seed_mode` (`None` = that mode's
+	/// [`default_floor`](SpreadingSeedMode::default_floor)).
+	pub seed_activation_floor: Option<f64>,
+	/// How spreading activations are rescaled before combining
+	pub spreading_normalization: SpreadingNormalization,
+	/// Most recent accesses per memory summed for base-level (`None` = all);
+	/// histories are oldest first and older accesses are dropped
+	pub max_history: Option<usize>,
+	/// How probe-trace similarity is measured
+	pub similarity_metric: SimilarityMetric,
+	/// Promise that the probe and memory embeddings are unit length, so
+	/// cosine similarity reduces to a dot product and norms are skipped.
+	/// Debug builds check the probe and first memory.
+	pub embeddings_prenormalized: bool,
+	/// How base-level, probe and spreading activation form the total
+	pub combine_mode: CombineMode,
+	/// How strongly emotional weight modulates probe activation: the
+	/// multiplier is `1 + scale × (emotional_weight - 0.5)` (1.0 = [0.5, 1.5])
+	pub emotional_multiplier_scale: f64,
+	/// Added to the total activation of memories with no access history, so
+	/// brand-new memories get a temporary novelty boost (0 = off)
+	pub cold_start_boost: f64,
+	/// Latency scaling factor `F` in `F × e^(-A) × 1000`; calibrate to the
+	/// activation range your embeddings produce
+	pub latency_factor: f64,
+	/// Scales the noise parameter when computing the *reported* probability
+	/// (1.0 = identity). Filtering by `min_probability` and ranking still use
+	/// the uncalibrated value, so this only changes the displayed numbers.
+	pub probability_temperature: f64,
+	/// Fill in each candidate's `normalized_activation` by min-max scaling
+	/// the returned candidates' total activations to 0-1
+	pub normalize_activations: bool,
+	/// Make [`retrieve_checked`] fail when any memory embedding's dimension
+	/// differs from the probe's, instead of quietly skipping it
+	pub strict_dimensions: bool,
+	/// Ceiling on similarity after the working memory boost (1.0 = valid
+	/// cosine range). Above 1, boosted similarities may deliberately leave
+	/// that range so near-identical memories still differ by WM boost.
+	pub wm_cap: f64,
 }
 
 impl Default for RetrievalConfig {
@@ -66,7 +256,24 @@ impl Default for RetrievalConfig {
 			spreading_decay: 0.7,
 			min_probability: 0.1,
 			max_results: 10,
+			offset: 0,
 			bidirectional: true,
+			backward_decay: 0.7,
+			seed_mode: SpreadingSeedMode::default(),
+			max_spreading_seeds: 5,
+			seed_activation_floor: None,
+			spreading_normalization: SpreadingNormalization::default(),
+			max_history: None,
+			similarity_metric: SimilarityMetric::default(),
+			embeddings_prenormalized: false,
+			combine_mode: CombineMode::default(),
+			emotional_multiplier_scale: 1.0,
+			cold_start_boost: 0.0,
+			latency_factor: 1.0,
+			probability_temperature: 1.0,
+			normalize_activations: false,
+			strict_dimensions: false,
+			wm_cap: 1.0,
 		}
 	}
 }
@@ -91,6 +298,325 @@ pub struct RetrievalInput<'a> {
 	pub associations: &'a [Association],
 	/// Current time (ms)
 	pub current_time_ms: f64,
+	/// Prediction error of this probe, for reconsolidation-aware decay
+	/// (`None` = every memory uses its decay rate as given)
+	pub reconsolidation: Option<ReconsolidationInput<'a>>,
+}
+
+/// Reconsolidation sidecar for [`RetrievalInput`].
+///
+/// A probe that surprises a memory moderately puts it in the reconsolidation
+/// zone, where the trace is labile again. Those memories decay at
+/// `reconsolidating_decay_rate` for this retrieval; memories in the reinforce
+/// and new-trace zones keep their usual rate. Zones use the per-memory
+/// effective thresholds from [`compute_effective_thresholds`].
+#[derive(Clone, Debug)]
+pub struct ReconsolidationInput<'a> {
+	/// Prediction error (surprise) of the current probe, 0-1
+	pub surprise: f64,
+	/// How many times each memory has been accessed
+	pub access_counts: &'a [u32],
+	/// Days since each memory was last accessed
+	pub days_since_access: &'a [f64],
+	/// Base-level decay rate for memories in the reconsolidation zone
+	pub reconsolidating_decay_rate: f64,
+	/// Zone thresholds and their modulators
+	pub config: ReconsolidationConfig,
+}
+
+impl ReconsolidationInput<'_> {
+	/// Decay rate override for memory `index`: `Some` if the probe puts it in
+	/// the reconsolidation zone, `None` otherwise or if its counts are missing.
+	#[must_use]
+	pub fn decay_rate_for(&self, index: usize) -> Option<f64> {
+		let access_count = *self.access_counts.get(index)?;
+		let days = *self.days_since_access.get(index)?;
+		let (theta_low, theta_high) = compute_effective_thresholds(
+			self.config.theta_low,
+			self.config.theta_high,
+			access_count,
+			days,
+			&self.config,
+		);
+		(pe_zone(self.surprise.abs(), theta_low, theta_high) == "reconsolidate")
+			.then_some(self.reconsolidating_decay_rate)
+	}
+}
+
+/// A parallel input array whose length doesn't match `memory_embeddings`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LengthMismatch {
+	/// Name of the offending input field
+	pub field: &'static str,
+	/// Expected length (number of memories)
+	pub expected: usize,
+	/// Actual length
+	pub actual: usize,
+}
+
+/// Error returned by [`RetrievalInput::validate`] and
+/// [`VisualRetrievalInput::validate`](crate::visual::VisualRetrievalInput::validate).
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("ragged retrieval input: {}", format_mismatches(.mismatches))]
+pub struct RetrievalInputError {
+	/// Every mismatched field, in declaration order
+	pub mismatches: Vec<LengthMismatch>,
+}
+
+fn format_mismatches(mismatches: &[LengthMismatch]) -> String {
+	mismatches
+		.iter()
+		.map(|m| {
+			format!(
+				"{} has {} entries, expected {}",
+				m.field, m.actual, m.expected
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("; ")
+}
+
+/// Error returned by [`RetrievalInput::check_dimensions`] and, with
+/// `strict_dimensions`, by [`retrieve_checked`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error(
+	"{} memory embedding(s) don't match the {probe_dimension}-dimensional probe: indices {mismatched_indices:?}",
+	.mismatched_indices.len()
+)]
+pub struct DimensionMismatchError {
+	/// Dimension of the probe embedding
+	pub probe_dimension: usize,
+	/// Indices of memories whose embedding dimension differs from the probe's
+	pub mismatched_indices: Vec<usize>,
+}
+
+impl<'a> RetrievalInput<'a> {
+	/// Input with only the required arrays.
+	///
+	/// Emotional weights, decay rates, working memory boosts and associations
+	/// start empty (every memory uses the default) and reconsolidation is
+	/// off; set them with the `with_*` methods. Unlike a struct literal, this
+	/// keeps compiling when optional fields are added.
+	#[must_use]
+	pub const fn new(
+		probe_embedding: &'a [f64],
+		memory_embeddings: &'a [Vec<f64>],
+		access_histories_ms: &'a [Vec<f64>],
+		current_time_ms: f64,
+	) -> Self {
+		Self {
+			probe_embedding,
+			memory_embeddings,
+			access_histories_ms,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms,
+			reconsolidation: None,
+		}
+	}
+
+	/// Set the per-memory emotional weights.
+	#[must_use]
+	pub const fn with_emotional_weights(mut self, emotional_weights: &'a [f64]) -> Self {
+		self.emotional_weights = emotional_weights;
+		self
+	}
+
+	/// Set the per-memory decay rates.
+	#[must_use]
+	pub const fn with_decay_rates(mut self, decay_rates: &'a [f64]) -> Self {
+		self.decay_rates = decay_rates;
+		self
+	}
+
+	/// Set the per-memory working memory boosts.
+	#[must_use]
+	pub const fn with_working_memory_boosts(mut self, working_memory_boosts: &'a [f64]) -> Self {
+		self.working_memory_boosts = working_memory_boosts;
+		self
+	}
+
+	/// Set the association graph edges.
+	#[must_use]
+	pub const fn with_associations(mut self, associations: &'a [Association]) -> Self {
+		self.associations = associations;
+		self
+	}
+
+	/// Turn on reconsolidation-aware decay for this probe.
+	#[must_use]
+	pub const fn with_reconsolidation(mut self, reconsolidation: ReconsolidationInput<'a>) -> Self {
+		self.reconsolidation = Some(reconsolidation);
+		self
+	}
+}
+
+impl RetrievalInput<'_> {
+	/// Check that every memory embedding has the probe's dimension.
+	///
+	/// Similarity between vectors of different lengths is 0, so a store that
+	/// mixes embedding models otherwise just retrieves nothing.
+	///
+	/// # Errors
+	///
+	/// Returns a [`DimensionMismatchError`] listing every mismatched memory.
+	pub fn check_dimensions(&self) -> Result<(), DimensionMismatchError> {
+		let probe_dimension = self.probe_embedding.len();
+		let mismatched_indices: Vec<usize> = self
+			.memory_embeddings
+			.iter()
+			.enumerate()
+			.filter(|(_, embedding)| embedding.len() != probe_dimension)
+			.map(|(i, _)| i)
+			.collect();
+
+		if mismatched_indices.is_empty() {
+			Ok(())
+		} else {
+			Err(DimensionMismatchError {
+				probe_dimension,
+				mismatched_indices,
+			})
+		}
+	}
+
+	/// Check that every parallel array matches the number of memories.
+	///
+	/// `access_histories_ms` must have exactly one entry per memory.
+	/// `emotional_weights`, `decay_rates`, `working_memory_boosts` and the
+	/// reconsolidation counts may be empty (every memory uses the default),
+	/// but otherwise must match too —
+	/// a partially filled array would silently give the tail memories defaults.
+	///
+	/// # Errors
+	///
+	/// Returns a [`RetrievalInputError`] listing every mismatched field.
+	pub fn validate(&self) -> Result<(), RetrievalInputError> {
+		let expected = self.memory_embeddings.len();
+		let required = [("access_histories_ms", self.access_histories_ms.len())];
+		let reconsolidation = self.reconsolidation.as_ref();
+		let optional = [
+			("emotional_weights", self.emotional_weights.len()),
+			("decay_rates", self.decay_rates.len()),
+			("working_memory_boosts", self.working_memory_boosts.len()),
+			(
+				"reconsolidation.access_counts",
+				reconsolidation.map_or(0, |r| r.access_counts.len()),
+			),
+			(
+				"reconsolidation.days_since_access",
+				reconsolidation.map_or(0, |r| r.days_since_access.len()),
+			),
+		];
+
+		check_lengths(expected, &required, &optional)
+	}
+}
+
+/// Check `(field, length)` pairs against `expected` memories: `required`
+/// fields must match exactly, `optional` ones may also be empty.
+pub(crate) fn check_lengths(
+	expected: usize,
+	required: &[(&'static str, usize)],
+	optional: &[(&'static str, usize)],
+) -> Result<(), RetrievalInputError> {
+	let mismatches: Vec<LengthMismatch> = required
+		.iter()
+		.filter(|&&(_, actual)| actual != expected)
+		.chain(
+			optional
+				.iter()
+				.filter(|&&(_, actual)| actual != 0 && actual != expected),
+		)
+		.map(|&(field, actual)| LengthMismatch {
+			field,
+			expected,
+			actual,
+		})
+		.collect();
+
+	if mismatches.is_empty() {
+		Ok(())
+	} else {
+		Err(RetrievalInputError { mismatches })
+	}
+}
+
+/// Owned counterpart of [`RetrievalInput`], without the probe.
+///
+/// Useful when the per-memory arrays are built once (e.g. converted from
+/// another memory type) and then queried with many probes.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OwnedRetrievalInput {
+	/// All memory embeddings
+	pub memory_embeddings: Vec<Vec<f64>>,
+	/// Access timestamps (ms) for each memory
+	pub access_histories_ms: Vec<Vec<f64>>,
+	/// Emotional weight for each memory (0-1)
+	pub emotional_weights: Vec<f64>,
+	/// Per-memory decay rates (empty = config default)
+	pub decay_rates: Vec<f64>,
+	/// Working memory boost for each memory (empty = no boost)
+	pub working_memory_boosts: Vec<f64>,
+	/// Association graph edges
+	pub associations: Vec<Association>,
+	/// Current time (ms)
+	pub current_time_ms: f64,
+}
+
+impl OwnedRetrievalInput {
+	/// Borrow as a [`RetrievalInput`] for the given probe.
+	#[must_use]
+	pub fn as_input<'a>(&'a self, probe_embedding: &'a [f64]) -> RetrievalInput<'a> {
+		RetrievalInput::new(
+			probe_embedding,
+			&self.memory_embeddings,
+			&self.access_histories_ms,
+			self.current_time_ms,
+		)
+		.with_emotional_weights(&self.emotional_weights)
+		.with_decay_rates(&self.decay_rates)
+		.with_working_memory_boosts(&self.working_memory_boosts)
+		.with_associations(&self.associations)
+	}
+}
+
+/// Embedding dimension diagnostics from a retrieval pass.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetrievalDiagnostics {
+	/// Dimension of the first non-empty memory embedding (0 if none)
+	pub embedding_dimension: usize,
+	/// Dimension of the probe embedding
+	pub probe_dimension: usize,
+	/// Memories excluded because their dimension differs from `embedding_dimension`
+	pub skipped_mismatched: usize,
+}
+
+impl RetrievalDiagnostics {
+	/// Whether the probe shares the detected memory dimension.
+	#[must_use]
+	pub const fn probe_matches(&self) -> bool {
+		self.probe_dimension == self.embedding_dimension
+	}
+}
+
+/// Detect the memory embedding dimension and flag memories that don't match it.
+///
+/// The dimension is taken from the first non-empty embedding. Empty embeddings
+/// count as mismatched.
+fn detect_dimension(memory_embeddings: &[Vec<f64>]) -> (usize, Vec<bool>) {
+	let dimension = memory_embeddings
+		.iter()
+		.map(Vec::len)
+		.find(|&len| len > 0)
+		.unwrap_or(0);
+	let mismatched = memory_embeddings
+		.iter()
+		.map(|e| e.len() != dimension)
+		.collect();
+	(dimension, mismatched)
 }
 
 /// Full retrieval pipeline.
@@ -107,13 +633,432 @@ pub struct RetrievalInput<'a> {
 /// Ranked list of retrieval candidates.
 #[must_use]
 pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<RetrievalCandidate> {
+	retrieve_with_diagnostics(input, config).0
+}
+
+/// Full retrieval pipeline that can reject mixed embedding dimensions.
+///
+/// With `config.strict_dimensions` set, any memory whose embedding
+/// dimension differs from the probe's is an error rather than a silent miss.
+/// Otherwise this is [`retrieve`].
+///
+/// # Errors
+///
+/// Returns a [`DimensionMismatchError`] listing the offending memory indices
+/// when `strict_dimensions` is set and any dimension differs.
+pub fn retrieve_checked(
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+) -> Result<Vec<RetrievalCandidate>, DimensionMismatchError> {
+	if config.strict_dimensions {
+		input.check_dimensions()?;
+	}
+	Ok(retrieve(input, config))
+}
+
+/// Serialize retrieval results to a JSON array, for logging and debug
+/// tooling outside the napi bindings.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn retrieval_result_to_json(
+	candidates: &[RetrievalCandidate],
+) -> Result<String, serde_json::Error> {
+	serde_json::to_string(candidates)
+}
+
+/// Full retrieval pipeline that also reports embedding dimension diagnostics.
+///
+/// Memories whose embedding dimension differs from the first non-empty memory
+/// are never returned as candidates, and are counted in
+/// [`RetrievalDiagnostics::skipped_mismatched`]. A mixed-dimension store
+/// usually means part of it was embedded with a different model.
+///
+/// # Returns
+///
+/// Ranked list of retrieval candidates and the diagnostics for this pass.
+#[must_use]
+pub fn retrieve_with_diagnostics(
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+) -> (Vec<RetrievalCandidate>, RetrievalDiagnostics) {
+	let n = input.memory_embeddings.len();
+	let (embedding_dimension, mismatched) = detect_dimension(input.memory_embeddings);
+	let diagnostics = RetrievalDiagnostics {
+		embedding_dimension,
+		probe_dimension: input.probe_embedding.len(),
+		skipped_mismatched: mismatched.iter().filter(|&&m| m).count(),
+	};
+	if n == 0 {
+		return (Vec::new(), diagnostics);
+	}
+
+	let components = compute_components(input, config, &mismatched);
+	let candidates = rank_candidates(&components, input, config, &mismatched);
+
+	(candidates, diagnostics)
+}
+
+/// Retrieval that takes base-levels from a [`RetrievalSession`] cache.
+///
+/// Everything else comes from `input` exactly as in [`retrieve`], but base
+/// levels are the session's cached values at its reference time, so no access
+/// history is summed. `input.access_histories_ms`, `input.decay_rates`,
+/// `input.current_time_ms`, `input.reconsolidation` and `config.max_history`
+/// therefore don't affect the result. Memories beyond the session's length
+/// count as never accessed.
+///
+/// Matches [`retrieve`] when the input uses the session's histories and decay
+/// rates at its reference time and nothing is capped or reconsolidated.
+#[must_use]
+pub fn retrieve_with_session(
+	session: &RetrievalSession,
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+) -> Vec<RetrievalCandidate> {
 	let n = input.memory_embeddings.len();
 	if n == 0 {
 		return Vec::new();
 	}
 
-	// 1. Compute probe-trace similarities (batch)
-	let similarities = cosine_similarity_batch(input.probe_embedding, input.memory_embeddings);
+	let (_, mismatched) = detect_dimension(input.memory_embeddings);
+	let similarities = probe_similarities(input.probe_embedding, input.memory_embeddings, config);
+	let base_levels = (0..n)
+		.map(|i| session.base_level(i).unwrap_or(f64::NEG_INFINITY))
+		.collect();
+	let components = compute_components_from_similarities(
+		similarities,
+		Some(base_levels),
+		input,
+		config,
+		&mismatched,
+	);
+	rank_candidates(&components, input, config, &mismatched)
+}
+
+/// A retrieval candidate annotated with how spreading reached it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TracedCandidate {
+	/// The candidate, exactly as [`retrieve`] would return it
+	pub candidate: RetrievalCandidate,
+	/// Hops from the nearest spreading seed when the memory was first
+	/// activated; `None` for the seeds themselves and for memories spreading
+	/// never reached (direct probe matches only)
+	pub spread_depth: Option<usize>,
+}
+
+/// Retrieval that also reports each candidate's spreading hop distance.
+///
+/// Ranking is identical to [`retrieve`]; use this to see why a memory
+/// surfaced where it did.
+#[must_use]
+pub fn retrieve_with_trace(
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+) -> Vec<TracedCandidate> {
+	if input.memory_embeddings.is_empty() {
+		return Vec::new();
+	}
+
+	let (_, mismatched) = detect_dimension(input.memory_embeddings);
+	let components = compute_components(input, config, &mismatched);
+
+	rank_candidates(&components, input, config, &mismatched)
+		.into_iter()
+		.map(|candidate| TracedCandidate {
+			spread_depth: components.spread_depths[candidate.index],
+			candidate,
+		})
+		.collect()
+}
+
+/// How per-probe similarities are merged in [`retrieve_multi_probe`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultiProbeMode {
+	/// Best weighted similarity across probes: relevant to *any* probe
+	#[default]
+	WeightedMax,
+	/// Weighted average similarity: relevant to the probes *as a whole*
+	WeightedMean,
+}
+
+impl MultiProbeMode {
+	/// Merge one memory's similarities to each probe.
+	///
+	/// Missing weights count as 1. A weighted mean with non-positive total
+	/// weight is 0.
+	fn combine(self, similarities: impl Iterator<Item = f64>, weights: &[f64]) -> f64 {
+		let weighted = similarities
+			.enumerate()
+			.map(|(p, sim)| (sim, weights.get(p).copied().unwrap_or(1.0)));
+
+		match self {
+			Self::WeightedMax => weighted
+				.map(|(sim, weight)| sim * weight)
+				.fold(f64::NEG_INFINITY, f64::max),
+			Self::WeightedMean => {
+				let (sum, total_weight) = weighted
+					.fold((0.0, 0.0), |(sum, total), (sim, weight)| {
+						(sim.mul_add(weight, sum), total + weight)
+					});
+				if total_weight > 0.0 {
+					sum / total_weight
+				} else {
+					0.0
+				}
+			}
+		}
+	}
+}
+
+/// Retrieval against several probes at once (query expansion).
+///
+/// Each memory's similarity to every probe is merged according to `mode`
+/// before working-memory boost and the nonlinear step; everything after
+/// that, including spreading, is the same as [`retrieve`].
+/// `input.probe_embedding` is ignored. `probe_weights` may be shorter than
+/// `probes` (missing weights are 1). No probes means no candidates.
+#[must_use]
+pub fn retrieve_multi_probe(
+	probes: &[Vec<f64>],
+	probe_weights: &[f64],
+	mode: MultiProbeMode,
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+) -> Vec<RetrievalCandidate> {
+	if probes.is_empty() || input.memory_embeddings.is_empty() {
+		return Vec::new();
+	}
+
+	let (_, mismatched) = detect_dimension(input.memory_embeddings);
+	let per_probe: Vec<Vec<f64>> = probes
+		.iter()
+		.map(|probe| probe_similarities(probe, input.memory_embeddings, config))
+		.collect();
+	let similarities = (0..input.memory_embeddings.len())
+		.map(|i| mode.combine(per_probe.iter().map(|sims| sims[i]), probe_weights))
+		.collect();
+
+	let components =
+		compute_components_from_similarities(similarities, None, input, config, &mismatched);
+	rank_candidates(&components, input, config, &mismatched)
+}
+
+/// Steps 7-8 of the pipeline: score, filter, sort and truncate.
+fn rank_candidates(
+	components: &ActivationComponents,
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+	mismatched: &[bool],
+) -> Vec<RetrievalCandidate> {
+	// 7. Combine all activations and build candidates
+	let mut candidates: Vec<RetrievalCandidate> = (0..input.memory_embeddings.len())
+		.filter(|&i| !mismatched[i])
+		.map(|i| score_candidate(i, components, input, config))
+		// Filter by minimum probability
+		.filter(|c| c.probability >= config.min_probability)
+		.collect();
+
+	// 8. Sort by total activation, skip to the requested page and limit;
+	// ties go to the lower index so equal activations come back in the same
+	// order on every run, which keeps pages from overlapping
+	candidates.sort_by(|a, b| {
+		b.total_activation
+			.partial_cmp(&a.total_activation)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.index.cmp(&b.index))
+	});
+	let _ = candidates.drain(..config.offset.min(candidates.len()));
+	candidates.truncate(config.max_results);
+	for candidate in &mut candidates {
+		candidate.probability = reported_probability(candidate.total_activation, config);
+	}
+	if config.normalize_activations {
+		normalize_activations(&mut candidates);
+	}
+
+	candidates
+}
+
+/// Min-max scale the candidates' total activations into
+/// `normalized_activation`.
+///
+/// Scaling is relative to this result set only, so the values rank results
+/// within one retrieval but aren't comparable across retrievals. When every
+/// candidate has the same activation, all get 1.0.
+fn normalize_activations(candidates: &mut [RetrievalCandidate]) {
+	let (min, max) = candidates
+		.iter()
+		.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), c| {
+			(min.min(c.total_activation), max.max(c.total_activation))
+		});
+	let range = max - min;
+
+	for candidate in candidates {
+		candidate.normalized_activation = Some(if range > 0.0 {
+			(candidate.total_activation - min) / range
+		} else {
+			1.0
+		});
+	}
+}
+
+/// Every memory's activation components from one retrieval, as parallel arrays.
+///
+/// Index `i` in each array refers to memory `i`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FullScoreTable {
+	/// Raw cosine similarity to the probe
+	pub similarity: Vec<f64>,
+	/// Base-level activation (-10 for never-accessed memories)
+	pub base_level: Vec<f64>,
+	/// Probe activation after WM boost, cubing and emotional modulation
+	pub probe_activation: Vec<f64>,
+	/// Spreading activation received
+	pub spreading: Vec<f64>,
+	/// Emotional weight
+	pub emotional_weight: Vec<f64>,
+	/// Combined total activation
+	pub total_activation: Vec<f64>,
+	/// Retrieval probability
+	pub probability: Vec<f64>,
+}
+
+/// Score every memory without thresholding, sorting or truncation.
+///
+/// The debugging counterpart to [`retrieve`]: same pipeline, but returns all
+/// component scores so they can be dumped for offline analysis or tuning.
+/// It allocates seven `n`-length arrays per call, so keep it off hot paths.
+#[must_use]
+pub fn retrieve_full_scores(
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+) -> FullScoreTable {
+	let n = input.memory_embeddings.len();
+	if n == 0 {
+		return FullScoreTable::default();
+	}
+
+	let (_, mismatched) = detect_dimension(input.memory_embeddings);
+	let components = compute_components(input, config, &mismatched);
+
+	let mut table = FullScoreTable {
+		similarity: components.similarities.clone(),
+		base_level: Vec::with_capacity(n),
+		probe_activation: Vec::with_capacity(n),
+		spreading: Vec::with_capacity(n),
+		emotional_weight: Vec::with_capacity(n),
+		total_activation: Vec::with_capacity(n),
+		probability: Vec::with_capacity(n),
+	};
+	for i in 0..n {
+		let candidate = score_candidate(i, &components, input, config);
+		table.base_level.push(candidate.base_level);
+		table.probe_activation.push(candidate.probe_activation);
+		table.spreading.push(candidate.spreading);
+		table.emotional_weight.push(candidate.emotional_weight);
+		table.total_activation.push(candidate.total_activation);
+		table
+			.probability
+			.push(reported_probability(candidate.total_activation, config));
+	}
+
+	table
+}
+
+/// Retrieval probability with `probability_temperature` applied, for output only.
+fn reported_probability(total_activation: f64, config: &RetrievalConfig) -> f64 {
+	retrieval_probability(
+		total_activation,
+		config.activation_threshold,
+		config.noise_parameter * config.probability_temperature,
+	)
+}
+
+/// Per-memory activation components shared by the retrieval entry points.
+struct ActivationComponents {
+	similarities: Vec<f64>,
+	probe_activations: Vec<f64>,
+	base_levels: Vec<f64>,
+	spreading: Vec<f64>,
+	/// Hop at which spreading first reached each memory (seeds excluded)
+	spread_depths: Vec<Option<usize>>,
+}
+
+/// Whether a vector's L2 norm is 1, within rounding of stored embeddings.
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+fn is_unit_length(v: &[f64]) -> bool {
+	let norm_sq: f64 = v.iter().map(|x| x * x).sum();
+	(norm_sq - 1.0).abs() < 1e-3
+}
+
+/// Steps 1-6 of the pipeline: similarity through spreading.
+fn compute_components(
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+	mismatched: &[bool],
+) -> ActivationComponents {
+	let similarities = probe_similarities(input.probe_embedding, input.memory_embeddings, config);
+	compute_components_from_similarities(similarities, None, input, config, mismatched)
+}
+
+/// Step 1 of the pipeline: probe-trace similarities (batch).
+fn probe_similarities(
+	probe: &[f64],
+	memory_embeddings: &[Vec<f64>],
+	config: &RetrievalConfig,
+) -> Vec<f64> {
+	let metric = if config.embeddings_prenormalized
+		&& config.similarity_metric == SimilarityMetric::Cosine
+	{
+		debug_assert!(
+			is_unit_length(probe) && memory_embeddings.first().is_none_or(|m| is_unit_length(m)),
+			"embeddings_prenormalized is set but embeddings aren't unit length"
+		);
+		// Cosine of unit vectors is their dot product
+		SimilarityMetric::DotProduct
+	} else {
+		config.similarity_metric
+	};
+	similarity_batch(probe, memory_embeddings, metric)
+}
+
+/// Step 4 of the pipeline: base-level activation (batch) with per-memory
+/// decay rates.
+fn input_base_levels(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<f64> {
+	input
+		.access_histories_ms
+		.iter()
+		.enumerate()
+		.map(|(i, history)| {
+			let decay_rate = input
+				.reconsolidation
+				.as_ref()
+				.and_then(|r| r.decay_rate_for(i))
+				.or_else(|| input.decay_rates.get(i).copied())
+				.unwrap_or(config.decay_rate);
+			compute_base_level_capped(
+				history,
+				input.current_time_ms,
+				decay_rate,
+				config.max_history,
+			)
+		})
+		.collect()
+}
+
+/// Steps 2-6 of the pipeline, from already computed similarities.
+///
+/// `base_levels` of `None` computes them from the input's access histories.
+fn compute_components_from_similarities(
+	similarities: Vec<f64>,
+	base_levels: Option<Vec<f64>>,
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+	mismatched: &[bool],
+) -> ActivationComponents {
+	let n = input.memory_embeddings.len();
 
 	// 2. Apply Working Memory boost to similarities BEFORE nonlinear activation
 	// This models how prefrontal WM modulates hippocampal retrieval in real-time.
@@ -124,61 +1069,45 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 		.enumerate()
 		.map(|(i, &sim)| {
 			let boost = input.working_memory_boosts.get(i).copied().unwrap_or(1.0);
-			// Cap at 1.0 to maintain valid similarity range
-			(sim * boost).min(1.0)
+			// Capped at 1.0 by default to keep a valid similarity; a higher cap
+			// lets boosts still separate memories that already match fully
+			(sim * boost).min(config.wm_cap)
 		})
 		.collect();
 
 	// 3. Apply nonlinear activation (MINERVA 2) to boosted similarities
 	let probe_activations = nonlinear_activation_batch(&boosted_similarities);
 
-	// 4. Compute base-level activation (batch) with per-memory decay rates
-	let base_levels: Vec<f64> = input
-		.access_histories_ms
-		.iter()
-		.enumerate()
-		.map(|(i, history)| {
-			let decay_rate = input
-				.decay_rates
-				.get(i)
-				.copied()
-				.unwrap_or(config.decay_rate);
-			compute_base_level(history, input.current_time_ms, decay_rate)
-		})
-		.collect();
+	// 4. Base-level activation, computed by the caller
+	let base_levels = base_levels.unwrap_or_else(|| input_base_levels(input, config));
 
-	// 5. Initial activation (before spreading)
-	// Uses MULTIPLICATIVE combination: similarity is primary, recency is boost
-	let initial_activations: Vec<f64> = (0..n)
-		.map(|i| {
-			let base = if base_levels[i].is_finite() {
-				base_levels[i]
-			} else {
-				-10.0
-			};
+	// 5. Find seeds for spreading (top activated)
+	let seed_floor = config
+		.seed_activation_floor
+		.unwrap_or_else(|| config.seed_mode.default_floor());
+	let mut seeds: Vec<(usize, f64)> = (0..n)
+		.filter(|&i| !mismatched[i])
+		.filter_map(|i| {
 			let emotional = input.emotional_weights.get(i).copied().unwrap_or(0.5);
-			let emotional_multiplier = 1.0 + (emotional - 0.5);
-
-			// Normalize base-level to [0, 1] for multiplicative boost
-			let recency_boost = ((base + 10.0) / 10.0).clamp(0.0, 1.0);
-
-			// Multiplicative: probe * emotional * (1 + recency)
-			probe_activations[i] * emotional_multiplier * (1.0 + recency_boost)
+			config
+				.seed_mode
+				.seed_activation_with_floor(
+					base_levels[i],
+					probe_activations[i],
+					emotional,
+					seed_floor,
+				)
+				.map(|a| (i, a))
 		})
 		.collect();
+	seeds.sort_by(|a, b| {
+		b.1.partial_cmp(&a.1)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.0.cmp(&b.0))
+	});
+	seeds.truncate(config.max_spreading_seeds);
 
-	// 6. Find seeds for spreading (top activated)
-	// With multiplicative formula, use probe activation threshold instead
-	let mut seeds: Vec<(usize, f64)> = initial_activations
-		.iter()
-		.enumerate()
-		.filter(|(i, _)| probe_activations[*i] > 0.1) // Minimum similarity threshold
-		.map(|(i, &a)| (i, a))
-		.collect();
-	seeds.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-	seeds.truncate(5); // Top 5 as seeds
-
-	// 7. Spread activation
+	// 6. Spread activation
 	let spreading_result = if !seeds.is_empty() && config.spreading_depth > 0 {
 		let seed_indices: Vec<usize> = seeds.iter().map(|(i, _)| *i).collect();
 		let seed_activations: Vec<f64> = seeds.iter().map(|(_, a)| *a).collect();
@@ -186,8 +1115,12 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 		let spreading_config = SpreadingConfig {
 			decay_per_hop: config.spreading_decay,
 			minimum_activation: 0.01,
-			max_nodes: 1000,
+			max_nodes: SPREADING_MAX_NODES,
 			bidirectional: config.bidirectional,
+			backward_decay: config.backward_decay,
+			fan_normalization: FanNormalization::default(),
+			warn_on_cycles: false,
+			visit_budget: None,
 		};
 
 		spread_activation(
@@ -202,56 +1135,75 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 		SpreadingResult {
 			activations: vec![0.0; n],
 			visited_by_depth: Vec::new(),
+			relaxations: 0,
+			has_cycles: None,
 		}
 	};
 
-	// 8. Combine all activations and build candidates
-	let mut candidates: Vec<RetrievalCandidate> = (0..n)
-		.filter_map(|i| {
-			let base_level = if base_levels[i].is_finite() {
-				base_levels[i]
-			} else {
-				-10.0
-			};
-			let probe_activation = probe_activations[i];
-			let spreading = spreading_result.activations[i];
-			let emotional_weight = input.emotional_weights.get(i).copied().unwrap_or(0.5);
+	let mut spread_depths = vec![None; n];
+	for (depth, nodes) in spreading_result.visited_by_depth.iter().enumerate().skip(1) {
+		for &node in nodes {
+			spread_depths[node] = Some(depth);
+		}
+	}
 
-			let breakdown =
-				combine_activations(base_level, probe_activation, spreading, emotional_weight);
+	let mut spreading = spreading_result.activations;
+	config.spreading_normalization.apply(&mut spreading);
 
-			let probability = retrieval_probability(
-				breakdown.total,
-				config.activation_threshold,
-				config.noise_parameter,
-			);
+	ActivationComponents {
+		similarities,
+		probe_activations,
+		base_levels,
+		spreading,
+		spread_depths,
+	}
+}
 
-			// Filter by minimum probability
-			if probability < config.min_probability {
-				return None;
-			}
+/// Combine one memory's components into a (not yet filtered) candidate.
+fn score_candidate(
+	i: usize,
+	components: &ActivationComponents,
+	input: &RetrievalInput<'_>,
+	config: &RetrievalConfig,
+) -> RetrievalCandidate {
+	let base_level = if components.base_levels[i].is_finite() {
+		components.base_levels[i]
+	} else {
+		-10.0
+	};
+	let emotional_weight = input.emotional_weights.get(i).copied().unwrap_or(0.5);
 
-			Some(RetrievalCandidate {
-				index: i,
-				base_level: breakdown.base_level,
-				probe_activation: breakdown.probe_activation,
-				spreading: breakdown.spreading,
-				emotional_weight: breakdown.emotional_weight,
-				total_activation: breakdown.total,
-				probability,
-			})
-		})
-		.collect();
+	let mut breakdown = combine_activations_with_emotional_scale(
+		base_level,
+		components.probe_activations[i],
+		components.spreading[i],
+		emotional_weight,
+		config.combine_mode,
+		config.emotional_multiplier_scale,
+	);
 
-	// 9. Sort by total activation and limit
-	candidates.sort_by(|a, b| {
-		b.total_activation
-			.partial_cmp(&a.total_activation)
-			.unwrap_or(std::cmp::Ordering::Equal)
-	});
-	candidates.truncate(config.max_results);
+	// Never-accessed memories have no recency to draw on
+	if input.access_histories_ms.get(i).is_some_and(Vec::is_empty) {
+		breakdown.total += config.cold_start_boost;
+	}
 
-	candidates
+	let probability = retrieval_probability(
+		breakdown.total,
+		config.activation_threshold,
+		config.noise_parameter,
+	);
+
+	RetrievalCandidate {
+		index: i,
+		base_level: breakdown.base_level,
+		probe_activation: breakdown.probe_activation,
+		spreading: breakdown.spreading,
+		emotional_weight: breakdown.emotional_weight,
+		total_activation: breakdown.total,
+		probability,
+		latency_ms: retrieval_latency(breakdown.total, config.latency_factor),
+		normalized_activation: None,
+	}
 }
 
 /// Lightweight similarity-only retrieval.
@@ -266,11 +1218,128 @@ pub fn retrieve_by_similarity(
 	let similarities = cosine_similarity_batch(probe_embedding, memory_embeddings);
 
 	let mut indexed: Vec<(usize, f64)> = similarities.into_iter().enumerate().collect();
-	indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+	indexed.sort_by(|a, b| {
+		b.1.partial_cmp(&a.1)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.0.cmp(&b.0))
+	});
 
 	indexed.into_iter().take(top_k).map(|(i, _)| i).collect()
 }
 
+/// Similarity-only retrieval with a similarity floor.
+///
+/// Returns `(index, similarity)` for at most `max_results` memories whose
+/// cosine similarity to the probe is at least `min_similarity`, most similar
+/// first. Memories below the floor are dropped as they are scored, so only
+/// the matches are ever sorted.
+#[must_use]
+pub fn retrieve_by_similarity_threshold(
+	probe_embedding: &[f64],
+	memory_embeddings: &[Vec<f64>],
+	min_similarity: f64,
+	max_results: usize,
+) -> Vec<(usize, f64)> {
+	if max_results == 0 {
+		return Vec::new();
+	}
+
+	let mut matches: Vec<(usize, f64)> = memory_embeddings
+		.iter()
+		.enumerate()
+		.filter_map(|(i, memory)| {
+			let similarity = cosine_similarity(probe_embedding, memory);
+			(similarity >= min_similarity).then_some((i, similarity))
+		})
+		.collect();
+	matches.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+	matches.truncate(max_results);
+	matches
+}
+
+/// Most nodes [`retrieve`] lets spreading visit.
+const SPREADING_MAX_NODES: usize = 1000;
+
+/// Single-core similarity throughput assumed by [`estimate_retrieval_cost`].
+const SIMILARITY_FLOPS_PER_MS: f64 = 1e6;
+
+/// Cost of scoring one memory: base-level, nonlinearity and probability.
+const NS_PER_MEMORY_SCORED: f64 = 200.0;
+
+/// Cost of pushing activation along one edge while spreading.
+const NS_PER_EDGE_RELAXATION: f64 = 20.0;
+
+/// Approximate work a [`retrieve`] call will do, from [`estimate_retrieval_cost`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetrievalCostEstimate {
+	/// Floating-point operations spent on probe-trace similarity
+	pub similarity_flops: f64,
+	/// Expected nodes reached by spreading, capped as `retrieve` caps it
+	pub spreading_node_visits: f64,
+	/// Coarse single-core wall time in milliseconds
+	pub estimated_ms: f64,
+}
+
+/// Estimate what [`retrieve`] will cost without running it.
+///
+/// Similarity costs `6 × embedding_dim` FLOPs per memory for cosine (dot
+/// product plus two norms), a third of that with `embeddings_prenormalized`.
+/// Spreading is modelled as the seeds fanning out by the graph's average
+/// degree each hop, with the frontier capped at the number of memories and
+/// total visits at `retrieve`'s node limit. The time is only good for
+/// choosing between full retrieval and similarity-only fallbacks.
+#[must_use]
+pub fn estimate_retrieval_cost(
+	num_memories: usize,
+	embedding_dim: usize,
+	num_associations: usize,
+	config: &RetrievalConfig,
+) -> RetrievalCostEstimate {
+	#[allow(clippy::cast_precision_loss)]
+	let (n, dim, edges) = (
+		num_memories as f64,
+		embedding_dim as f64,
+		num_associations as f64,
+	);
+	let flops_per_dim = if config.embeddings_prenormalized {
+		2.0
+	} else {
+		6.0
+	};
+	let similarity_flops = n * dim * flops_per_dim;
+
+	let directions = if config.bidirectional { 2.0 } else { 1.0 };
+	let fan_out = if num_memories == 0 {
+		0.0
+	} else {
+		edges * directions / n
+	};
+	#[allow(clippy::cast_precision_loss)]
+	let (visit_cap, mut frontier) = (
+		num_memories.min(SPREADING_MAX_NODES) as f64,
+		config.max_spreading_seeds.min(num_memories) as f64,
+	);
+	let mut visits = 0.0;
+	let mut relaxations = 0.0;
+	for _ in 0..config.spreading_depth {
+		if frontier <= 0.0 || visits >= visit_cap {
+			break;
+		}
+		relaxations += frontier * fan_out;
+		frontier = (frontier * fan_out).min(n);
+		visits = (visits + frontier).min(visit_cap);
+	}
+
+	let estimated_ms = similarity_flops / SIMILARITY_FLOPS_PER_MS
+		+ n.mul_add(NS_PER_MEMORY_SCORED, relaxations * NS_PER_EDGE_RELAXATION) / 1e6;
+
+	RetrievalCostEstimate {
+		similarity_flops,
+		spreading_node_visits: visits,
+		estimated_ms,
+	}
+}
+
 /// Compute surprise (prediction error) between expected and actual.
 ///
 /// Used to trigger reconsolidation - when a retrieved memory differs
@@ -309,16 +1378,109 @@ pub fn compute_surprise(
 	(semantic_surprise / adjusted_threshold).min(1.0)
 }
 
+/// Batch version of [`compute_surprise`] for a set of retrieved memories.
+///
+/// Entry `i` of the result is the surprise for the `i`-th element of each
+/// slice. Slices are zipped, so entries past the shortest one are ignored.
+#[must_use]
+pub fn compute_surprise_batch(
+	expected_embeddings: &[Vec<f64>],
+	actual_embeddings: &[Vec<f64>],
+	memory_ages_days: &[f64],
+	memory_strengths: &[f64],
+	base_threshold: f64,
+) -> Vec<f64> {
+	expected_embeddings
+		.iter()
+		.zip(actual_embeddings)
+		.zip(memory_ages_days.iter().zip(memory_strengths))
+		.map(|((expected, actual), (&age_days, &strength))| {
+			compute_surprise(expected, actual, age_days, strength, base_threshold)
+		})
+		.collect()
+}
+
 /// Check if surprise triggers lability (reconsolidation window).
 #[must_use]
 pub fn triggers_lability(surprise: f64, threshold: f64) -> bool {
 	surprise > threshold
 }
 
+/// Fixtures shared by the retrieval tests in this and sibling modules.
+#[cfg(test)]
+pub(crate) mod test_support {
+	use crate::spreading::Association;
+
+	/// Seed count test fixture shared by the text and visual paths.
+	///
+	/// Seven memories at `now`, where the last is orthogonal to `probe` and
+	/// linked only from the 6th-best match, so it gets spreading activation
+	/// with six seeds but not with five.
+	pub struct SixthSeedFixture {
+		pub probe: Vec<f64>,
+		pub memories: Vec<Vec<f64>>,
+		pub histories: Vec<Vec<f64>>,
+		pub associations: Vec<Association>,
+		pub now: f64,
+	}
+
+	impl SixthSeedFixture {
+		/// Index of the memory only the 6th seed reaches
+		pub const TARGET: usize = 6;
+
+		pub fn new() -> Self {
+			let mut memories: Vec<Vec<f64>> = [1.0, 0.95, 0.9, 0.85, 0.8, 0.75]
+				.iter()
+				.map(|&s: &f64| vec![s, s.mul_add(-s, 1.0).sqrt()])
+				.collect();
+			memories.push(vec![0.0, 1.0]);
+			let now = 1_000_000.0;
+			Self {
+				probe: vec![1.0, 0.0],
+				histories: vec![vec![now]; memories.len()],
+				memories,
+				associations: vec![Association {
+					source: 5,
+					target: 6,
+					forward_strength: 1.0,
+					backward_strength: 0.0,
+				}],
+				now,
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
+	use super::test_support::SixthSeedFixture;
 	use super::*;
 
+	#[test]
+	fn test_retrieve_by_similarity_threshold() {
+		let probe = [1.0, 0.0];
+		let memories = vec![
+			vec![0.0, 1.0], // 0.0
+			vec![1.0, 0.1], // ~0.995
+			vec![1.0, 1.0], // ~0.707
+			vec![1.0, 0.5], // ~0.894
+			vec![-1.0, 0.0],
+		];
+
+		let matches = retrieve_by_similarity_threshold(&probe, &memories, 0.7, 10);
+		let indices: Vec<usize> = matches.iter().map(|&(i, _)| i).collect();
+		assert_eq!(indices, vec![1, 3, 2]);
+		assert!(matches.iter().all(|&(_, similarity)| similarity >= 0.7));
+		assert!(matches.windows(2).all(|w| w[0].1 >= w[1].1));
+
+		// max_results keeps the best matches
+		let top = retrieve_by_similarity_threshold(&probe, &memories, 0.7, 2);
+		assert_eq!(top, matches[..2]);
+
+		assert!(retrieve_by_similarity_threshold(&probe, &memories, 0.999, 10).is_empty());
+		assert!(retrieve_by_similarity_threshold(&probe, &memories, 0.0, 0).is_empty());
+	}
+
 	#[test]
 	fn test_retrieve_empty() {
 		let input = RetrievalInput {
@@ -330,6 +1492,7 @@ mod tests {
 			working_memory_boosts: &[],
 			associations: &[],
 			current_time_ms: 1_000_000.0,
+			reconsolidation: None,
 		};
 
 		let config = RetrievalConfig::default();
@@ -337,6 +1500,72 @@ mod tests {
 		assert!(result.is_empty());
 	}
 
+	#[test]
+	fn test_retrieve_with_session_matches_retrieve() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0],
+			vec![0.8, 0.6],
+			vec![0.6, 0.8],
+			vec![0.0, 1.0],
+		];
+		let now = 50_000_000.0;
+		let session = RetrievalSession::new(
+			vec![
+				vec![now - 9_000_000.0, now - 60_000.0],
+				vec![now - 400_000.0],
+				vec![],
+				vec![now - 30_000_000.0, now - 2_000_000.0, now - 5_000.0],
+			],
+			&[0.5, 0.3],
+			0.5,
+			now,
+		);
+		let associations = [Association {
+			source: 0,
+			target: 2,
+			forward_strength: 0.8,
+			backward_strength: 0.4,
+		}];
+
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: session.access_histories(),
+			emotional_weights: &[0.5, 0.9, 0.2, 0.5],
+			decay_rates: session.decay_rates(),
+			working_memory_boosts: &[1.0, 1.5, 1.0, 1.0],
+			associations: &associations,
+			current_time_ms: session.reference_time_ms(),
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		let expected = retrieve(&input, &config);
+		let from_session = retrieve_with_session(&session, &input, &config);
+
+		assert_eq!(from_session.len(), expected.len());
+		for (a, b) in from_session.iter().zip(&expected) {
+			assert_eq!(a.index, b.index);
+			assert!((a.base_level - b.base_level).abs() < 1e-12);
+			assert!((a.total_activation - b.total_activation).abs() < 1e-12);
+		}
+
+		// Histories in the input are not consulted
+		let stale = RetrievalInput {
+			access_histories_ms: &[],
+			..input
+		};
+		let from_stale = retrieve_with_session(&session, &stale, &config);
+		assert_eq!(
+			from_stale.iter().map(|c| c.index).collect::<Vec<_>>(),
+			expected.iter().map(|c| c.index).collect::<Vec<_>>()
+		);
+	}
+
 	#[test]
 	fn test_retrieve_similarity_ordering() {
 		let probe = vec![1.0, 0.0, 0.0];
@@ -356,6 +1585,7 @@ mod tests {
 			working_memory_boosts: &[1.0, 1.0, 1.0], // No boost
 			associations: &[],
 			current_time_ms: now,
+			reconsolidation: None,
 		};
 
 		let config = RetrievalConfig {
@@ -372,19 +1602,1143 @@ mod tests {
 	}
 
 	#[test]
-	fn test_surprise_similar() {
-		let a = vec![1.0, 0.0, 0.0];
-		let b = vec![1.0, 0.0, 0.0];
-		let surprise = compute_surprise(&a, &b, 1.0, 0.5, 0.5);
-		assert!(surprise < 0.1); // Low surprise for identical
+	fn test_retrieve_skips_mismatched_dimensions() {
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![1.0, 0.0],
+			vec![0.9, 0.1, 0.0],
+			vec![1.0, 0.0, 0.0, 0.0],
+		];
+		let now = 1_000_000.0;
+
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now], vec![now], vec![now], vec![now]],
+			emotional_weights: &[0.5, 0.5, 0.5, 0.5],
+			decay_rates: &[0.5, 0.5, 0.5, 0.5],
+			working_memory_boosts: &[1.0, 1.0, 1.0, 1.0],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+
+		let config = RetrievalConfig {
+			spreading_depth: 0,
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		let (result, diagnostics) = retrieve_with_diagnostics(&input, &config);
+
+		assert_eq!(diagnostics.embedding_dimension, 3);
+		assert_eq!(diagnostics.skipped_mismatched, 2);
+		assert!(diagnostics.probe_matches());
+		let indices: Vec<usize> = result.iter().map(|c| c.index).collect();
+		assert_eq!(indices, vec![0, 2]);
+	}
+
+	fn validation_input<'a>(
+		memories: &'a [Vec<f64>],
+		histories: &'a [Vec<f64>],
+		emotional: &'a [f64],
+		decay: &'a [f64],
+		boosts: &'a [f64],
+	) -> RetrievalInput<'a> {
+		RetrievalInput {
+			probe_embedding: &[1.0, 0.0],
+			memory_embeddings: memories,
+			access_histories_ms: histories,
+			emotional_weights: emotional,
+			decay_rates: decay,
+			working_memory_boosts: boosts,
+			associations: &[],
+			current_time_ms: 1_000_000.0,
+			reconsolidation: None,
+		}
+	}
+
+	fn mismatched_fields(input: &RetrievalInput<'_>) -> Vec<&'static str> {
+		input
+			.validate()
+			.err()
+			.map(|e| e.mismatches.iter().map(|m| m.field).collect())
+			.unwrap_or_default()
 	}
 
 	#[test]
-	fn test_surprise_different() {
-		let a = vec![1.0, 0.0, 0.0];
-		let b = vec![0.0, 1.0, 0.0];
-		let surprise = compute_surprise(&a, &b, 1.0, 0.5, 0.5);
-		assert!(surprise > 0.5); // High surprise for orthogonal
+	fn test_validate_consistent_lengths() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let histories = vec![vec![0.0], vec![0.0]];
+
+		let full = validation_input(&memories, &histories, &[0.5; 2], &[0.5; 2], &[1.0; 2]);
+		assert!(full.validate().is_ok());
+
+		let defaults = validation_input(&memories, &histories, &[], &[], &[]);
+		assert!(defaults.validate().is_ok());
+	}
+
+	#[test]
+	fn test_validate_access_histories_mismatch() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let histories = vec![vec![0.0]];
+		let input = validation_input(&memories, &histories, &[], &[], &[]);
+		assert_eq!(mismatched_fields(&input), vec!["access_histories_ms"]);
+
+		let no_histories = validation_input(&memories, &[], &[], &[], &[]);
+		assert_eq!(
+			mismatched_fields(&no_histories),
+			vec!["access_histories_ms"]
+		);
+	}
+
+	#[test]
+	fn test_validate_emotional_weights_mismatch() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let histories = vec![vec![0.0], vec![0.0]];
+		let input = validation_input(&memories, &histories, &[0.5], &[], &[]);
+		assert_eq!(mismatched_fields(&input), vec!["emotional_weights"]);
+	}
+
+	#[test]
+	fn test_validate_decay_rates_mismatch() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let histories = vec![vec![0.0], vec![0.0]];
+		let input = validation_input(&memories, &histories, &[], &[0.5; 3], &[]);
+		assert_eq!(mismatched_fields(&input), vec!["decay_rates"]);
+	}
+
+	#[test]
+	fn test_validate_working_memory_boosts_mismatch() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let histories = vec![vec![0.0], vec![0.0]];
+		let input = validation_input(&memories, &histories, &[], &[], &[1.0]);
+		assert_eq!(mismatched_fields(&input), vec!["working_memory_boosts"]);
+	}
+
+	#[test]
+	fn test_validate_reports_all_mismatches() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let histories = vec![vec![0.0]];
+		let input = validation_input(&memories, &histories, &[0.5], &[0.5], &[1.0]);
+
+		let err = input.validate().err();
+		assert_eq!(err.as_ref().map(|e| e.mismatches.len()), Some(4));
+		let message = err.map(|e| e.to_string()).unwrap_or_default();
+		assert!(message.contains("emotional_weights has 1 entries, expected 2"));
+	}
+
+	#[test]
+	fn test_retrieve_full_scores_unfiltered() {
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![0.5, 0.5, 0.0],
+			vec![0.0, 1.0, 0.0],
+		];
+		let now = 1_000_000.0;
+
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now], vec![now], vec![]],
+			emotional_weights: &[0.5, 0.5, 0.5],
+			decay_rates: &[0.5, 0.5, 0.5],
+			working_memory_boosts: &[1.0, 1.0, 1.0],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			max_results: 1,
+			..Default::default()
+		};
+
+		let table = retrieve_full_scores(&input, &config);
+		let candidates = retrieve(&input, &config);
+
+		assert_eq!(table.similarity.len(), 3);
+		assert_eq!(table.probability.len(), 3);
+		assert!((table.similarity[0] - 1.0).abs() < 1e-10);
+		assert!(table.similarity[2].abs() < 1e-10);
+		assert!((table.base_level[2] + 10.0).abs() < 1e-10);
+		assert_eq!(candidates.len(), 1);
+		assert!((table.total_activation[0] - candidates[0].total_activation).abs() < 1e-10);
+		assert!((table.probability[0] - candidates[0].probability).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_spreading_normalization_across_graph_density() {
+		let now = 1_000_000.0;
+		let memories = vec![
+			vec![1.0, 0.0],
+			vec![1.0, 0.0],
+			vec![1.0, 0.0],
+			vec![1.0, 0.0],
+			vec![1.0, 0.0],
+			vec![0.0, 1.0],
+		];
+		let histories = vec![vec![now]; 6];
+		let probe = vec![1.0, 0.0];
+		let edge = |source: usize| Association {
+			source,
+			target: 5,
+			forward_strength: 1.0,
+			backward_strength: 0.0,
+		};
+		let sparse = vec![edge(0)];
+		let dense: Vec<Association> = (0..5).map(edge).collect();
+
+		let max_spreading = |associations: &[Association], normalization| {
+			let input = RetrievalInput {
+				probe_embedding: &probe,
+				memory_embeddings: &memories,
+				access_histories_ms: &histories,
+				emotional_weights: &[],
+				decay_rates: &[],
+				working_memory_boosts: &[],
+				associations,
+				current_time_ms: now,
+				reconsolidation: None,
+			};
+			let config = RetrievalConfig {
+				spreading_normalization: normalization,
+				..Default::default()
+			};
+			retrieve_full_scores(&input, &config)
+				.spreading
+				.into_iter()
+				.fold(0.0_f64, f64::max)
+		};
+
+		let ratio = |normalization| {
+			max_spreading(&dense, normalization) / max_spreading(&sparse, normalization)
+		};
+
+		assert!(ratio(SpreadingNormalization::None) > 2.0);
+		assert!((ratio(SpreadingNormalization::MaxNormalize) - 1.0).abs() < 1e-10);
+		assert!((ratio(SpreadingNormalization::SoftmaxTemperature(0.5)) - 1.0).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_softmax_normalization_keeps_unreached_at_zero() {
+		let mut activations = vec![0.0, 2.0, 1.0];
+		SpreadingNormalization::SoftmaxTemperature(1.0).apply(&mut activations);
+		assert!(activations[0].abs() < 1e-12);
+		assert!((activations[1] - 1.0).abs() < 1e-12);
+		assert!((activations[2] - (-1.0_f64).exp()).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_probability_temperature_only_changes_reported_probability() {
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![0.8, 0.6, 0.0],
+			vec![0.6, 0.8, 0.0],
+			vec![0.0, 1.0, 0.0],
+		];
+		let now = 1_000_000.0;
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now], vec![now], vec![now], vec![now]],
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+
+		let plain = retrieve(&input, &RetrievalConfig::default());
+		let calibrated = retrieve(
+			&input,
+			&RetrievalConfig {
+				probability_temperature: 3.0,
+				..Default::default()
+			},
+		);
+
+		let order = |c: &[RetrievalCandidate]| c.iter().map(|c| c.index).collect::<Vec<_>>();
+		assert!(!plain.is_empty());
+		assert_eq!(order(&plain), order(&calibrated));
+		for (a, b) in plain.iter().zip(&calibrated) {
+			assert!((a.total_activation - b.total_activation).abs() < 1e-12);
+			assert!((a.probability - b.probability).abs() > 1e-6);
+		}
+	}
+
+	#[test]
+	fn test_normalize_activations_preserves_order() {
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![0.8, 0.6, 0.0],
+			vec![0.6, 0.8, 0.0],
+			vec![0.0, 1.0, 0.0],
+		];
+		let now = 1_000_000.0;
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now], vec![now], vec![now], vec![now]],
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		let plain = retrieve(&input, &config);
+		assert!(plain.iter().all(|c| c.normalized_activation.is_none()));
+
+		let normalized = retrieve(
+			&input,
+			&RetrievalConfig {
+				normalize_activations: true,
+				..config
+			},
+		);
+		assert_eq!(normalized.len(), 4);
+		let scaled: Vec<f64> = normalized
+			.iter()
+			.filter_map(|c| c.normalized_activation)
+			.collect();
+		assert_eq!(scaled.len(), 4);
+		assert!((scaled[0] - 1.0).abs() < 1e-12);
+		assert!(scaled[3].abs() < 1e-12);
+		assert!(scaled.windows(2).all(|w| w[0] >= w[1]));
+		assert!(scaled.iter().all(|v| (0.0..=1.0).contains(v)));
+
+		// Raw activations are left alone
+		for (a, b) in plain.iter().zip(&normalized) {
+			assert_eq!(a.index, b.index);
+			assert!((a.total_activation - b.total_activation).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_dot_product_ranking_respects_magnitude() {
+		let probe = vec![1.0, 0.0];
+		// Same direction as the probe but short, vs. unit length at an angle
+		let memories = vec![vec![0.3, 0.0], vec![0.8, 0.6]];
+		let now = 1_000_000.0;
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now], vec![now]],
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+		let ranked = |similarity_metric| {
+			let config = RetrievalConfig {
+				similarity_metric,
+				min_probability: 0.0,
+				spreading_depth: 0,
+				..Default::default()
+			};
+			retrieve(&input, &config)
+				.iter()
+				.map(|c| c.index)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(ranked(SimilarityMetric::Cosine), vec![0, 1]);
+		assert_eq!(ranked(SimilarityMetric::DotProduct), vec![1, 0]);
+	}
+
+	#[test]
+	fn test_reconsolidation_zone_decay() {
+		let memories = vec![vec![1.0, 0.0], vec![1.0, 0.0]];
+		let probe = vec![1.0, 0.0];
+		let now = 10_000_000.0;
+		let histories = vec![vec![now - 3_600_000.0]; 2];
+		let recent_and_old = ReconsolidationInput {
+			surprise: 0.3,
+			access_counts: &[1, 1],
+			// The older memory's raised θ_low keeps this surprise in its reinforce zone
+			days_since_access: &[0.1, 5.0],
+			reconsolidating_decay_rate: 0.9,
+			config: ReconsolidationConfig::default(),
+		};
+		assert_eq!(recent_and_old.decay_rate_for(0), Some(0.9));
+		assert_eq!(recent_and_old.decay_rate_for(1), None);
+		assert_eq!(recent_and_old.decay_rate_for(2), None);
+
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			spreading_depth: 0,
+			..Default::default()
+		};
+		let base_levels = |reconsolidation| {
+			let input = RetrievalInput {
+				probe_embedding: &probe,
+				memory_embeddings: &memories,
+				access_histories_ms: &histories,
+				emotional_weights: &[],
+				decay_rates: &[0.5, 0.5],
+				working_memory_boosts: &[],
+				associations: &[],
+				current_time_ms: now,
+				reconsolidation,
+			};
+			let mut candidates = retrieve(&input, &config);
+			candidates.sort_by_key(|c| c.index);
+			candidates.iter().map(|c| c.base_level).collect::<Vec<_>>()
+		};
+
+		let plain = base_levels(None);
+		assert!((plain[0] - plain[1]).abs() < 1e-12);
+
+		// The labile memory decays faster, the reinforced one is unchanged
+		let zoned = base_levels(Some(recent_and_old));
+		assert!(zoned[0] < plain[0]);
+		assert!((zoned[1] - plain[1]).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_input_builder_matches_literal() {
+		let memories = vec![vec![1.0, 0.0], vec![0.6, 0.8]];
+		let probe = vec![1.0, 0.0];
+		let now = 10_000_000.0;
+		let histories = vec![vec![now - 3_600_000.0]; 2];
+		let associations = [Association {
+			source: 0,
+			target: 1,
+			forward_strength: 0.5,
+			backward_strength: 0.5,
+		}];
+		let reconsolidation = || ReconsolidationInput {
+			surprise: 0.3,
+			access_counts: &[1, 1],
+			days_since_access: &[0.1, 5.0],
+			reconsolidating_decay_rate: 0.9,
+			config: ReconsolidationConfig::default(),
+		};
+
+		let minimal = RetrievalInput::new(&probe, &memories, &histories, now);
+		assert!(minimal.emotional_weights.is_empty());
+		assert!(minimal.decay_rates.is_empty());
+		assert!(minimal.working_memory_boosts.is_empty());
+		assert!(minimal.associations.is_empty());
+		assert!(minimal.reconsolidation.is_none());
+		assert!(minimal.validate().is_ok());
+
+		let built = RetrievalInput::new(&probe, &memories, &histories, now)
+			.with_emotional_weights(&[0.9, 0.1])
+			.with_decay_rates(&[0.5, 0.4])
+			.with_working_memory_boosts(&[1.0, 1.5])
+			.with_associations(&associations)
+			.with_reconsolidation(reconsolidation());
+		let literal = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[0.9, 0.1],
+			decay_rates: &[0.5, 0.4],
+			working_memory_boosts: &[1.0, 1.5],
+			associations: &associations,
+			current_time_ms: now,
+			reconsolidation: Some(reconsolidation()),
+		};
+
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+		let summary = |input: &RetrievalInput<'_>| {
+			retrieve(input, &config)
+				.iter()
+				.map(|c| (c.index, c.total_activation))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(summary(&built), summary(&literal));
+	}
+
+	#[test]
+	fn test_prenormalized_matches_general_path() {
+		let probe = vec![0.6, 0.8, 0.0];
+		let memories: Vec<Vec<f64>> = [
+			[1.0, 0.0, 0.0],
+			[0.0, 1.0, 0.0],
+			[0.6, 0.0, 0.8],
+			[0.48, 0.64, 0.6],
+			[0.0, 0.0, 1.0],
+		]
+		.iter()
+		.map(|m| m.to_vec())
+		.collect();
+		let now = 1_000_000.0;
+		let histories = vec![vec![now - 1000.0]; 5];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		let general = retrieve(&input, &config);
+		let fast = retrieve(
+			&input,
+			&RetrievalConfig {
+				embeddings_prenormalized: true,
+				..config
+			},
+		);
+
+		assert_eq!(general.len(), fast.len());
+		for (g, f) in general.iter().zip(&fast) {
+			assert_eq!(g.index, f.index);
+			assert!((g.total_activation - f.total_activation).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_max_spreading_seeds_changes_spreading() {
+		let fixture = SixthSeedFixture::new();
+		let input = RetrievalInput::new(
+			&fixture.probe,
+			&fixture.memories,
+			&fixture.histories,
+			fixture.now,
+		)
+		.with_associations(&fixture.associations);
+
+		let spreading_to_target = |max_spreading_seeds| {
+			let config = RetrievalConfig {
+				max_spreading_seeds,
+				..Default::default()
+			};
+			retrieve_full_scores(&input, &config).spreading[SixthSeedFixture::TARGET]
+		};
+
+		assert!(spreading_to_target(5).abs() < f64::EPSILON);
+		assert!(spreading_to_target(6) > 0.0);
+	}
+
+	#[test]
+	fn test_retrieve_checked_reports_mismatched_indices() {
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![1.0, 0.0], // Embedded with a different model
+			vec![0.0, 1.0, 0.0],
+			vec![0.5, 0.5, 0.5, 0.5], // Embedded with yet another model
+		];
+		let histories = vec![vec![1000.0]; 4];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: 2000.0,
+			reconsolidation: None,
+		};
+
+		let lenient = RetrievalConfig::default();
+		assert_eq!(
+			retrieve_checked(&input, &lenient).map(|c| c.len()),
+			Ok(retrieve(&input, &lenient).len())
+		);
+
+		let strict = RetrievalConfig {
+			strict_dimensions: true,
+			..Default::default()
+		};
+		assert_eq!(
+			retrieve_checked(&input, &strict).err(),
+			Some(DimensionMismatchError {
+				probe_dimension: 3,
+				mismatched_indices: vec![1, 3],
+			})
+		);
+	}
+
+	#[test]
+	fn test_equal_activations_come_back_in_index_order() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![vec![1.0, 0.0]; 6];
+		let histories = vec![vec![1000.0]; 6];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: 2000.0,
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		let indices: Vec<usize> = retrieve(&input, &config).iter().map(|c| c.index).collect();
+		assert_eq!(indices, vec![0, 1, 2, 3, 4, 5]);
+		assert_eq!(retrieve_by_similarity(&probe, &memories, 3), vec![0, 1, 2]);
+	}
+
+	#[test]
+	fn test_cost_estimate_scaling() {
+		let config = RetrievalConfig::default();
+
+		// Without a graph the cost is linear in memory count
+		let small = estimate_retrieval_cost(1_000, 384, 0, &config);
+		let large = estimate_retrieval_cost(2_000, 384, 0, &config);
+		assert!((large.similarity_flops / small.similarity_flops - 2.0).abs() < 1e-12);
+		assert!((large.estimated_ms / small.estimated_ms - 2.0).abs() < 1e-12);
+		assert!(small.spreading_node_visits.abs() < f64::EPSILON);
+
+		// Denser graphs reach more nodes, up to the node limit however many
+		// of the n² possible edges exist
+		let n = 500;
+		let mut previous = 0.0;
+		for edges in [n / 2, n, 4 * n, n * n] {
+			let estimate = estimate_retrieval_cost(n, 384, edges, &config);
+			assert!(estimate.spreading_node_visits >= previous);
+			assert!(estimate.spreading_node_visits <= 500.0);
+			previous = estimate.spreading_node_visits;
+		}
+		assert!((previous - 500.0).abs() < 1e-9);
+
+		let shallow = RetrievalConfig {
+			spreading_depth: 0,
+			..RetrievalConfig::default()
+		};
+		let no_spread = estimate_retrieval_cost(n, 384, n * n, &shallow);
+		assert!(no_spread.spreading_node_visits.abs() < f64::EPSILON);
+		assert!(
+			estimate_retrieval_cost(0, 384, 10, &config)
+				.estimated_ms
+				.abs() < f64::EPSILON
+		);
+	}
+
+	#[test]
+	fn test_wm_cap_differentiates_exact_matches() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![vec![1.0, 0.0], vec![1.0, 0.0]];
+		let histories = vec![vec![1000.0], vec![1000.0]];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[1.0, 1.5], // Second one is in working memory
+			associations: &[],
+			current_time_ms: 2000.0,
+			reconsolidation: None,
+		};
+
+		let capped = retrieve_full_scores(&input, &RetrievalConfig::default());
+		assert!((capped.probe_activation[0] - capped.probe_activation[1]).abs() < 1e-12);
+
+		let uncapped = retrieve_full_scores(
+			&input,
+			&RetrievalConfig {
+				wm_cap: 2.0,
+				..Default::default()
+			},
+		);
+		assert!((uncapped.probe_activation[0] - 1.0).abs() < 1e-12);
+		assert!((uncapped.probe_activation[1] - 1.5_f64.powi(3)).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_offset_pages_through_ranked_results() {
+		let probe = vec![1.0, 0.0];
+		// Pairs of equal similarity, so pages have ties to break
+		let memories: Vec<Vec<f64>> = (0..8).map(|i| vec![1.0, f64::from(i / 2) * 0.2]).collect();
+		let histories = vec![vec![1000.0]; 8];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: 2000.0,
+			reconsolidation: None,
+		};
+		let page = |offset, max_results| -> Vec<usize> {
+			let config = RetrievalConfig {
+				min_probability: 0.0,
+				max_results,
+				offset,
+				..Default::default()
+			};
+			retrieve(&input, &config).iter().map(|c| c.index).collect()
+		};
+
+		let full = page(0, 8);
+		let first = page(0, 3);
+		let second = page(3, 3);
+
+		assert_eq!(full.len(), 8);
+		assert!(first.iter().all(|i| !second.contains(i)));
+		assert_eq!([first, second].concat(), full[..6]);
+		assert!(page(8, 3).is_empty());
+	}
+
+	#[test]
+	fn test_emotional_multiplier_scale_widens_gap() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![vec![1.0, 0.0], vec![1.0, 0.0]];
+		let histories = vec![vec![1000.0], vec![1000.0]];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[1.0, 0.5], // Emotional, neutral
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: 2000.0,
+			reconsolidation: None,
+		};
+		let gap = |scale: f64| {
+			let candidates = retrieve(
+				&input,
+				&RetrievalConfig {
+					min_probability: 0.0,
+					spreading_depth: 0,
+					emotional_multiplier_scale: scale,
+					..Default::default()
+				},
+			);
+			let activation = |index: usize| {
+				candidates
+					.iter()
+					.find(|c| c.index == index)
+					.map_or(f64::NAN, |c| c.total_activation)
+			};
+			activation(0) - activation(1)
+		};
+
+		// Neutral memories are unaffected, so the gap grows with the scale
+		assert!((gap(2.0) / gap(1.0) - 2.0).abs() < 1e-9);
+		assert!(gap(2.0) > gap(1.0));
+		assert!(gap(0.0).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_retrieval_result_json_round_trip() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![vec![1.0, 0.1], vec![0.5, 0.5]];
+		let histories = vec![vec![1000.0], vec![500.0]];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: 2000.0,
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			normalize_activations: true,
+			..Default::default()
+		};
+		let candidates = retrieve(&input, &config);
+		assert!(!candidates.is_empty());
+
+		let Ok(json) = retrieval_result_to_json(&candidates) else {
+			unreachable!("candidates always serialize")
+		};
+		let Ok(parsed) = serde_json::from_str::<Vec<RetrievalCandidate>>(&json) else {
+			unreachable!("serialized candidates parse back")
+		};
+		assert_eq!(parsed.len(), candidates.len());
+		for (a, b) in parsed.iter().zip(&candidates) {
+			assert_eq!(a.index, b.index);
+			assert!((a.total_activation - b.total_activation).abs() < 1e-12);
+			assert!((a.probability - b.probability).abs() < 1e-12);
+			assert_eq!(a.normalized_activation, b.normalized_activation);
+		}
+	}
+
+	#[test]
+	fn test_retrieve_multi_probe_surfaces_each_probe_match() {
+		let probes = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let memories = vec![
+			vec![1.0, 0.0], // Best match for the first probe
+			vec![0.0, 1.0], // Best match for the second probe
+			vec![0.6, 0.8], // Partial match for both
+		];
+		let histories = vec![vec![]; 3];
+		let input = RetrievalInput {
+			probe_embedding: &[],
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: 1_000_000.0,
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+		let top_two = |weights: &[f64], mode| {
+			let mut indices: Vec<usize> =
+				retrieve_multi_probe(&probes, weights, mode, &input, &config)
+					.iter()
+					.take(2)
+					.map(|c| c.index)
+					.collect();
+			indices.sort_unstable();
+			indices
+		};
+
+		assert_eq!(top_two(&[], MultiProbeMode::WeightedMax), vec![0, 1]);
+		// Averaging favors the memory relevant to both probes
+		assert_eq!(
+			top_two(&[1.0, 2.0], MultiProbeMode::WeightedMean),
+			vec![1, 2]
+		);
+		// Weighting one probe out leaves single-probe ranking
+		assert_eq!(
+			top_two(&[1.0, 0.0], MultiProbeMode::WeightedMean),
+			vec![0, 2]
+		);
+		assert!(
+			retrieve_multi_probe(&[], &[], MultiProbeMode::WeightedMax, &input, &config).is_empty()
+		);
+	}
+
+	#[test]
+	fn test_seed_activation_floor_limits_spreading() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0],  // Strong match (probe activation 1.0)
+			vec![0.6, 0.8],  // Weak match (probe activation ~0.22)
+			vec![0.0, 1.0],  // Only linked from the strong match
+			vec![-0.6, 0.8], // Only linked from the weak match
+		];
+		let now = 1_000_000.0;
+		let histories = vec![vec![now]; 4];
+		let associations = [
+			Association {
+				source: 0,
+				target: 2,
+				forward_strength: 1.0,
+				backward_strength: 0.0,
+			},
+			Association {
+				source: 1,
+				target: 3,
+				forward_strength: 1.0,
+				backward_strength: 0.0,
+			},
+		];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &associations,
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+
+		let spreading = |seed_activation_floor| {
+			let config = RetrievalConfig {
+				seed_activation_floor,
+				..Default::default()
+			};
+			retrieve_full_scores(&input, &config).spreading
+		};
+
+		let default_floor = spreading(None);
+		assert!(default_floor[2] > 0.0);
+		assert!(default_floor[3] > 0.0);
+		assert_eq!(spreading(Some(0.1)), default_floor);
+
+		let raised_floor = spreading(Some(0.5));
+		assert!(raised_floor[2] > 0.0);
+		assert!(raised_floor[3].abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_seed_floor_defaults_per_seed_mode() {
+		let probe = vec![1.0, 0.0];
+		// Additive seed of the weak match is 0.3³ ≈ 0.027: above 0, below 0.1
+		let memories = vec![vec![0.3, 0.91_f64.sqrt()], vec![0.0, 1.0]];
+		let now = 1_000_000.0;
+		let histories = vec![vec![now]; 2];
+		let associations = [Association {
+			source: 0,
+			target: 1,
+			forward_strength: 1.0,
+			backward_strength: 0.0,
+		}];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &associations,
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+
+		let spreading = |seed_activation_floor| {
+			let config = RetrievalConfig {
+				seed_mode: SpreadingSeedMode::Additive,
+				seed_activation_floor,
+				..Default::default()
+			};
+			retrieve_full_scores(&input, &config).spreading[1]
+		};
+
+		// Additive keeps its floor of 0 unless one is set explicitly
+		assert!(spreading(None) > 0.0);
+		assert!(spreading(Some(0.1)).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_additive_combine_lets_recency_win() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![
+			vec![0.1, 0.995], // Barely similar, accessed just now
+			vec![0.6, 0.8],   // Fairly similar, accessed long ago
+		];
+		let now = 1_000_000_000.0;
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now - 1000.0], vec![1000.0]],
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+
+		let totals = |combine_mode| {
+			let config = RetrievalConfig {
+				combine_mode,
+				..Default::default()
+			};
+			retrieve_full_scores(&input, &config).total_activation
+		};
+
+		let multiplicative = totals(CombineMode::Multiplicative);
+		assert!(multiplicative[1] > multiplicative[0]);
+
+		let additive = totals(CombineMode::Additive);
+		assert!(additive[0] > additive[1]);
+	}
+
+	#[test]
+	fn test_latency_factor_scales_latency() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![vec![1.0, 0.0], vec![0.6, 0.8]];
+		let now = 1_000_000.0;
+		let histories = vec![vec![now - 1000.0]; 2];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+
+		let latencies = |latency_factor| {
+			let config = RetrievalConfig {
+				latency_factor,
+				min_probability: 0.0,
+				..Default::default()
+			};
+			retrieve(&input, &config)
+				.iter()
+				.map(|c| c.latency_ms)
+				.collect::<Vec<_>>()
+		};
+
+		let base = latencies(1.0);
+		let doubled = latencies(2.0);
+		assert_eq!(base.len(), 2);
+		// Stronger memories are retrieved faster
+		assert!(base[0] < base[1]);
+		for (b, d) in base.iter().zip(&doubled) {
+			assert!((d / b - 2.0).abs() < 1e-9);
+		}
+	}
+
+	#[test]
+	fn test_cold_start_boost_lifts_new_memories() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0], // Exact match, never accessed
+			vec![0.8, 0.6], // Weaker match, accessed every minute for 20 minutes
+		];
+		let now = 1_000_000_000.0;
+		let frequent: Vec<f64> = (1..=20)
+			.map(|k| f64::from(k).mul_add(-60_000.0, now))
+			.collect();
+		let histories = vec![Vec::new(), frequent];
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+
+		let top = |cold_start_boost| {
+			let config = RetrievalConfig {
+				cold_start_boost,
+				min_probability: 0.0,
+				..Default::default()
+			};
+			retrieve(&input, &config)[0].index
+		};
+
+		assert_eq!(top(0.0), 1);
+		assert_eq!(top(1.0), 0);
+	}
+
+	#[test]
+	fn test_retrieve_with_trace_chain_depths() {
+		// Only memory 0 matches the probe; 1-3 hang off it in a chain
+		let probe = vec![1.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0],
+			vec![0.0, 1.0],
+			vec![0.0, 1.0],
+			vec![0.0, 1.0],
+		];
+		let now = 1_000_000.0;
+		let histories = vec![vec![now]; 4];
+		let associations: Vec<Association> = [(0, 1), (1, 2), (2, 3)]
+			.into_iter()
+			.map(|(source, target)| Association {
+				source,
+				target,
+				forward_strength: 1.0,
+				backward_strength: 0.0,
+			})
+			.collect();
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &associations,
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+		let config = RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		let traced = retrieve_with_trace(&input, &config);
+		let depth_of = |index| {
+			traced
+				.iter()
+				.find(|t| t.candidate.index == index)
+				.and_then(|t| t.spread_depth)
+		};
+
+		assert_eq!(traced.len(), 4);
+		assert_eq!(depth_of(0), None);
+		assert_eq!(depth_of(1), Some(1));
+		assert_eq!(depth_of(2), Some(2));
+		assert_eq!(depth_of(3), Some(3));
+
+		// Same ranking as plain retrieval
+		let plain: Vec<usize> = retrieve(&input, &config).iter().map(|c| c.index).collect();
+		let traced: Vec<usize> = traced.iter().map(|t| t.candidate.index).collect();
+		assert_eq!(plain, traced);
+	}
+
+	#[test]
+	fn test_surprise_similar() {
+		let a = vec![1.0, 0.0, 0.0];
+		let b = vec![1.0, 0.0, 0.0];
+		let surprise = compute_surprise(&a, &b, 1.0, 0.5, 0.5);
+		assert!(surprise < 0.1); // Low surprise for identical
+	}
+
+	#[test]
+	fn test_surprise_different() {
+		let a = vec![1.0, 0.0, 0.0];
+		let b = vec![0.0, 1.0, 0.0];
+		let surprise = compute_surprise(&a, &b, 1.0, 0.5, 0.5);
+		assert!(surprise > 0.5); // High surprise for orthogonal
+	}
+
+	#[test]
+	fn test_surprise_batch_matches_scalar() {
+		let expected = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![0.6, 0.8, 0.0],
+			vec![0.0, 0.0, 1.0],
+		];
+		let actual = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![0.8, 0.6, 0.0],
+			vec![0.0, 1.0, 0.0],
+		];
+		let ages = [1.0, 30.0, 365.0];
+		let strengths = [0.2, 0.5, 0.9];
+
+		let batch = compute_surprise_batch(&expected, &actual, &ages, &strengths, 0.5);
+
+		assert_eq!(batch.len(), 3);
+		for i in 0..3 {
+			let scalar = compute_surprise(&expected[i], &actual[i], ages[i], strengths[i], 0.5);
+			assert!((batch[i] - scalar).abs() < f64::EPSILON);
+		}
 	}
 
 	#[test]
@@ -405,6 +2759,7 @@ mod tests {
 			working_memory_boosts: &[1.0, 2.0], // Memory 1 gets 2x WM boost
 			associations: &[],
 			current_time_ms: now,
+			reconsolidation: None,
 		};
 
 		let config = RetrievalConfig {
@@ -441,6 +2796,7 @@ mod tests {
 			working_memory_boosts: &[2.0], // 2x boost would exceed 1.0, should cap
 			associations: &[],
 			current_time_ms: now,
+			reconsolidation: None,
 		};
 
 		let config = RetrievalConfig {
@@ -0,0 +1,192 @@
+//! Retrieval Session
+//!
+//! Owned per-memory state that lives across many retrievals.
+//!
+//! `retrieve` recomputes every memory's base-level from its full access
+//! history on each call. A session instead keeps the histories and caches the
+//! base-level sum `Σ(t_k)^(-d)` for each memory at a reference time, so
+//! recording a new access is a single term added to the cache rather than a
+//! pass over the whole history. `retrieve_with_session` ranks with these
+//! cached base-levels instead of summing histories.
+//!
+//! ## Numerical Stability
+//!
+//! Every `push_access` adds one term to a running `f64` sum, which rounds once
+//! per push. Over `n` pushes the cached sum can drift from a fresh summation
+//! by roughly `n · ε` relative (ε ≈ 2.2e-16), far below anything that affects
+//! ranking. Moving the reference time with `advance_to` changes every term,
+//! so it recomputes all sums from scratch and discards any accumulated drift.
+
+use crate::activation::compute_base_level;
+
+/// Per-memory access state with cached base-level sums.
+#[derive(Clone, Debug)]
+pub struct RetrievalSession {
+	access_histories_ms: Vec<Vec<f64>>,
+	decay_rates: Vec<f64>,
+	/// `Σ(t_k)^(-d)` per memory, valid at `reference_time_ms`
+	base_level_sums: Vec<f64>,
+	reference_time_ms: f64,
+}
+
+impl RetrievalSession {
+	/// Create a session and compute base-level sums at `reference_time_ms`.
+	///
+	/// Memories without an entry in `decay_rates` use `default_decay_rate`.
+	#[must_use]
+	pub fn new(
+		access_histories_ms: Vec<Vec<f64>>,
+		decay_rates: &[f64],
+		default_decay_rate: f64,
+		reference_time_ms: f64,
+	) -> Self {
+		let decay_rates: Vec<f64> = (0..access_histories_ms.len())
+			.map(|i| decay_rates.get(i).copied().unwrap_or(default_decay_rate))
+			.collect();
+		let mut session = Self {
+			base_level_sums: vec![0.0; access_histories_ms.len()],
+			access_histories_ms,
+			decay_rates,
+			reference_time_ms,
+		};
+		session.recompute();
+		session
+	}
+
+	/// Number of memories in the session.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.access_histories_ms.len()
+	}
+
+	/// Whether the session holds no memories.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.access_histories_ms.is_empty()
+	}
+
+	/// Time (ms) at which the cached base-levels are valid.
+	#[must_use]
+	pub const fn reference_time_ms(&self) -> f64 {
+		self.reference_time_ms
+	}
+
+	/// Access histories for every memory, suitable for a `RetrievalInput`.
+	#[must_use]
+	pub fn access_histories(&self) -> &[Vec<f64>] {
+		&self.access_histories_ms
+	}
+
+	/// Per-memory decay rates, suitable for a `RetrievalInput`.
+	#[must_use]
+	pub fn decay_rates(&self) -> &[f64] {
+		&self.decay_rates
+	}
+
+	/// Cached base-level activation for a memory at the reference time.
+	///
+	/// Returns `None` if `index` is out of range, and negative infinity for a
+	/// memory that has never been accessed (matching `compute_base_level`).
+	#[must_use]
+	pub fn base_level(&self, index: usize) -> Option<f64> {
+		let sum = *self.base_level_sums.get(index)?;
+		Some(if self.access_histories_ms[index].is_empty() {
+			f64::NEG_INFINITY
+		} else {
+			sum.ln()
+		})
+	}
+
+	/// Record one access and update that memory's cached base-level sum.
+	///
+	/// Accesses later than the reference time count as one second old, the
+	/// same floor `compute_base_level` applies.
+	///
+	/// # Returns
+	///
+	/// `false` if `index` is out of range (nothing is recorded).
+	pub fn push_access(&mut self, index: usize, timestamp_ms: f64) -> bool {
+		let Some(history) = self.access_histories_ms.get_mut(index) else {
+			return false;
+		};
+		history.push(timestamp_ms);
+
+		let time_since_access_s = (self.reference_time_ms - timestamp_ms).max(1000.0) / 1000.0;
+		self.base_level_sums[index] += time_since_access_s.powf(-self.decay_rates[index]);
+		true
+	}
+
+	/// Move the reference time and recompute every cached sum.
+	pub fn advance_to(&mut self, reference_time_ms: f64) {
+		self.reference_time_ms = reference_time_ms;
+		self.recompute();
+	}
+
+	fn recompute(&mut self) {
+		for (i, history) in self.access_histories_ms.iter().enumerate() {
+			let base_level =
+				compute_base_level(history, self.reference_time_ms, self.decay_rates[i]);
+			self.base_level_sums[i] = if base_level.is_finite() {
+				base_level.exp()
+			} else {
+				0.0
+			};
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_push_access_matches_full_recompute() {
+		let now = 10_000_000.0;
+		let mut session =
+			RetrievalSession::new(vec![vec![now - 500_000.0], vec![]], &[0.5, 0.3], 0.5, now);
+
+		for (index, timestamp) in [
+			(0, now - 200_000.0),
+			(1, now - 90_000.0),
+			(0, now - 10_000.0),
+			(1, now - 5_000.0),
+			(0, now),
+		] {
+			assert!(session.push_access(index, timestamp));
+		}
+
+		for i in 0..session.len() {
+			let expected = compute_base_level(
+				&session.access_histories()[i],
+				now,
+				session.decay_rates()[i],
+			);
+			let cached = session.base_level(i).unwrap_or(f64::NAN);
+			assert!((cached - expected).abs() < 1e-12, "memory {i}");
+		}
+	}
+
+	#[test]
+	fn test_never_accessed_is_negative_infinity() {
+		let session = RetrievalSession::new(vec![vec![]], &[], 0.5, 1_000_000.0);
+		assert_eq!(session.base_level(0), Some(f64::NEG_INFINITY));
+		assert_eq!(session.base_level(1), None);
+	}
+
+	#[test]
+	fn test_advance_to_recomputes() {
+		let mut session = RetrievalSession::new(vec![vec![0.0]], &[], 0.5, 10_000.0);
+		let _ = session.push_access(0, 5_000.0);
+		session.advance_to(100_000.0);
+
+		let expected = compute_base_level(&[0.0, 5_000.0], 100_000.0, 0.5);
+		let cached = session.base_level(0).unwrap_or(f64::NAN);
+		assert!((cached - expected).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_push_access_out_of_range() {
+		let mut session = RetrievalSession::new(vec![vec![]], &[], 0.5, 0.0);
+		assert!(!session.push_access(3, 0.0));
+	}
+}
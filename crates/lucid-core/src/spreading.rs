@@ -13,7 +13,7 @@
 //! - `S_ij` = associative strength between i and j
 
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
 
 /// Adjacency list type for graph edges: Vec of (`target_index`, weight) pairs per node.
 type AdjacencyList = Vec<Vec<(usize, f64)>>;
@@ -38,6 +38,48 @@ pub struct SpreadingResult {
 	pub activations: Vec<f64>,
 	/// Which nodes were visited at each depth
 	pub visited_by_depth: Vec<Vec<usize>>,
+	/// Number of edges activation was spread along
+	pub relaxations: usize,
+	/// Whether the forward graph has a cycle (`None` unless
+	/// `SpreadingConfig::warn_on_cycles` is set)
+	pub has_cycles: Option<bool>,
+}
+
+/// How spread activation is divided across an edge.
+///
+/// `BySource` is the textbook `W_i / n_i`: a cue linked to many memories is
+/// a weaker cue for each of them (the fan effect). `ByTarget` divides by the
+/// receiving node's in-degree instead, modelling associative interference at
+/// the memory itself: a trace reachable from many cues gets a smaller share
+/// from each, so densely linked hubs don't accumulate activation from every
+/// neighbour.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FanNormalization {
+	/// Divide by the source's out-degree
+	#[default]
+	BySource,
+	/// Divide by the target's in-degree
+	ByTarget,
+	/// No division; every edge carries the full source activation
+	None,
+}
+
+impl FanNormalization {
+	/// Divisor for an edge from a source with `source_fan` outgoing edges to a
+	/// target with `target_fan_in` incoming edges.
+	#[allow(clippy::cast_precision_loss)]
+	const fn divisor(self, source_fan: usize, target_fan_in: usize) -> f64 {
+		let fan = match self {
+			Self::BySource => source_fan,
+			Self::ByTarget => target_fan_in,
+			Self::None => 1,
+		};
+		if fan > 1 {
+			fan as f64
+		} else {
+			1.0
+		}
+	}
 }
 
 /// Configuration for spreading activation.
@@ -51,6 +93,17 @@ pub struct SpreadingConfig {
 	pub max_nodes: usize,
 	/// Whether to spread bidirectionally
 	pub bidirectional: bool,
+	/// Extra multiplier on backward spreading, on top of `backward_strength`
+	/// (1.0 lets the edge strength alone govern it)
+	pub backward_decay: f64,
+	/// Which node's fan divides the spread activation
+	pub fan_normalization: FanNormalization,
+	/// Check the graph for cycles and report it in `SpreadingResult::has_cycles`
+	pub warn_on_cycles: bool,
+	/// Maximum edge relaxations (`None` = unlimited). `max_nodes` only counts
+	/// distinct nodes, so dense graphs with heavy fan-in can still spread
+	/// along every edge; this caps the work itself.
+	pub visit_budget: Option<usize>,
 }
 
 impl Default for SpreadingConfig {
@@ -60,10 +113,126 @@ impl Default for SpreadingConfig {
 			minimum_activation: 0.01,
 			max_nodes: 1000,
 			bidirectional: true,
+			backward_decay: 0.7,
+			fan_normalization: FanNormalization::default(),
+			warn_on_cycles: false,
+			visit_budget: None,
 		}
 	}
 }
 
+/// How [`merge_associations_with_mode`] combines the strengths of duplicate edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssociationMergeMode {
+	/// Keep the strongest of the duplicates in each direction
+	#[default]
+	Max,
+	/// Add the duplicates' strengths together
+	Sum,
+}
+
+/// Collapse duplicate directed `(source, target)` edges into one, keeping the
+/// maximum forward and backward strengths.
+///
+/// Graphs built from several sources often repeat edges, and every copy
+/// counts towards the fan, so spreading through an unmerged graph dilutes
+/// each real association.
+#[must_use]
+pub fn merge_associations(associations: &[Association]) -> Vec<Association> {
+	merge_associations_with_mode(associations, AssociationMergeMode::Max)
+}
+
+/// Collapse duplicate directed edges, combining their strengths with `mode`.
+///
+/// Edges keep the order in which each pair first appears. `a → b` and
+/// `b → a` are different edges and are not merged.
+#[must_use]
+pub fn merge_associations_with_mode(
+	associations: &[Association],
+	mode: AssociationMergeMode,
+) -> Vec<Association> {
+	let mut merged: Vec<Association> = Vec::with_capacity(associations.len());
+	let mut positions: HashMap<(usize, usize), usize> = HashMap::new();
+
+	for assoc in associations {
+		match positions.entry((assoc.source, assoc.target)) {
+			Entry::Occupied(entry) => {
+				let existing = &mut merged[*entry.get()];
+				match mode {
+					AssociationMergeMode::Max => {
+						existing.forward_strength =
+							existing.forward_strength.max(assoc.forward_strength);
+						existing.backward_strength =
+							existing.backward_strength.max(assoc.backward_strength);
+					}
+					AssociationMergeMode::Sum => {
+						existing.forward_strength += assoc.forward_strength;
+						existing.backward_strength += assoc.backward_strength;
+					}
+				}
+			}
+			Entry::Vacant(entry) => {
+				let _ = entry.insert(merged.len());
+				merged.push(assoc.clone());
+			}
+		}
+	}
+
+	merged
+}
+
+/// Marks a removed node in the index map returned by [`compact_graph`].
+pub const REMOVED_NODE: usize = usize::MAX;
+
+/// Drop nodes from the association graph and renumber the survivors.
+///
+/// `keep_mask[i]` says whether node `i` survives; nodes without an entry are
+/// removed. Surviving nodes keep their relative order and are numbered
+/// `0..kept`. Edges touching a removed node are dropped, and the rest are
+/// rewritten with the new indices.
+///
+/// Returns the compacted associations and an old → new index map of length
+/// `num_nodes`, holding [`REMOVED_NODE`] for removed nodes. Run
+/// [`decay_associations`](crate::activation::decay_associations) first to
+/// prune weak edges as part of the same maintenance pass.
+#[must_use]
+pub fn compact_graph(
+	num_nodes: usize,
+	associations: &[Association],
+	keep_mask: &[bool],
+) -> (Vec<Association>, Vec<usize>) {
+	let mut next = 0;
+	let index_map: Vec<usize> = (0..num_nodes)
+		.map(|i| {
+			if keep_mask.get(i).copied().unwrap_or(false) {
+				next += 1;
+				next - 1
+			} else {
+				REMOVED_NODE
+			}
+		})
+		.collect();
+
+	let remap = |node: usize| {
+		index_map
+			.get(node)
+			.copied()
+			.filter(|&new| new != REMOVED_NODE)
+	};
+	let compacted = associations
+		.iter()
+		.filter_map(|assoc| {
+			Some(Association {
+				source: remap(assoc.source)?,
+				target: remap(assoc.target)?,
+				..assoc.clone()
+			})
+		})
+		.collect();
+
+	(compacted, index_map)
+}
+
 /// Build adjacency lists from associations.
 fn build_adjacency(
 	associations: &[Association],
@@ -107,6 +276,61 @@ pub fn spread_activation(
 	seed_activations: &[f64],
 	config: &SpreadingConfig,
 	depth: usize,
+) -> SpreadingResult {
+	spread_with_edge_hook(
+		num_nodes,
+		associations,
+		seed_indices,
+		seed_activations,
+		config,
+		depth,
+		|_, _, _| {},
+	)
+}
+
+/// Spreading activation like [`spread_activation`], also logging which
+/// edges carried activation.
+///
+/// Each `(source, target, amount)` entry is one edge traversal that spread
+/// more than `config.minimum_activation`, in the order they were relaxed;
+/// an edge relaxed at several depths appears once per depth. Slower than
+/// [`spread_activation`], so use it to explain a retrieval, not to run one.
+#[must_use]
+pub fn spread_activation_traced(
+	num_nodes: usize,
+	associations: &[Association],
+	seed_indices: &[usize],
+	seed_activations: &[f64],
+	config: &SpreadingConfig,
+	depth: usize,
+) -> (SpreadingResult, Vec<(usize, usize, f64)>) {
+	let mut edges = Vec::new();
+	let result = spread_with_edge_hook(
+		num_nodes,
+		associations,
+		seed_indices,
+		seed_activations,
+		config,
+		depth,
+		|source, target, amount| {
+			if amount > config.minimum_activation {
+				edges.push((source, target, amount));
+			}
+		},
+	);
+	(result, edges)
+}
+
+/// Shared spreading loop; `on_edge(source, target, amount)` sees every edge
+/// traversal.
+fn spread_with_edge_hook(
+	num_nodes: usize,
+	associations: &[Association],
+	seed_indices: &[usize],
+	seed_activations: &[f64],
+	config: &SpreadingConfig,
+	depth: usize,
+	mut on_edge: impl FnMut(usize, usize, f64),
 ) -> SpreadingResult {
 	let (forward_adj, backward_adj) = build_adjacency(associations, num_nodes);
 
@@ -122,10 +346,12 @@ pub fn spread_activation(
 	let mut visited_by_depth: Vec<Vec<usize>> = vec![seed_indices.to_vec()];
 	let mut frontier: Vec<usize> = seed_indices.to_vec();
 	let mut total_visited = frontier.len();
+	let mut relaxations = 0;
+	let visit_budget = config.visit_budget.unwrap_or(usize::MAX);
 
 	// Spread for each depth level
 	for _ in 0..depth {
-		if total_visited >= config.max_nodes {
+		if total_visited >= config.max_nodes || relaxations >= visit_budget {
 			break;
 		}
 
@@ -133,6 +359,10 @@ pub fn spread_activation(
 		let mut next_activations: HashMap<usize, f64> = HashMap::new();
 
 		for &source_idx in &frontier {
+			if relaxations >= visit_budget {
+				break;
+			}
+
 			let source_activation = activations[source_idx];
 			if source_activation < config.minimum_activation {
 				continue;
@@ -140,18 +370,23 @@ pub fn spread_activation(
 
 			// Forward spreading
 			let forward_edges = &forward_adj[source_idx];
-			#[allow(clippy::cast_precision_loss)]
-			let fan = forward_edges.len().max(1) as f64;
 
 			for &(target_idx, strength) in forward_edges {
-				if total_visited >= config.max_nodes {
+				if total_visited >= config.max_nodes || relaxations >= visit_budget {
 					break;
 				}
+				relaxations += 1;
+
+				// In-degree of a forward target = its backward edge count
+				let fan = config
+					.fan_normalization
+					.divisor(forward_edges.len(), backward_adj[target_idx].len());
 
 				// ACT-R spreading: A_j = Σ(W_i / n_i) × S_ij
 				let spread_amount = (source_activation / fan) * strength * config.decay_per_hop;
 
 				*next_activations.entry(target_idx).or_insert(0.0) += spread_amount;
+				on_edge(source_idx, target_idx, spread_amount);
 
 				if visited.insert(target_idx) {
 					next_frontier.push(target_idx);
@@ -162,19 +397,23 @@ pub fn spread_activation(
 			// Backward spreading (if enabled)
 			if config.bidirectional {
 				let backward_edges = &backward_adj[source_idx];
-				#[allow(clippy::cast_precision_loss)]
-				let back_fan = backward_edges.len().max(1) as f64;
 
 				for &(target_idx, strength) in backward_edges {
-					if total_visited >= config.max_nodes {
+					if total_visited >= config.max_nodes || relaxations >= visit_budget {
 						break;
 					}
+					relaxations += 1;
+
+					let back_fan = config
+						.fan_normalization
+						.divisor(backward_edges.len(), forward_adj[target_idx].len());
 
-					// Reduced strength for backward spreading
-					let spread_amount =
-						(source_activation / back_fan) * strength * config.decay_per_hop * 0.7;
+					let spread_amount = (source_activation / back_fan)
+						* strength * config.decay_per_hop
+						* config.backward_decay;
 
 					*next_activations.entry(target_idx).or_insert(0.0) += spread_amount;
+					on_edge(source_idx, target_idx, spread_amount);
 
 					if visited.insert(target_idx) {
 						next_frontier.push(target_idx);
@@ -201,9 +440,60 @@ pub fn spread_activation(
 	SpreadingResult {
 		activations,
 		visited_by_depth,
+		relaxations,
+		has_cycles: config
+			.warn_on_cycles
+			.then(|| detect_cycles(num_nodes, associations)),
 	}
 }
 
+/// Check whether the association graph has a directed cycle, following
+/// edges from source to target. Self-loops count as cycles.
+///
+/// Uses an iterative depth-first search, so deep graphs can't overflow the
+/// stack. Associations with out-of-range endpoints are ignored.
+#[must_use]
+pub fn detect_cycles(num_nodes: usize, associations: &[Association]) -> bool {
+	#[derive(Clone, Copy, PartialEq, Eq)]
+	enum Mark {
+		Unvisited,
+		OnStack,
+		Done,
+	}
+
+	let (forward_adj, _) = build_adjacency(associations, num_nodes);
+	let mut marks = vec![Mark::Unvisited; num_nodes];
+
+	for root in 0..num_nodes {
+		if marks[root] != Mark::Unvisited {
+			continue;
+		}
+
+		// (node, index of the next edge to follow)
+		let mut stack = vec![(root, 0)];
+		marks[root] = Mark::OnStack;
+
+		while let Some((node, next_edge)) = stack.last_mut() {
+			if let Some(&(target, _)) = forward_adj[*node].get(*next_edge) {
+				*next_edge += 1;
+				match marks[target] {
+					Mark::OnStack => return true,
+					Mark::Unvisited => {
+						marks[target] = Mark::OnStack;
+						stack.push((target, 0));
+					}
+					Mark::Done => {}
+				}
+			} else {
+				marks[*node] = Mark::Done;
+				let _ = stack.pop();
+			}
+		}
+	}
+
+	false
+}
+
 /// Get top k activated nodes.
 #[must_use]
 pub fn get_top_activated(activations: &[f64], top_k: usize) -> Vec<usize> {
@@ -214,7 +504,11 @@ pub fn get_top_activated(activations: &[f64], top_k: usize) -> Vec<usize> {
 		.map(|(i, &a)| (i, a))
 		.collect();
 
-	indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+	indexed.sort_by(|a, b| {
+		b.1.partial_cmp(&a.1)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.0.cmp(&b.0))
+	});
 
 	indexed.into_iter().take(top_k).map(|(i, _)| i).collect()
 }
@@ -310,6 +604,75 @@ pub fn compute_pagerank(
 	ranks
 }
 
+/// Summary of an association graph's shape, for tuning spreading.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphStats {
+	/// Number of nodes
+	pub num_nodes: usize,
+	/// Number of edges with both endpoints in range
+	pub num_edges: usize,
+	/// Mean out-degree over all nodes
+	pub average_fan_out: f64,
+	/// Largest out-degree of any node
+	pub max_fan_out: usize,
+	/// Nodes with no edges in either direction
+	pub isolated_nodes: usize,
+	/// Whether every node can reach every other ignoring edge direction
+	pub is_connected: bool,
+}
+
+/// Compute [`GraphStats`] for an association graph.
+///
+/// Fan-out counts edges leaving a node, as spreading does. Associations with
+/// out-of-range endpoints are ignored. An empty graph is reported as
+/// connected.
+#[must_use]
+pub fn graph_stats(num_nodes: usize, associations: &[Association]) -> GraphStats {
+	let (forward_adj, backward_adj) = build_adjacency(associations, num_nodes);
+
+	let num_edges: usize = forward_adj.iter().map(Vec::len).sum();
+	let max_fan_out = forward_adj.iter().map(Vec::len).max().unwrap_or(0);
+	let isolated_nodes = forward_adj
+		.iter()
+		.zip(&backward_adj)
+		.filter(|(out, inc)| out.is_empty() && inc.is_empty())
+		.count();
+
+	#[allow(clippy::cast_precision_loss)]
+	let average_fan_out = if num_nodes == 0 {
+		0.0
+	} else {
+		num_edges as f64 / num_nodes as f64
+	};
+
+	// Undirected reachability from node 0
+	let mut reached = vec![false; num_nodes];
+	let mut stack: Vec<usize> = (num_nodes > 0).then_some(0).into_iter().collect();
+	let mut reached_count = 0;
+	while let Some(node) = stack.pop() {
+		if std::mem::replace(&mut reached[node], true) {
+			continue;
+		}
+		reached_count += 1;
+		stack.extend(
+			forward_adj[node]
+				.iter()
+				.chain(&backward_adj[node])
+				.map(|&(neighbor, _)| neighbor)
+				.filter(|&neighbor| !reached[neighbor]),
+		);
+	}
+
+	GraphStats {
+		num_nodes,
+		num_edges,
+		average_fan_out,
+		max_fan_out,
+		isolated_nodes,
+		is_connected: reached_count == num_nodes,
+	}
+}
+
 // ============================================================================
 // Temporal Spreading (Episodic Memory - TCM)
 // ============================================================================
@@ -563,6 +926,60 @@ pub fn spread_temporal_activation_multi(
 	}
 }
 
+/// Spread activation through temporal links from several seed memories.
+///
+/// Each seed spreads independently; a memory reached from more than one seed
+/// keeps its strongest activation, as in [`spread_temporal_activation_multi`].
+/// Seeds and activations are paired by position, so extra entries in the
+/// longer slice are ignored.
+#[must_use]
+pub fn spread_temporal_activation_from_seeds(
+	num_memories: usize,
+	temporal_links: &[TemporalLink],
+	seed_memories: &[usize],
+	seed_activations: &[f64],
+	config: &TemporalSpreadingConfig,
+) -> TemporalSpreadingResult {
+	let mut combined_activations = vec![0.0_f64; num_memories];
+	let mut all_forward = Vec::new();
+	let mut all_backward = Vec::new();
+
+	for (&seed_memory, &seed_activation) in seed_memories.iter().zip(seed_activations) {
+		let result = spread_temporal_activation(
+			num_memories,
+			temporal_links,
+			seed_memory,
+			seed_activation,
+			config,
+		);
+
+		for (combined, a) in combined_activations.iter_mut().zip(result.activations) {
+			*combined = combined.max(a);
+		}
+
+		for m in result.forward_activated {
+			if !all_forward.contains(&m) {
+				all_forward.push(m);
+			}
+		}
+
+		for m in result.backward_activated {
+			if !all_backward.contains(&m) {
+				all_backward.push(m);
+			}
+		}
+	}
+
+	all_forward.sort_unstable();
+	all_backward.sort_unstable();
+
+	TemporalSpreadingResult {
+		activations: combined_activations,
+		forward_activated: all_forward,
+		backward_activated: all_backward,
+	}
+}
+
 /// Find temporally adjacent memories ("what was I working on before/after X?").
 ///
 /// Returns memory indices sorted by temporal proximity.
@@ -612,10 +1029,12 @@ pub fn find_temporal_neighbors(
 		}
 	}
 
-	// Sort by distance (closest first), then by strength (highest first)
+	// Sort by distance (closest first), then by strength (highest first),
+	// then by memory index
 	neighbors.sort_by(|a, b| {
 		a.2.cmp(&b.2)
 			.then_with(|| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal))
+			.then_with(|| a.0.cmp(&b.0))
 	});
 
 	// Return (memory, strength) pairs
@@ -626,6 +1045,68 @@ pub fn find_temporal_neighbors(
 		.collect()
 }
 
+/// Everything produced when an episode is consolidated into memory.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EpisodeConsolidation {
+	/// Temporal links between the episode's events
+	pub links: Vec<TemporalLink>,
+	/// L2-normalized centroid of the event embeddings (empty if none)
+	pub summary_embedding: Vec<f64>,
+	/// Time from the first to the last event (ms)
+	pub span_ms: f64,
+}
+
+/// Consolidate a finished episode in one step.
+///
+/// Creates the episode's temporal links (as [`create_episode_links`]) and a
+/// summary embedding for a higher-level episode memory: the mean of the event
+/// embeddings, normalized to unit length. Embeddings whose dimension differs
+/// from the first event's are left out of the centroid.
+///
+/// # Arguments
+///
+/// * `event_memory_indices` - Memory index of each event, in episode order
+/// * `event_embeddings` - Embedding of each event
+/// * `event_timestamps_ms` - When each event happened (ms), used for `span_ms`
+/// * `config` - Temporal spreading configuration
+#[must_use]
+pub fn consolidate_episode(
+	event_memory_indices: &[usize],
+	event_embeddings: &[Vec<f64>],
+	event_timestamps_ms: &[f64],
+	config: &TemporalSpreadingConfig,
+) -> EpisodeConsolidation {
+	let links = create_episode_links(event_memory_indices, config);
+
+	let dimension = event_embeddings.first().map_or(0, Vec::len);
+	let mut summary_embedding = vec![0.0; dimension];
+	for embedding in event_embeddings.iter().filter(|e| e.len() == dimension) {
+		for (sum, value) in summary_embedding.iter_mut().zip(embedding) {
+			*sum += value;
+		}
+	}
+	// Normalizing the sum gives the same direction as normalizing the mean
+	let norm = summary_embedding.iter().map(|x| x * x).sum::<f64>().sqrt();
+	if norm > 0.0 {
+		for x in &mut summary_embedding {
+			*x /= norm;
+		}
+	}
+
+	let (first, last) = event_timestamps_ms
+		.iter()
+		.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &t| {
+			(lo.min(t), hi.max(t))
+		});
+	let span_ms = if last >= first { last - first } else { 0.0 };
+
+	EpisodeConsolidation {
+		links,
+		summary_embedding,
+		span_ms,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -649,6 +1130,8 @@ mod tests {
 			minimum_activation: 0.01,
 			max_nodes: 100,
 			bidirectional: false,
+			fan_normalization: FanNormalization::BySource,
+			..Default::default()
 		};
 
 		let result = spread_activation(3, &associations, &[0], &[1.0], &config, 2);
@@ -672,6 +1155,8 @@ mod tests {
 			minimum_activation: 0.01,
 			max_nodes: 100,
 			bidirectional: false,
+			fan_normalization: FanNormalization::BySource,
+			..Default::default()
 		};
 
 		let result = spread_activation(4, &associations, &[0], &[1.0], &config, 1);
@@ -683,6 +1168,200 @@ mod tests {
 		assert!((result.activations[3] - expected).abs() < 0.01);
 	}
 
+	#[test]
+	fn test_spreading_traced_logs_fan_out_edges() {
+		let associations = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(0, 2, 1.0),
+			make_assoc(0, 3, 1.0),
+		];
+		let config = SpreadingConfig {
+			bidirectional: false,
+			fan_normalization: FanNormalization::BySource,
+			..Default::default()
+		};
+
+		let (result, edges) = spread_activation_traced(4, &associations, &[0], &[1.0], &config, 1);
+
+		let expected = 1.0 / 3.0 * config.decay_per_hop;
+		assert_eq!(edges.len(), 3);
+		for (&(source, target, amount), expected_target) in edges.iter().zip(1..) {
+			assert_eq!((source, target), (0, expected_target));
+			assert!((amount - expected).abs() < 1e-12);
+		}
+		let plain = spread_activation(4, &associations, &[0], &[1.0], &config, 1);
+		assert_eq!(result.activations, plain.activations);
+	}
+
+	#[test]
+	fn test_backward_decay_scales_backward_spreading() {
+		// Only reachable from the seed by following 1 → 0 backwards
+		let associations = vec![make_assoc(1, 0, 1.0)];
+		let backward = |backward_decay| {
+			let config = SpreadingConfig {
+				backward_decay,
+				..Default::default()
+			};
+			spread_activation(2, &associations, &[0], &[1.0], &config, 1).activations[1]
+		};
+
+		// Backward strength 0.5 × decay per hop 0.7 × backward decay
+		assert!((backward(1.0) - 0.35).abs() < 1e-12);
+		assert!((backward(0.7) - 0.245).abs() < 1e-12);
+		assert!((backward(0.5) / backward(0.25) - 2.0).abs() < 1e-9);
+		assert!(backward(0.0).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_fan_normalization_by_source_vs_by_target() {
+		// 0 → 1 and 0 → 2, but 2 is also reachable from 3
+		let associations = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(0, 2, 1.0),
+			make_assoc(3, 2, 1.0),
+		];
+		let spread = |fan_normalization| {
+			let config = SpreadingConfig {
+				bidirectional: false,
+				fan_normalization,
+				..Default::default()
+			};
+			spread_activation(4, &associations, &[0], &[1.0], &config, 1).activations
+		};
+
+		// By source: node 0's two edges split its activation evenly
+		let by_source = spread(FanNormalization::BySource);
+		assert!((by_source[1] - 0.35).abs() < 1e-9);
+		assert!((by_source[2] - 0.35).abs() < 1e-9);
+
+		// By target: node 2 has two incoming edges, so it gets half of node 1's share
+		let by_target = spread(FanNormalization::ByTarget);
+		assert!((by_target[1] - 0.7).abs() < 1e-9);
+		assert!((by_target[2] - 0.35).abs() < 1e-9);
+
+		let unnormalized = spread(FanNormalization::None);
+		assert!((unnormalized[1] - 0.7).abs() < 1e-9);
+		assert!((unnormalized[2] - 0.7).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_detect_cycles() {
+		let chain = vec![make_assoc(0, 1, 1.0), make_assoc(1, 2, 1.0)];
+		assert!(!detect_cycles(3, &chain));
+
+		// Diamond: two paths to the same node, but no cycle
+		let diamond = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(0, 2, 1.0),
+			make_assoc(1, 3, 1.0),
+			make_assoc(2, 3, 1.0),
+		];
+		assert!(!detect_cycles(4, &diamond));
+
+		let loop_back = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(1, 2, 1.0),
+			make_assoc(2, 0, 1.0),
+		];
+		assert!(detect_cycles(3, &loop_back));
+
+		assert!(detect_cycles(1, &[make_assoc(0, 0, 1.0)]));
+		assert!(!detect_cycles(0, &[]));
+	}
+
+	#[test]
+	fn test_merge_associations() {
+		let associations = vec![
+			make_assoc(0, 1, 0.3),
+			make_assoc(1, 2, 0.5),
+			Association {
+				source: 0,
+				target: 1,
+				forward_strength: 0.6,
+				backward_strength: 0.1,
+			},
+			make_assoc(0, 1, 0.2),
+			make_assoc(1, 0, 0.9), // reverse direction stays separate
+		];
+
+		let merged = merge_associations(&associations);
+		assert_eq!(merged.len(), 3);
+		assert_eq!((merged[0].source, merged[0].target), (0, 1));
+		assert!((merged[0].forward_strength - 0.6).abs() < 1e-12);
+		assert!((merged[0].backward_strength - 0.15).abs() < 1e-12);
+		assert_eq!((merged[1].source, merged[1].target), (1, 2));
+		assert_eq!((merged[2].source, merged[2].target), (1, 0));
+
+		let summed = merge_associations_with_mode(&associations, AssociationMergeMode::Sum);
+		assert_eq!(summed.len(), 3);
+		assert!((summed[0].forward_strength - 1.1).abs() < 1e-12);
+		assert!((summed[0].backward_strength - 0.35).abs() < 1e-12);
+
+		// The duplicates no longer split node 0's activation three ways
+		let config = SpreadingConfig {
+			bidirectional: false,
+			..Default::default()
+		};
+		let raw = spread_activation(3, &associations, &[0], &[1.0], &config, 1);
+		let deduped = spread_activation(3, &merged, &[0], &[1.0], &config, 1);
+		assert!(deduped.activations[1] > raw.activations[1]);
+	}
+
+	#[test]
+	fn test_compact_graph_reindexes_edges() {
+		let associations = vec![
+			make_assoc(0, 1, 0.9),
+			make_assoc(1, 2, 0.8),
+			make_assoc(0, 2, 0.7),
+			make_assoc(2, 3, 0.6),
+			make_assoc(3, 0, 0.5),
+		];
+
+		// Remove node 1 from the middle
+		let (compacted, index_map) = compact_graph(4, &associations, &[true, false, true, true]);
+
+		assert_eq!(index_map, vec![0, REMOVED_NODE, 1, 2]);
+		let edges: Vec<(usize, usize)> = compacted.iter().map(|a| (a.source, a.target)).collect();
+		assert_eq!(edges, vec![(0, 1), (1, 2), (2, 0)]);
+		assert!((compacted[0].forward_strength - 0.7).abs() < 1e-12);
+		assert!((compacted[2].backward_strength - 0.25).abs() < 1e-12);
+
+		// A short mask removes the unlisted nodes
+		let (compacted, index_map) = compact_graph(4, &associations, &[true, true]);
+		assert_eq!(index_map, vec![0, 1, REMOVED_NODE, REMOVED_NODE]);
+		assert_eq!(compacted.len(), 1);
+	}
+
+	#[test]
+	fn test_visit_budget_on_complete_graph() {
+		let n = 20;
+		let complete: Vec<Association> = (0..n)
+			.flat_map(|i| {
+				(0..n)
+					.filter(move |&j| j != i)
+					.map(move |j| make_assoc(i, j, 1.0))
+			})
+			.collect();
+
+		let spread = |visit_budget, warn_on_cycles| {
+			let config = SpreadingConfig {
+				minimum_activation: 0.0,
+				visit_budget,
+				warn_on_cycles,
+				..Default::default()
+			};
+			spread_activation(n, &complete, &[0, 1, 2], &[1.0; 3], &config, 3)
+		};
+
+		let unbounded = spread(None, false);
+		assert!(unbounded.relaxations > 100);
+		assert_eq!(unbounded.has_cycles, None);
+
+		let bounded = spread(Some(100), true);
+		assert_eq!(bounded.relaxations, 100);
+		assert_eq!(bounded.has_cycles, Some(true));
+	}
+
 	#[test]
 	fn test_find_path() {
 		let associations = vec![
@@ -713,6 +1392,32 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_graph_stats() {
+		// 0 fans out to 1, 2, 3; 3 links on to 4; 5 is on its own
+		let associations = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(0, 2, 1.0),
+			make_assoc(0, 3, 1.0),
+			make_assoc(3, 4, 1.0),
+			make_assoc(9, 0, 1.0), // out of range, ignored
+		];
+
+		let stats = graph_stats(6, &associations);
+		assert_eq!(stats.num_edges, 4);
+		assert_eq!(stats.max_fan_out, 3);
+		assert!((stats.average_fan_out - 4.0 / 6.0).abs() < 1e-12);
+		assert_eq!(stats.isolated_nodes, 1);
+		assert!(!stats.is_connected);
+
+		// Following edges backwards still reaches every node
+		let joined = graph_stats(5, &associations);
+		assert_eq!(joined.isolated_nodes, 0);
+		assert!(joined.is_connected);
+
+		assert!(graph_stats(0, &[]).is_connected);
+	}
+
 	// Temporal Spreading tests
 
 	#[test]
@@ -743,6 +1448,30 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_spread_temporal_activation_from_seeds() {
+		let config = TemporalSpreadingConfig {
+			max_temporal_distance: 1,
+			..Default::default()
+		};
+		// Chain 0 → 1 → 2 → 3 → 4, seeded at both ends
+		let links = create_episode_links(&[0, 1, 2, 3, 4], &config);
+		let result =
+			spread_temporal_activation_from_seeds(5, &links, &[0, 4], &[1.0, 0.5], &config);
+
+		assert_eq!(result.forward_activated, vec![1]);
+		assert_eq!(result.backward_activated, vec![3]);
+		assert!((result.activations[0] - 1.0).abs() < f64::EPSILON);
+		assert!((result.activations[4] - 0.5).abs() < f64::EPSILON);
+		assert!(result.activations[1] > 0.0);
+		assert!(result.activations[3] > 0.0);
+		assert!(result.activations[2].abs() < f64::EPSILON);
+
+		// Each seed alone contributes the same as in the combined result
+		let single = spread_temporal_activation(5, &links, 0, 1.0, &config);
+		assert!((single.activations[1] - result.activations[1]).abs() < f64::EPSILON);
+	}
+
 	#[test]
 	fn test_spread_temporal_activation() {
 		let config = TemporalSpreadingConfig::default();
@@ -800,4 +1529,38 @@ mod tests {
 		// Should NOT contain 0 (comes before)
 		assert!(!memory_ids.contains(&0));
 	}
+
+	#[test]
+	fn test_consolidate_episode() {
+		let config = TemporalSpreadingConfig::default();
+		let indices = [4, 7, 9];
+		let embeddings = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+		let timestamps = [5_000.0, 1_000.0, 9_000.0];
+
+		let episode = consolidate_episode(&indices, &embeddings, &timestamps, &config);
+		let expected_links = create_episode_links(&indices, &config);
+
+		assert_eq!(episode.links.len(), expected_links.len());
+		for (link, expected) in episode.links.iter().zip(&expected_links) {
+			assert_eq!(link.source_memory, expected.source_memory);
+			assert_eq!(link.target_memory, expected.target_memory);
+			assert!((link.forward_strength - expected.forward_strength).abs() < 1e-12);
+			assert!((link.backward_strength - expected.backward_strength).abs() < 1e-12);
+		}
+
+		// Centroid is (2/3, 2/3), normalized to (1/√2, 1/√2)
+		let component = std::f64::consts::FRAC_1_SQRT_2;
+		assert_eq!(episode.summary_embedding.len(), 2);
+		assert!((episode.summary_embedding[0] - component).abs() < 1e-12);
+		assert!((episode.summary_embedding[1] - component).abs() < 1e-12);
+		assert!((episode.span_ms - 8_000.0).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_consolidate_empty_episode() {
+		let episode = consolidate_episode(&[], &[], &[], &TemporalSpreadingConfig::default());
+		assert!(episode.links.is_empty());
+		assert!(episode.summary_embedding.is_empty());
+		assert!(episode.span_ms.abs() < f64::EPSILON);
+	}
 }
@@ -32,10 +32,16 @@ use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
 use crate::activation::{
-	combine_activations, compute_base_level, cosine_similarity_batch, nonlinear_activation_batch,
-	retrieval_probability,
+	combine_activations, compute_base_level, cosine_similarity, cosine_similarity_batch,
+	nonlinear_activation_batch, retrieval_latency, retrieval_probability,
+};
+use crate::retrieval::{
+	check_lengths, retrieve, OwnedRetrievalInput, RetrievalConfig, RetrievalInput,
+	RetrievalInputError, SpreadingSeedMode,
+};
+use crate::spreading::{
+	spread_activation, Association, FanNormalization, SpreadingConfig, SpreadingResult,
 };
-use crate::spreading::{spread_activation, Association, SpreadingConfig, SpreadingResult};
 
 // ============================================================================
 // Source Types
@@ -113,6 +119,137 @@ impl EmotionalContext {
 	}
 }
 
+/// Words that push [`infer_emotional_prior`] towards pleasant.
+const POSITIVE_WORDS: &[&str] = &[
+	"love",
+	"loved",
+	"great",
+	"happy",
+	"amazing",
+	"awesome",
+	"wonderful",
+	"beautiful",
+	"fun",
+	"glad",
+	"excited",
+	"best",
+	"good",
+	"nice",
+	"laugh",
+	"yay",
+	"congrats",
+	"congratulations",
+	"perfect",
+	"thanks",
+	"enjoy",
+	"enjoyed",
+	"fantastic",
+	"proud",
+	"cute",
+];
+
+/// Words that push [`infer_emotional_prior`] towards unpleasant.
+const NEGATIVE_WORDS: &[&str] = &[
+	"hate", "sad", "terrible", "awful", "angry", "bad", "worst", "scared", "afraid", "cry",
+	"crying", "hurt", "sorry", "horrible", "upset", "worried", "pain", "disaster", "sick",
+	"lonely", "miss", "annoying", "furious", "tired",
+];
+
+/// Words that flip the polarity of the next sentiment word.
+const NEGATIONS: &[&str] = &[
+	"not", "no", "never", "don't", "dont", "isn't", "wasn't", "can't", "won't", "didn't", "nothing",
+];
+
+/// Words that strengthen the next sentiment word and raise arousal.
+const INTENSIFIERS: &[&str] = &[
+	"very",
+	"really",
+	"so",
+	"extremely",
+	"totally",
+	"absolutely",
+	"super",
+	"incredibly",
+];
+
+/// Sentiment words a negation reaches past.
+const NEGATION_REACH: usize = 3;
+
+/// Weight of a sentiment word right after an intensifier.
+const INTENSIFIED_WEIGHT: f64 = 1.5;
+
+/// Arousal added per exclamation mark, intensifier or shouted (all-caps) word.
+const AROUSAL_PER_CUE: f64 = 0.1;
+
+/// Arousal added per sentiment word of either polarity.
+const AROUSAL_PER_SENTIMENT: f64 = 0.05;
+
+/// Guess the emotional tone of speech near a frame from keywords alone.
+///
+/// A cheap prior to seed valence and arousal before the vision model is
+/// asked, and to blend with its answer. Sentiment words from a small lexicon
+/// set valence; a negation within three words before one flips it, and an
+/// intensifier right before one strengthens it. Exclamation marks,
+/// intensifiers, shouted words and sentiment words all raise arousal from
+/// the neutral 0.5. Text with no cues gives [`EmotionalContext::default`].
+#[must_use]
+pub fn infer_emotional_prior(transcript_near_frame: &str) -> EmotionalContext {
+	let mut sentiment = 0.0_f64;
+	let mut cues = 0.0_f64;
+	let mut sentiment_words = 0.0_f64;
+	let mut negated_for = 0;
+	let mut intensified = false;
+
+	for raw in transcript_near_frame.split(|c: char| !c.is_alphanumeric() && c != '\'') {
+		if raw.is_empty() {
+			continue;
+		}
+		let word = raw.to_lowercase();
+		let shouted = raw.chars().count() > 1
+			&& raw.chars().any(char::is_alphabetic)
+			&& raw.chars().all(|c| !c.is_alphabetic() || c.is_uppercase());
+		if shouted {
+			cues += 1.0;
+		}
+
+		let polarity = if POSITIVE_WORDS.contains(&word.as_str()) {
+			1.0
+		} else if NEGATIVE_WORDS.contains(&word.as_str()) {
+			-1.0
+		} else {
+			if NEGATIONS.contains(&word.as_str()) {
+				negated_for = NEGATION_REACH;
+			} else if INTENSIFIERS.contains(&word.as_str()) {
+				intensified = true;
+				cues += 1.0;
+			} else {
+				negated_for = negated_for.saturating_sub(1);
+				intensified = false;
+			}
+			continue;
+		};
+
+		let weight = if intensified { INTENSIFIED_WEIGHT } else { 1.0 };
+		let sign = if negated_for > 0 { -1.0 } else { 1.0 };
+		sentiment += polarity * weight * sign;
+		sentiment_words += 1.0;
+		negated_for = 0;
+		intensified = false;
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let exclamations = transcript_near_frame.matches('!').count() as f64;
+	let neutral = EmotionalContext::default();
+
+	EmotionalContext::new(
+		sentiment / (sentiment.abs() + 2.0),
+		(cues + exclamations).mul_add(
+			AROUSAL_PER_CUE,
+			sentiment_words.mul_add(AROUSAL_PER_SENTIMENT, neutral.arousal),
+		),
+	)
+}
+
 // ============================================================================
 // Visual Memory
 // ============================================================================
@@ -162,6 +299,10 @@ pub struct VisualMemory {
 	/// Frame number within video
 	pub frame_number: Option<u32>,
 
+	/// Frame opens a new scene in its video
+	#[serde(default)]
+	pub is_scene_change: bool,
+
 	/// Detected objects/entities
 	pub objects: Vec<String>,
 
@@ -221,8 +362,31 @@ pub struct VisualConfig {
 
 	/// Whether to preserve video keyframes from pruning
 	pub preserve_keyframes: bool,
+
+	/// Whether to preserve scene-change frames from pruning
+	pub preserve_scene_changes: bool,
+
+	/// Cosine similarity at or above which two memories count as duplicates
+	pub duplicate_similarity_threshold: f64,
+
+	/// Consolidation window (ms) for images shared via Discord
+	pub discord_consolidation_ms: f64,
+
+	/// Consolidation window (ms) for images received by SMS/iMessage
+	pub sms_consolidation_ms: f64,
+
+	/// Consolidation window (ms) for direct uploads and screenshots
+	pub direct_consolidation_ms: f64,
+
+	/// Consolidation window (ms) for frames extracted from video
+	pub video_frame_consolidation_ms: f64,
+
+	/// Consolidation window (ms) for images of unknown origin
+	pub other_consolidation_ms: f64,
 }
 
+const HOUR_MS: f64 = 60.0 * 60.0 * 1000.0;
+
 impl Default for VisualConfig {
 	fn default() -> Self {
 		Self {
@@ -235,6 +399,15 @@ impl Default for VisualConfig {
 			pruning_threshold: 0.2,
 			pruning_stale_days: 90,
 			preserve_keyframes: true,
+			preserve_scene_changes: true,
+			duplicate_similarity_threshold: 0.97,
+			// A deliberate capture is worth consolidating slowly and fully;
+			// one frame of many passing by settles quickly
+			discord_consolidation_ms: 6.0 * HOUR_MS,
+			sms_consolidation_ms: 6.0 * HOUR_MS,
+			direct_consolidation_ms: 24.0 * HOUR_MS,
+			video_frame_consolidation_ms: HOUR_MS,
+			other_consolidation_ms: 6.0 * HOUR_MS,
 		}
 	}
 }
@@ -262,10 +435,33 @@ pub struct VisualRetrievalConfig {
 	pub max_results: usize,
 	/// Whether to spread bidirectionally
 	pub bidirectional: bool,
+	/// Multiplier on backward spreading, as in [`SpreadingConfig`]
+	pub backward_decay: f64,
 	/// Boost factor for emotionally significant memories
 	pub emotional_boost: f64,
+	/// How much `|valence|` adds to each memory's emotional weight
+	///
+	/// Strongly valenced memories are retrieved more readily than neutral
+	/// ones at the same arousal. The boost is symmetric: pleasant and
+	/// unpleasant memories of equal strength weigh the same. 0 ignores valence.
+	pub valence_weight: f64,
 	/// Boost factor for high-significance memories
 	pub significance_boost: f64,
+	/// How seed activations for spreading are computed (`Additive` by
+	/// default, unlike text retrieval)
+	pub seed_mode: SpreadingSeedMode,
+	/// Number of top-activated memories that seed spreading
+	///
+	/// Spreading reaches past the seeds, so `max_results` may exceed this,
+	/// but memories hanging off lower-ranked matches are only reached once
+	/// those matches are seeds.
+	pub max_spreading_seeds: usize,
+	/// Memories must be above this to seed spreading, as in
+	/// [`RetrievalConfig`](crate::retrieval::RetrievalConfig) (`None` = the
+	/// seed mode's default floor)
+	pub seed_activation_floor: Option<f64>,
+	/// Latency scaling factor, as in [`RetrievalConfig`](crate::retrieval::RetrievalConfig)
+	pub latency_factor: f64,
 }
 
 impl Default for VisualRetrievalConfig {
@@ -279,8 +475,14 @@ impl Default for VisualRetrievalConfig {
 			min_probability: 0.1,
 			max_results: 10,
 			bidirectional: true,
+			backward_decay: 0.7,
 			emotional_boost: 0.3,
+			valence_weight: 0.0,
 			significance_boost: 0.2,
+			seed_mode: SpreadingSeedMode::Additive,
+			max_spreading_seeds: 5,
+			seed_activation_floor: None,
+			latency_factor: 1.0,
 		}
 	}
 }
@@ -304,6 +506,8 @@ pub struct VisualRetrievalCandidate {
 	pub total_activation: f64,
 	/// Retrieval probability (0-1)
 	pub probability: f64,
+	/// Estimated retrieval latency in milliseconds
+	pub latency_ms: f64,
 }
 
 /// Input data for visual retrieval.
@@ -316,6 +520,8 @@ pub struct VisualRetrievalInput<'a> {
 	pub access_histories_ms: &'a [Vec<f64>],
 	/// Emotional weights for each memory
 	pub emotional_weights: &'a [f64],
+	/// Emotional valence (-1 to 1) for each memory; missing entries are neutral
+	pub valences: &'a [f64],
 	/// Significance scores for each memory
 	pub significance_scores: &'a [f64],
 	/// Association graph edges
@@ -324,6 +530,29 @@ pub struct VisualRetrievalInput<'a> {
 	pub current_time_ms: f64,
 }
 
+impl VisualRetrievalInput<'_> {
+	/// Check that every parallel array matches the number of memories.
+	///
+	/// `access_histories_ms` must have exactly one entry per memory; the
+	/// emotional weights, valences and significance scores may be empty
+	/// (every memory uses the default) but otherwise must match too.
+	///
+	/// # Errors
+	///
+	/// Returns a [`RetrievalInputError`] listing every mismatched field.
+	pub fn validate(&self) -> Result<(), RetrievalInputError> {
+		check_lengths(
+			self.memory_embeddings.len(),
+			&[("access_histories_ms", self.access_histories_ms.len())],
+			&[
+				("emotional_weights", self.emotional_weights.len()),
+				("valences", self.valences.len()),
+				("significance_scores", self.significance_scores.len()),
+			],
+		)
+	}
+}
+
 /// Retrieve visual memories based on probe embedding.
 ///
 /// This uses the same ACT-R spreading activation model as text retrieval,
@@ -351,31 +580,40 @@ pub fn retrieve_visual(
 		.map(|history| compute_base_level(history, input.current_time_ms, config.decay_rate))
 		.collect();
 
-	// 4. Initial activation (before spreading)
-	let initial_activations: Vec<f64> = (0..n)
+	// Arousal-based weight, raised by the strength of the valence
+	let emotional_weights: Vec<f64> = (0..n)
 		.map(|i| {
-			let base = if base_levels[i].is_finite() {
-				base_levels[i]
-			} else {
-				-10.0
-			};
-			let emotional = input.emotional_weights.get(i).copied().unwrap_or(0.5);
-			let emotional_multiplier = 1.0 + (emotional - 0.5);
-			(base + probe_activations[i]) * emotional_multiplier
+			let arousal_weight = input.emotional_weights.get(i).copied().unwrap_or(0.5);
+			let valence = input.valences.get(i).copied().unwrap_or(0.0);
+			config.valence_weight.mul_add(valence.abs(), arousal_weight)
 		})
 		.collect();
 
-	// 5. Find seeds for spreading (top activated)
-	let mut seeds: Vec<(usize, f64)> = initial_activations
-		.iter()
-		.enumerate()
-		.filter(|(_, &a)| a > 0.0)
-		.map(|(i, &a)| (i, a))
+	// 4. Find seeds for spreading (top activated)
+	let seed_floor = config
+		.seed_activation_floor
+		.unwrap_or_else(|| config.seed_mode.default_floor());
+	let mut seeds: Vec<(usize, f64)> = (0..n)
+		.filter_map(|i| {
+			config
+				.seed_mode
+				.seed_activation_with_floor(
+					base_levels[i],
+					probe_activations[i],
+					emotional_weights[i],
+					seed_floor,
+				)
+				.map(|a| (i, a))
+		})
 		.collect();
-	seeds.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-	seeds.truncate(5);
+	seeds.sort_by(|a, b| {
+		b.1.partial_cmp(&a.1)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.0.cmp(&b.0))
+	});
+	seeds.truncate(config.max_spreading_seeds);
 
-	// 6. Spread activation
+	// 5. Spread activation
 	let spreading_result = if !seeds.is_empty() && config.spreading_depth > 0 {
 		let seed_indices: Vec<usize> = seeds.iter().map(|(i, _)| *i).collect();
 		let seed_activations: Vec<f64> = seeds.iter().map(|(_, a)| *a).collect();
@@ -385,6 +623,10 @@ pub fn retrieve_visual(
 			minimum_activation: 0.01,
 			max_nodes: 1000,
 			bidirectional: config.bidirectional,
+			backward_decay: config.backward_decay,
+			fan_normalization: FanNormalization::default(),
+			warn_on_cycles: false,
+			visit_budget: None,
 		};
 
 		spread_activation(
@@ -399,10 +641,12 @@ pub fn retrieve_visual(
 		SpreadingResult {
 			activations: vec![0.0; n],
 			visited_by_depth: Vec::new(),
+			relaxations: 0,
+			has_cycles: None,
 		}
 	};
 
-	// 7. Combine all activations and build candidates
+	// 6. Combine all activations and build candidates
 	let mut candidates: Vec<VisualRetrievalCandidate> = (0..n)
 		.filter_map(|i| {
 			let base_level = if base_levels[i].is_finite() {
@@ -412,7 +656,7 @@ pub fn retrieve_visual(
 			};
 			let probe_activation = probe_activations[i];
 			let spreading = spreading_result.activations[i];
-			let emotional_weight = input.emotional_weights.get(i).copied().unwrap_or(0.5);
+			let emotional_weight = emotional_weights[i];
 			let significance = input.significance_scores.get(i).copied().unwrap_or(0.5);
 
 			// Add significance boost
@@ -448,21 +692,183 @@ pub fn retrieve_visual(
 				significance_boost: significance_boost + emotional_boost,
 				total_activation: boosted_total,
 				probability,
+				latency_ms: retrieval_latency(boosted_total, config.latency_factor),
 			})
 		})
 		.collect();
 
-	// 8. Sort by total activation and limit
+	// 7. Sort by total activation (ties by index) and limit
 	candidates.sort_by(|a, b| {
 		b.total_activation
 			.partial_cmp(&a.total_activation)
 			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.index.cmp(&b.index))
 	});
 	candidates.truncate(config.max_results);
 
 	candidates
 }
 
+/// Serialize visual retrieval results to a JSON array, for logging and
+/// debug tooling.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn visual_retrieval_result_to_json(
+	candidates: &[VisualRetrievalCandidate],
+) -> Result<String, serde_json::Error> {
+	serde_json::to_string(candidates)
+}
+
+/// Upper bound on reconstructed accesses per memory in
+/// [`visual_memories_to_retrieval_input`].
+pub const MAX_APPROXIMATED_ACCESSES: u32 = 100;
+
+/// Reconstruct an access history from a visual memory's summary fields.
+///
+/// `VisualMemory` keeps only the most recent access timestamp and a count, so
+/// the `access_count` accesses are spaced evenly between capture and the last
+/// access (the last one landing exactly on `last_accessed_ms`). A memory that
+/// was never accessed gets a single entry at capture time, since encoding is
+/// itself a presentation.
+fn approximate_access_history(memory: &VisualMemory) -> Vec<f64> {
+	let count = memory.access_count.min(MAX_APPROXIMATED_ACCESSES);
+	if count == 0 {
+		return vec![memory.captured_at_ms];
+	}
+	let span = memory.last_accessed_ms - memory.captured_at_ms;
+	(1..=count)
+		.map(|k| memory.captured_at_ms + span * f64::from(k) / f64::from(count))
+		.collect()
+}
+
+/// Convert visual memories into the text retrieval input shape.
+///
+/// Lets visual memories flow through the plain [`crate::retrieval::retrieve`]
+/// alongside text memories. The conversion is lossy:
+///
+/// - Access histories are approximated, because a `VisualMemory` only records
+///   its last access timestamp and an access count (see
+///   [`MAX_APPROXIMATED_ACCESSES`]).
+/// - The emotional weight is the capture arousal, which already sits on the
+///   text path's 0-1 scale with 0.5 as neutral. Valence is dropped.
+/// - Significance has no text-path equivalent and is ignored.
+///
+/// Decay rates, WM boosts and associations are left empty for the caller to
+/// fill in.
+#[must_use]
+pub fn visual_memories_to_retrieval_input(
+	memories: &[VisualMemory],
+	current_time_ms: f64,
+) -> OwnedRetrievalInput {
+	OwnedRetrievalInput {
+		memory_embeddings: memories.iter().map(|m| m.embedding.clone()).collect(),
+		access_histories_ms: memories.iter().map(approximate_access_history).collect(),
+		emotional_weights: memories
+			.iter()
+			.map(|m| m.emotional_context.arousal)
+			.collect(),
+		current_time_ms,
+		..Default::default()
+	}
+}
+
+// ============================================================================
+// Multimodal Retrieval
+// ============================================================================
+
+/// Which memory store a [`MultimodalCandidate`] came from.
+///
+/// Ordered text first, which is how [`retrieve_multimodal`] breaks ties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Modality {
+	/// Text memory, scored by [`retrieve`]
+	Text,
+	/// Visual memory, scored by [`retrieve_visual`]
+	Visual,
+}
+
+/// A candidate from [`retrieve_multimodal`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultimodalCandidate {
+	/// Store the memory came from
+	pub modality: Modality,
+	/// Memory index within its own store
+	pub index: usize,
+	/// Combined total activation
+	pub total_activation: f64,
+	/// Retrieval probability (0-1)
+	pub probability: f64,
+	/// Estimated retrieval latency in milliseconds
+	pub latency_ms: f64,
+}
+
+/// Retrieve text and visual memories together as one ranked list.
+///
+/// Both inputs should carry the same probe. Each store is scored by its own
+/// pipeline and config, then the candidates are merged, ranked by total
+/// activation and cut to `max_results`, which replaces the configs' own
+/// `max_results`. Ties keep text candidates first, then go to the lower
+/// index.
+///
+/// Activations are compared as-is, so visual memories' emotional and
+/// significance boosts count against text memories' working memory boosts.
+#[must_use]
+pub fn retrieve_multimodal(
+	text_input: &RetrievalInput<'_>,
+	visual_input: &VisualRetrievalInput<'_>,
+	text_config: &RetrievalConfig,
+	visual_config: &VisualRetrievalConfig,
+	max_results: usize,
+) -> Vec<MultimodalCandidate> {
+	let text_config = RetrievalConfig {
+		max_results,
+		..text_config.clone()
+	};
+	let visual_config = VisualRetrievalConfig {
+		max_results,
+		..visual_config.clone()
+	};
+
+	let text = retrieve(text_input, &text_config)
+		.into_iter()
+		.map(|c| MultimodalCandidate {
+			modality: Modality::Text,
+			index: c.index,
+			total_activation: c.total_activation,
+			probability: c.probability,
+			latency_ms: c.latency_ms,
+		});
+	let visual = retrieve_visual(visual_input, &visual_config)
+		.into_iter()
+		.map(|c| MultimodalCandidate {
+			modality: Modality::Visual,
+			index: c.index,
+			total_activation: c.total_activation,
+			probability: c.probability,
+			latency_ms: c.latency_ms,
+		});
+
+	rank_multimodal(text.chain(visual).collect(), max_results)
+}
+
+/// Sort merged candidates by total activation, text before visual on ties
+/// and then by index, and keep the top `max_results`.
+fn rank_multimodal(
+	mut candidates: Vec<MultimodalCandidate>,
+	max_results: usize,
+) -> Vec<MultimodalCandidate> {
+	candidates.sort_by(|a, b| {
+		b.total_activation
+			.total_cmp(&a.total_activation)
+			.then_with(|| a.modality.cmp(&b.modality))
+			.then_with(|| a.index.cmp(&b.index))
+	});
+	candidates.truncate(max_results);
+	candidates
+}
+
 // ============================================================================
 // Consolidation
 // ============================================================================
@@ -566,6 +972,29 @@ impl VisualConsolidationState {
 		self.window = Some(ConsolidationWindow::new(current_time_ms, duration_ms));
 	}
 
+	/// Start consolidation over the window configured for `source`.
+	pub fn start_consolidation_for(
+		&mut self,
+		current_time_ms: f64,
+		source: VisualSource,
+		config: &VisualConfig,
+	) {
+		self.start_consolidation(current_time_ms, consolidation_duration_for(source, config));
+	}
+
+	/// Reactivate a memory, reopening it to modification.
+	///
+	/// Each reactivation opens a fresh reconsolidation window and bumps
+	/// `reactivation_count`; strength is recomputed from the window on the
+	/// next [`update`](Self::update).
+	pub fn reactivate(&mut self, current_time_ms: f64, duration_ms: f64) {
+		let mut window = ConsolidationWindow::new(current_time_ms, duration_ms);
+		window.state = ConsolidationState::Reconsolidating;
+		self.state = ConsolidationState::Reconsolidating;
+		self.window = Some(window);
+		self.reactivation_count = self.reactivation_count.saturating_add(1);
+	}
+
 	/// Update consolidation state based on current time.
 	pub fn update(&mut self, current_time_ms: f64) {
 		if let Some(ref window) = self.window {
@@ -580,6 +1009,18 @@ impl VisualConsolidationState {
 	}
 }
 
+/// Consolidation window length (ms) for a memory from `source`.
+#[must_use]
+pub const fn consolidation_duration_for(source: VisualSource, config: &VisualConfig) -> f64 {
+	match source {
+		VisualSource::Discord => config.discord_consolidation_ms,
+		VisualSource::Sms => config.sms_consolidation_ms,
+		VisualSource::Direct => config.direct_consolidation_ms,
+		VisualSource::VideoFrame => config.video_frame_consolidation_ms,
+		VisualSource::Other => config.other_consolidation_ms,
+	}
+}
+
 // ============================================================================
 // Tagging
 // ============================================================================
@@ -651,6 +1092,45 @@ pub fn should_tag(strength: f64, threshold: f64) -> bool {
 	strength >= threshold
 }
 
+// ============================================================================
+// Significance Decay
+// ============================================================================
+
+/// Decay a visual memory's significance by how long it has gone unaccessed.
+///
+/// Significance holds steady for `stale_threshold_days`, then decays
+/// exponentially at `base_decay_rate` per day:
+///
+/// ```text
+/// s' = s · e^(-rate · (days - stale_threshold_days))
+/// ```
+///
+/// Memories with `emotional_arousal` at or above
+/// `emotional_retention_threshold` decay at `rate · (1 - emotional_decay_reduction)`
+/// instead. The result never drops below `significance_floor`, but a memory
+/// that already starts below the floor is not raised to it.
+#[must_use]
+pub fn compute_decayed_significance(
+	significance: f64,
+	days_since_access: f64,
+	emotional_arousal: f64,
+	config: &VisualConfig,
+) -> f64 {
+	let stale_days = days_since_access - f64::from(config.stale_threshold_days);
+	if stale_days <= 0.0 {
+		return significance;
+	}
+
+	let rate = if emotional_arousal >= config.emotional_retention_threshold {
+		config.base_decay_rate * (1.0 - config.emotional_decay_reduction.clamp(0.0, 1.0))
+	} else {
+		config.base_decay_rate
+	};
+
+	let decayed = significance * (-rate * stale_days).exp();
+	decayed.max(config.significance_floor.min(significance))
+}
+
 // ============================================================================
 // Pruning
 // ============================================================================
@@ -668,6 +1148,48 @@ pub struct PruningCandidate {
 	pub reason: PruningReason,
 	/// Pruning score (higher = more likely to prune)
 	pub score: f64,
+	/// The factors `score` was computed from
+	pub breakdown: PruningBreakdown,
+}
+
+/// How a [`PruningCandidate`]'s score was reached.
+///
+/// For every reason, `score = staleness_component × significance_component
+/// × duplicate_similarity` (a missing similarity counts as 1); see
+/// [`PruningBreakdown::score`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // Each protection applies independently
+pub struct PruningBreakdown {
+	/// `Stale`: days since access over `pruning_stale_days` (above 1).
+	/// `LowSignificance`: days since access over `stale_threshold_days`,
+	/// capped at 1. `Duplicate`: 1, as staleness plays no part.
+	pub staleness_component: f64,
+	/// `LowSignificance`: how far significance falls short of
+	/// `pruning_threshold`. Otherwise `1 - significance`.
+	pub significance_component: f64,
+	/// Cosine similarity to the kept copy, for `Duplicate` only
+	pub duplicate_similarity: Option<f64>,
+	/// Arousal is at or above `emotional_retention_threshold`, so the
+	/// memory's significance decays more slowly. The score itself doesn't
+	/// discount it.
+	pub emotional_protection: bool,
+	/// Keyframe kept by `preserve_keyframes` (never true for a candidate)
+	pub keyframe_protected: bool,
+	/// Scene-change frame kept by `preserve_scene_changes` (never true for a
+	/// candidate)
+	pub scene_change_protected: bool,
+	/// Pinned by the user (never true for a candidate)
+	pub pinned: bool,
+}
+
+impl PruningBreakdown {
+	/// The pruning score these components produce.
+	#[must_use]
+	pub fn score(&self) -> f64 {
+		self.staleness_component
+			* self.significance_component
+			* self.duplicate_similarity.unwrap_or(1.0)
+	}
 }
 
 /// Why a memory is a pruning candidate.
@@ -686,6 +1208,10 @@ pub enum PruningReason {
 /// Compute pruning candidates from a set of visual memories.
 ///
 /// Returns memories that may be candidates for pruning, sorted by score.
+///
+/// Memories that are not already stale or low-significance are also checked
+/// for near-duplicates: when two embeddings reach
+/// `duplicate_similarity_threshold`, the less significant one is flagged.
 #[must_use]
 pub fn compute_pruning_candidates(
 	memories: &[VisualMemory],
@@ -693,6 +1219,21 @@ pub fn compute_pruning_candidates(
 	config: &VisualConfig,
 ) -> SmallVec<[PruningCandidate; 32]> {
 	let ms_per_day = 24.0 * 60.0 * 60.0 * 1000.0;
+	let keyframe_protected =
+		|mem: &VisualMemory| config.preserve_keyframes && mem.frame_number == Some(0);
+	let scene_change_protected =
+		|mem: &VisualMemory| config.preserve_scene_changes && mem.is_scene_change;
+	let breakdown =
+		|mem: &VisualMemory, staleness_component, significance_component| PruningBreakdown {
+			staleness_component,
+			significance_component,
+			duplicate_similarity: None,
+			emotional_protection: mem.emotional_context.arousal
+				>= config.emotional_retention_threshold,
+			keyframe_protected: keyframe_protected(mem),
+			scene_change_protected: scene_change_protected(mem),
+			pinned: mem.is_pinned,
+		};
 
 	let mut candidates: SmallVec<[PruningCandidate; 32]> = memories
 		.iter()
@@ -703,8 +1244,8 @@ pub fn compute_pruning_candidates(
 				return None;
 			}
 
-			// Preserve keyframes if configured
-			if config.preserve_keyframes && mem.frame_number == Some(0) {
+			// Preserve keyframes and scene changes if configured
+			if keyframe_protected(mem) || scene_change_protected(mem) {
 				return None;
 			}
 
@@ -712,27 +1253,35 @@ pub fn compute_pruning_candidates(
 
 			// Check for stale memories
 			if days_since_access > f64::from(config.pruning_stale_days) {
-				let score = (days_since_access / f64::from(config.pruning_stale_days))
-					* (1.0 - mem.significance);
+				let breakdown = breakdown(
+					mem,
+					days_since_access / f64::from(config.pruning_stale_days),
+					1.0 - mem.significance,
+				);
 				return Some(PruningCandidate {
 					index: i,
 					significance: mem.significance,
 					days_since_access,
 					reason: PruningReason::Stale,
-					score,
+					score: breakdown.score(),
+					breakdown,
 				});
 			}
 
 			// Check for low significance
 			if mem.significance < config.pruning_threshold {
-				let score = (config.pruning_threshold - mem.significance)
-					* (days_since_access / f64::from(config.stale_threshold_days)).min(1.0);
+				let breakdown = breakdown(
+					mem,
+					(days_since_access / f64::from(config.stale_threshold_days)).min(1.0),
+					config.pruning_threshold - mem.significance,
+				);
 				return Some(PruningCandidate {
 					index: i,
 					significance: mem.significance,
 					days_since_access,
 					reason: PruningReason::LowSignificance,
-					score,
+					score: breakdown.score(),
+					breakdown,
 				});
 			}
 
@@ -740,11 +1289,62 @@ pub fn compute_pruning_candidates(
 		})
 		.collect();
 
+	// Near-duplicates: visit memories from most to least significant and compare
+	// each against the ones already kept, so the weaker copy is the one flagged.
+	// The scan for a memory stops at its first match.
+	let mut flagged = vec![false; memories.len()];
+	for candidate in &candidates {
+		flagged[candidate.index] = true;
+	}
+	let mut order: Vec<usize> = (0..memories.len())
+		.filter(|&i| !flagged[i] && !memories[i].embedding.is_empty())
+		.collect();
+	order.sort_by(|&a, &b| {
+		memories[b]
+			.significance
+			.partial_cmp(&memories[a].significance)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.cmp(&b))
+	});
+
+	let mut kept: Vec<usize> = Vec::with_capacity(order.len());
+	for i in order {
+		let mem = &memories[i];
+		let protected = mem.is_pinned || keyframe_protected(mem) || scene_change_protected(mem);
+		let duplicate_similarity = if protected {
+			None
+		} else {
+			kept.iter().find_map(|&j| {
+				let similarity = cosine_similarity(&mem.embedding, &memories[j].embedding);
+				(similarity >= config.duplicate_similarity_threshold).then_some(similarity)
+			})
+		};
+
+		match duplicate_similarity {
+			Some(similarity) => {
+				let breakdown = PruningBreakdown {
+					duplicate_similarity: Some(similarity),
+					..breakdown(mem, 1.0, 1.0 - mem.significance)
+				};
+				candidates.push(PruningCandidate {
+					index: i,
+					significance: mem.significance,
+					days_since_access: (current_time_ms - mem.last_accessed_ms) / ms_per_day,
+					reason: PruningReason::Duplicate,
+					score: breakdown.score(),
+					breakdown,
+				});
+			}
+			None => kept.push(i),
+		}
+	}
+
 	// Sort by score (highest first = most prunable)
 	candidates.sort_by(|a, b| {
 		b.score
 			.partial_cmp(&a.score)
 			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.index.cmp(&b.index))
 	});
 
 	candidates
@@ -758,6 +1358,27 @@ pub fn should_prune(
 	is_pinned: bool,
 	is_keyframe: bool,
 	config: &VisualConfig,
+) -> bool {
+	should_prune_with_scene_change(
+		significance,
+		days_since_access,
+		is_pinned,
+		is_keyframe,
+		false,
+		config,
+	)
+}
+
+/// Check if a specific memory should be pruned, keeping scene-change frames
+/// when `config.preserve_scene_changes` is set.
+#[must_use]
+pub fn should_prune_with_scene_change(
+	significance: f64,
+	days_since_access: f64,
+	is_pinned: bool,
+	is_keyframe: bool,
+	is_scene_change: bool,
+	config: &VisualConfig,
 ) -> bool {
 	if is_pinned {
 		return false;
@@ -767,6 +1388,10 @@ pub fn should_prune(
 		return false;
 	}
 
+	if config.preserve_scene_changes && is_scene_change {
+		return false;
+	}
+
 	// Stale and low significance
 	if days_since_access > f64::from(config.pruning_stale_days)
 		&& significance < config.pruning_threshold
@@ -812,6 +1437,31 @@ pub struct TranscriptSegment {
 	pub text: String,
 }
 
+/// Configuration for frame selection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameSelectionConfig {
+	/// Always include the first and last frame when there's room for two.
+	/// Disable for videos that open or close on black frames or title cards.
+	pub force_endpoints: bool,
+	/// Score bonus for keyframes (I-frames)
+	pub keyframe_bonus: f64,
+	/// Score bonus for scene changes
+	pub scene_change_bonus: f64,
+	/// Score bonus for frames that fall inside a transcript segment
+	pub transcript_bonus: f64,
+}
+
+impl Default for FrameSelectionConfig {
+	fn default() -> Self {
+		Self {
+			force_endpoints: true,
+			keyframe_bonus: 0.3,
+			scene_change_bonus: 0.5,
+			transcript_bonus: 0.2,
+		}
+	}
+}
+
 /// Select frames for description, respecting rate limits.
 ///
 /// Prioritizes: keyframes, scene changes, even distribution, transcript moments.
@@ -830,6 +1480,25 @@ pub fn select_frames_for_description(
 	frames: &[FrameCandidate],
 	max_frames: usize,
 	transcript_segments: Option<&[TranscriptSegment]>,
+) -> SmallVec<[usize; 32]> {
+	select_frames_for_description_with_config(
+		frames,
+		max_frames,
+		transcript_segments,
+		&FrameSelectionConfig::default(),
+	)
+}
+
+/// Select frames for description with custom scoring weights.
+///
+/// Same as [`select_frames_for_description`], but the endpoint handling and
+/// per-frame bonuses come from `config`.
+#[must_use]
+pub fn select_frames_for_description_with_config(
+	frames: &[FrameCandidate],
+	max_frames: usize,
+	transcript_segments: Option<&[TranscriptSegment]>,
+	config: &FrameSelectionConfig,
 ) -> SmallVec<[usize; 32]> {
 	if frames.is_empty() || max_frames == 0 {
 		return SmallVec::new();
@@ -844,12 +1513,12 @@ pub fn select_frames_for_description(
 
 			// Keyframes get priority
 			if frame.is_keyframe {
-				score += 0.3;
+				score += config.keyframe_bonus;
 			}
 
 			// Scene changes are important
 			if frame.is_scene_change {
-				score += 0.5;
+				score += config.scene_change_bonus;
 			}
 
 			// Boost frames near transcript segments (speech = important)
@@ -858,7 +1527,7 @@ pub fn select_frames_for_description(
 					if frame.timestamp_seconds >= seg.start_seconds
 						&& frame.timestamp_seconds <= seg.end_seconds
 					{
-						score += 0.2;
+						score += config.transcript_bonus;
 						break;
 					}
 				}
@@ -869,13 +1538,17 @@ pub fn select_frames_for_description(
 		.collect();
 
 	// Sort by score (highest first)
-	scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+	scored.sort_by(|a, b| {
+		b.1.partial_cmp(&a.1)
+			.unwrap_or(std::cmp::Ordering::Equal)
+			.then_with(|| a.0.cmp(&b.0))
+	});
 
 	// Take top candidates, but ensure temporal distribution
 	let mut selected: SmallVec<[usize; 32]> = SmallVec::new();
 
-	// Always include first and last frame if we have room
-	if max_frames >= 2 && !frames.is_empty() {
+	// Include first and last frame if we have room
+	if config.force_endpoints && max_frames >= 2 {
 		selected.push(0);
 		if frames.len() > 1 {
 			selected.push(frames.len() - 1);
@@ -1072,6 +1745,7 @@ Write a natural description that captures the essence of the video, not just a l
 #[allow(clippy::float_cmp)]
 mod tests {
 	use super::*;
+	use crate::retrieval::test_support::SixthSeedFixture;
 
 	#[test]
 	fn test_emotional_context_weight() {
@@ -1093,6 +1767,30 @@ mod tests {
 		assert!(negative.is_significant());
 	}
 
+	#[test]
+	fn test_emotional_prior_from_transcript() {
+		let positive = infer_emotional_prior("This is amazing, I love it!");
+		let negative = infer_emotional_prior("That was awful. I hate this, so sad");
+		assert!(positive.valence > 0.0);
+		assert!(negative.valence < 0.0);
+		assert!(positive.arousal > 0.5);
+
+		// Negation flips polarity; intensifiers and shouting add arousal
+		assert!(infer_emotional_prior("this is not good").valence < 0.0);
+		let calm = infer_emotional_prior("good");
+		let loud = infer_emotional_prior("REALLY good!!");
+		assert!(loud.valence > calm.valence);
+		assert!(loud.arousal > calm.arousal);
+
+		let neutral = infer_emotional_prior("the train leaves at noon");
+		assert!(neutral.valence.abs() < f64::EPSILON);
+		assert!((neutral.arousal - 0.5).abs() < f64::EPSILON);
+
+		// Numbers have no letters to shout
+		let numeric = infer_emotional_prior("the train leaves at 12 on 30 May 2024");
+		assert!((numeric.arousal - 0.5).abs() < f64::EPSILON);
+	}
+
 	#[test]
 	fn test_consolidation_window() {
 		let start = 1000.0;
@@ -1104,6 +1802,82 @@ mod tests {
 		assert!((window.progress(start + 500.0) - 0.5).abs() < 0.001);
 	}
 
+	#[test]
+	fn test_consolidation_duration_by_source() {
+		let config = VisualConfig::default();
+		let direct = consolidation_duration_for(VisualSource::Direct, &config);
+		let frame = consolidation_duration_for(VisualSource::VideoFrame, &config);
+		assert!(direct > frame);
+
+		let mut screenshot = VisualConsolidationState::default();
+		screenshot.start_consolidation_for(0.0, VisualSource::Direct, &config);
+		let mut passing = VisualConsolidationState::default();
+		passing.start_consolidation_for(0.0, VisualSource::VideoFrame, &config);
+
+		screenshot.update(frame);
+		passing.update(frame);
+		assert!(screenshot.is_labile());
+		assert_eq!(passing.state, ConsolidationState::Consolidated);
+		assert!((screenshot.strength - frame / direct).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_reactivation_reopens_window() {
+		let mut state = VisualConsolidationState::default();
+		state.start_consolidation(0.0, 1000.0);
+		state.update(2000.0);
+		assert_eq!(state.state, ConsolidationState::Consolidated);
+		assert_eq!(state.reactivation_count, 0);
+
+		state.reactivate(3000.0, 1000.0);
+		assert!(state.is_labile());
+		assert_eq!(state.state, ConsolidationState::Reconsolidating);
+		assert_eq!(state.reactivation_count, 1);
+
+		state.update(3250.0);
+		assert!((state.strength - 0.25).abs() < 0.001);
+
+		state.update(4000.0);
+		assert_eq!(state.state, ConsolidationState::Consolidated);
+		assert!((state.strength - 1.0).abs() < f64::EPSILON);
+		assert_eq!(state.reactivation_count, 1);
+	}
+
+	fn frame(index: u32, is_keyframe: bool, is_scene_change: bool) -> FrameCandidate {
+		FrameCandidate {
+			index: index as usize,
+			timestamp_seconds: f64::from(index),
+			is_keyframe,
+			is_scene_change,
+			quality_score: 0.5,
+		}
+	}
+
+	#[test]
+	fn test_frame_selection_config() {
+		// Frame 2 is a keyframe, frame 5 a scene change; the endpoints are neither
+		let frames: Vec<FrameCandidate> = (0..8u32).map(|i| frame(i, i == 2, i == 5)).collect();
+
+		let default = select_frames_for_description(&frames, 3, None);
+		assert_eq!(default.as_slice(), &[0, 5, 7]);
+
+		let no_endpoints = FrameSelectionConfig {
+			force_endpoints: false,
+			..Default::default()
+		};
+		let selected = select_frames_for_description_with_config(&frames, 2, None, &no_endpoints);
+		assert_eq!(selected.as_slice(), &[2, 5]);
+
+		// Without the scene change bonus, a single slot goes to the keyframe
+		let keyframes_only = FrameSelectionConfig {
+			force_endpoints: false,
+			scene_change_bonus: 0.0,
+			..Default::default()
+		};
+		let selected = select_frames_for_description_with_config(&frames, 1, None, &keyframes_only);
+		assert_eq!(selected.as_slice(), &[2]);
+	}
+
 	#[test]
 	fn test_tag_strength() {
 		let config = VisualConfig::default();
@@ -1135,6 +1909,50 @@ mod tests {
 		assert!(!should_prune(0.8, 100.0, false, false, &config));
 	}
 
+	#[test]
+	fn test_decayed_significance_before_threshold() {
+		let config = VisualConfig::default();
+
+		// Nothing decays until the memory goes stale
+		assert!((compute_decayed_significance(0.8, 10.0, 0.0, &config) - 0.8).abs() < 1e-12);
+
+		let expected = 0.8 * (-0.05f64 * 6.0).exp();
+		let decayed = compute_decayed_significance(0.8, 20.0, 0.0, &config);
+		assert!((decayed - expected).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_decayed_significance_emotional_retention() {
+		let config = VisualConfig::default();
+
+		let calm = compute_decayed_significance(0.8, 30.0, 0.2, &config);
+		let aroused = compute_decayed_significance(0.8, 30.0, 0.9, &config);
+		assert!(aroused > calm);
+
+		// Default reduction of 0.5 halves the rate
+		let expected = 0.8 * (-0.025f64 * 16.0).exp();
+		assert!((aroused - expected).abs() < 1e-12);
+
+		// Full reduction stops decay entirely
+		let config = VisualConfig {
+			emotional_decay_reduction: 1.0,
+			..Default::default()
+		};
+		assert!((compute_decayed_significance(0.8, 300.0, 0.9, &config) - 0.8).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_decayed_significance_floor() {
+		let config = VisualConfig::default();
+
+		let decayed = compute_decayed_significance(0.8, 1000.0, 0.0, &config);
+		assert!((decayed - config.significance_floor).abs() < 1e-12);
+
+		// Already below the floor: left where it is
+		let decayed = compute_decayed_significance(0.05, 1000.0, 0.0, &config);
+		assert!((decayed - 0.05).abs() < 1e-12);
+	}
+
 	const MS_PER_DAY: f64 = 1000.0 * 60.0 * 60.0 * 24.0;
 
 	#[test]
@@ -1158,6 +1976,7 @@ mod tests {
 				shared_by: None,
 				video_id: None,
 				frame_number: None,
+				is_scene_change: false,
 				objects: vec![],
 				tags: vec![],
 				is_pinned: false,
@@ -1176,6 +1995,7 @@ mod tests {
 				shared_by: None,
 				video_id: None,
 				frame_number: None,
+				is_scene_change: false,
 				objects: vec![],
 				tags: vec![],
 				is_pinned: false,
@@ -1189,6 +2009,149 @@ mod tests {
 		assert_eq!(candidates[0].index, 0);
 	}
 
+	#[test]
+	fn test_scene_change_frames_are_preserved() {
+		let now = 200.0 * MS_PER_DAY;
+		let frame = |frame_number: u32, is_scene_change: bool| VisualMemory {
+			id: frame_number,
+			description: format!("Frame {frame_number}"),
+			detailed_description: None,
+			embedding: vec![],
+			captured_at_ms: 0.0,
+			last_accessed_ms: 7.0f64.mul_add(-MS_PER_DAY, now),
+			access_count: 1,
+			emotional_context: EmotionalContext::default(),
+			significance: 0.05,
+			source: VisualSource::VideoFrame,
+			shared_by: None,
+			video_id: Some("clip".to_string()),
+			frame_number: Some(frame_number),
+			is_scene_change,
+			objects: vec![],
+			tags: vec![],
+			is_pinned: false,
+		};
+		let memories = vec![frame(12, true), frame(13, false)];
+
+		let config = VisualConfig::default();
+		let candidates = compute_pruning_candidates(&memories, now, &config);
+		let indices: Vec<usize> = candidates.iter().map(|c| c.index).collect();
+		assert_eq!(indices, vec![1]);
+		assert!(!should_prune_with_scene_change(
+			0.05, 200.0, false, false, true, &config
+		));
+
+		let config = VisualConfig {
+			preserve_scene_changes: false,
+			..Default::default()
+		};
+		let candidates = compute_pruning_candidates(&memories, now, &config);
+		assert_eq!(candidates.len(), 2);
+		assert!(should_prune_with_scene_change(
+			0.05, 200.0, false, false, true, &config
+		));
+	}
+
+	#[test]
+	fn test_pruning_breakdown_explains_score() {
+		let config = VisualConfig::default();
+		let now = 200.0 * MS_PER_DAY;
+		let memory = |id: u32, days_ago: f64, significance: f64, arousal: f64| VisualMemory {
+			id,
+			description: format!("Test {id}"),
+			detailed_description: None,
+			embedding: vec![],
+			captured_at_ms: 0.0,
+			last_accessed_ms: days_ago.mul_add(-MS_PER_DAY, now),
+			access_count: 1,
+			emotional_context: EmotionalContext {
+				valence: 0.0,
+				arousal,
+			},
+			significance,
+			source: VisualSource::Direct,
+			shared_by: None,
+			video_id: None,
+			frame_number: None,
+			is_scene_change: false,
+			objects: vec![],
+			tags: vec![],
+			is_pinned: false,
+		};
+
+		let memories = vec![
+			memory(0, 180.0, 0.5, 0.9), // Stale, emotionally charged
+			memory(1, 7.0, 0.1, 0.2),   // Low significance, half the stale threshold
+		];
+		let candidates = compute_pruning_candidates(&memories, now, &config);
+		assert_eq!(candidates.len(), 2);
+
+		let Some(stale) = candidates.iter().find(|c| c.index == 0) else {
+			unreachable!("stale memory should be a candidate");
+		};
+		assert_eq!(stale.reason, PruningReason::Stale);
+		assert!((stale.breakdown.staleness_component - 2.0).abs() < 1e-9);
+		assert!((stale.breakdown.significance_component - 0.5).abs() < 1e-9);
+		assert!(stale.breakdown.emotional_protection);
+		assert!((stale.breakdown.score() - stale.score).abs() < 1e-12);
+
+		let Some(low) = candidates.iter().find(|c| c.index == 1) else {
+			unreachable!("low-significance memory should be a candidate");
+		};
+		assert_eq!(low.reason, PruningReason::LowSignificance);
+		assert!((low.breakdown.staleness_component - 0.5).abs() < 1e-9);
+		assert!((low.breakdown.significance_component - 0.1).abs() < 1e-9);
+		assert!(!low.breakdown.emotional_protection);
+		assert!(!low.breakdown.pinned && !low.breakdown.keyframe_protected);
+		assert!((low.breakdown.score() - low.score).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_pruning_candidates_duplicates() {
+		let config = VisualConfig::default();
+		let now = MS_PER_DAY;
+		let memory =
+			|id: u32, embedding: Vec<f64>, significance: f64, is_pinned: bool| VisualMemory {
+				id,
+				description: format!("Test {id}"),
+				detailed_description: None,
+				embedding,
+				captured_at_ms: now,
+				last_accessed_ms: now,
+				access_count: 1,
+				emotional_context: EmotionalContext::default(),
+				significance,
+				source: VisualSource::Direct,
+				shared_by: None,
+				video_id: None,
+				frame_number: None,
+				is_scene_change: false,
+				objects: vec![],
+				tags: vec![],
+				is_pinned,
+			};
+
+		let memories = vec![
+			memory(0, vec![0.2, 0.98, 0.0], 0.5, false),
+			memory(1, vec![0.21, 0.97, 0.01], 0.8, false),
+			memory(2, vec![1.0, 0.0, 0.0], 0.5, false),
+		];
+		let candidates = compute_pruning_candidates(&memories, now, &config);
+
+		// The less significant of the near-identical pair is flagged
+		assert_eq!(candidates.len(), 1);
+		assert_eq!(candidates[0].index, 0);
+		assert_eq!(candidates[0].reason, PruningReason::Duplicate);
+
+		// Pinned memories are never flagged, even as the weaker copy
+		let memories = vec![
+			memory(0, vec![0.2, 0.98, 0.0], 0.5, true),
+			memory(1, vec![0.21, 0.97, 0.01], 0.8, false),
+		];
+		let candidates = compute_pruning_candidates(&memories, now, &config);
+		assert!(candidates.is_empty());
+	}
+
 	#[test]
 	fn test_retrieve_visual_empty() {
 		let input = VisualRetrievalInput {
@@ -1196,6 +2159,7 @@ mod tests {
 			memory_embeddings: &[],
 			access_histories_ms: &[],
 			emotional_weights: &[],
+			valences: &[],
 			significance_scores: &[],
 			associations: &[],
 			current_time_ms: 1_000_000.0,
@@ -1206,6 +2170,200 @@ mod tests {
 		assert!(result.is_empty());
 	}
 
+	#[test]
+	fn test_visual_validate_lengths() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let histories = vec![vec![0.0]];
+		let input = VisualRetrievalInput {
+			probe_embedding: &[1.0, 0.0],
+			memory_embeddings: &memories,
+			access_histories_ms: &histories,
+			emotional_weights: &[],
+			valences: &[0.1],
+			significance_scores: &[0.5, 0.5],
+			associations: &[],
+			current_time_ms: 1_000_000.0,
+		};
+
+		let fields: Vec<&str> = input
+			.validate()
+			.err()
+			.map(|e| e.mismatches.iter().map(|m| m.field).collect())
+			.unwrap_or_default();
+		assert_eq!(fields, vec!["access_histories_ms", "valences"]);
+
+		let full_histories = vec![vec![0.0], vec![0.0]];
+		let valid = VisualRetrievalInput {
+			access_histories_ms: &full_histories,
+			valences: &[],
+			..input
+		};
+		assert!(valid.validate().is_ok());
+	}
+
+	#[test]
+	fn test_retrieve_visual_valence_weight() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![vec![0.8, 0.6], vec![0.8, -0.6]];
+		let now = 1_000_000.0;
+
+		let input = VisualRetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now - 1000.0], vec![now - 1000.0]],
+			emotional_weights: &[0.5, 0.5],
+			valences: &[0.0, -0.9],
+			significance_scores: &[0.5, 0.5],
+			associations: &[],
+			current_time_ms: now,
+		};
+		let config = VisualRetrievalConfig {
+			spreading_depth: 0,
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		// Valence is ignored by default
+		let result = retrieve_visual(&input, &config);
+		assert!((result[0].total_activation - result[1].total_activation).abs() < 1e-12);
+
+		let result = retrieve_visual(
+			&input,
+			&VisualRetrievalConfig {
+				valence_weight: 0.4,
+				..config
+			},
+		);
+		assert_eq!(result[0].index, 1);
+		assert!(result[0].total_activation > result[1].total_activation);
+		assert!((result[0].emotional_weight - 0.86).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_retrieve_visual_valence_weight_is_symmetric() {
+		let probe = vec![1.0, 0.0];
+		let memories = vec![vec![0.8, 0.6], vec![0.8, -0.6]];
+		let now = 1_000_000.0;
+
+		let input = VisualRetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now - 1000.0], vec![now - 1000.0]],
+			emotional_weights: &[0.5, 0.5],
+			valences: &[0.9, -0.9],
+			significance_scores: &[0.5, 0.5],
+			associations: &[],
+			current_time_ms: now,
+		};
+		let config = VisualRetrievalConfig {
+			spreading_depth: 0,
+			min_probability: 0.0,
+			valence_weight: 0.4,
+			..Default::default()
+		};
+
+		let result = retrieve_visual(&input, &config);
+		assert_eq!(result.len(), 2);
+		assert!((result[0].emotional_weight - result[1].emotional_weight).abs() < 1e-12);
+		assert!((result[0].total_activation - result[1].total_activation).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_rank_multimodal_ties_keep_text_first() {
+		let candidate = |modality, index, total_activation| MultimodalCandidate {
+			modality,
+			index,
+			total_activation,
+			probability: 0.5,
+			latency_ms: 100.0,
+		};
+		let ranked = rank_multimodal(
+			vec![
+				candidate(Modality::Text, 3, 1.0),
+				candidate(Modality::Visual, 0, 1.0),
+				candidate(Modality::Text, 1, 1.0),
+				candidate(Modality::Visual, 2, 2.0),
+			],
+			10,
+		);
+
+		let order: Vec<(Modality, usize)> = ranked.iter().map(|c| (c.modality, c.index)).collect();
+		assert_eq!(
+			order,
+			vec![
+				(Modality::Visual, 2),
+				(Modality::Text, 1),
+				(Modality::Text, 3),
+				(Modality::Visual, 0),
+			]
+		);
+		assert_eq!(rank_multimodal(ranked, 2).len(), 2);
+	}
+
+	#[test]
+	fn test_retrieve_multimodal_interleaves_by_activation() {
+		let probe = vec![1.0, 0.0];
+		let now = 1_000_000.0;
+		let text_memories = vec![vec![1.0, 0.0], vec![0.6, 0.8], vec![0.0, 1.0]];
+		let visual_memories = vec![vec![0.9, 0.44], vec![0.3, 0.95]];
+		let text_histories = vec![vec![now - 1000.0]; 3];
+		let visual_histories = vec![vec![now - 1000.0]; 2];
+
+		let text_input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &text_memories,
+			access_histories_ms: &text_histories,
+			emotional_weights: &[],
+			decay_rates: &[],
+			working_memory_boosts: &[],
+			associations: &[],
+			current_time_ms: now,
+			reconsolidation: None,
+		};
+		let visual_input = VisualRetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &visual_memories,
+			access_histories_ms: &visual_histories,
+			emotional_weights: &[0.5, 0.5],
+			valences: &[],
+			significance_scores: &[0.5, 0.5],
+			associations: &[],
+			current_time_ms: now,
+		};
+		let text_config = RetrievalConfig {
+			min_probability: 0.0,
+			spreading_depth: 0,
+			..Default::default()
+		};
+		let visual_config = VisualRetrievalConfig {
+			min_probability: 0.0,
+			spreading_depth: 0,
+			..Default::default()
+		};
+
+		let merged =
+			retrieve_multimodal(&text_input, &visual_input, &text_config, &visual_config, 10);
+		let order: Vec<(Modality, usize)> = merged.iter().map(|c| (c.modality, c.index)).collect();
+		assert_eq!(
+			order,
+			vec![
+				(Modality::Text, 0),
+				(Modality::Visual, 0),
+				(Modality::Text, 1),
+				(Modality::Visual, 1),
+				(Modality::Text, 2),
+			]
+		);
+		assert!(merged
+			.windows(2)
+			.all(|w| w[0].total_activation >= w[1].total_activation));
+
+		// One cut across both stores
+		let top = retrieve_multimodal(&text_input, &visual_input, &text_config, &visual_config, 2);
+		assert_eq!(top.len(), 2);
+		assert_eq!(top[1].modality, Modality::Visual);
+	}
+
 	#[test]
 	fn test_retrieve_visual_similarity_ordering() {
 		let probe = vec![1.0, 0.0, 0.0];
@@ -1221,6 +2379,7 @@ mod tests {
 			memory_embeddings: &memories,
 			access_histories_ms: &[vec![now], vec![now], vec![now]],
 			emotional_weights: &[0.5, 0.5, 0.5],
+			valences: &[],
 			significance_scores: &[0.5, 0.5, 0.5],
 			associations: &[],
 			current_time_ms: now,
@@ -1237,5 +2396,188 @@ mod tests {
 		// First result should be the identical memory
 		assert!(!result.is_empty());
 		assert_eq!(result[0].index, 0);
+
+		let doubled = retrieve_visual(
+			&input,
+			&VisualRetrievalConfig {
+				latency_factor: 2.0,
+				..config
+			},
+		);
+		assert!((doubled[0].latency_ms / result[0].latency_ms - 2.0).abs() < 1e-9);
+
+		let Ok(json) = visual_retrieval_result_to_json(&result) else {
+			unreachable!("candidates always serialize")
+		};
+		let Ok(parsed) = serde_json::from_str::<Vec<VisualRetrievalCandidate>>(&json) else {
+			unreachable!("serialized candidates parse back")
+		};
+		assert_eq!(parsed.len(), result.len());
+		for (a, b) in parsed.iter().zip(&result) {
+			assert_eq!(a.index, b.index);
+			assert!((a.total_activation - b.total_activation).abs() < 1e-12);
+			assert!((a.significance_boost - b.significance_boost).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_text_and_visual_seeding_match() {
+		use crate::retrieval::{retrieve, RetrievalConfig, RetrievalInput};
+
+		// Shared formulas, but each path keeps its original default
+		assert_eq!(
+			RetrievalConfig::default().seed_mode,
+			SpreadingSeedMode::Multiplicative
+		);
+		assert_eq!(
+			VisualRetrievalConfig::default().seed_mode,
+			SpreadingSeedMode::Additive
+		);
+
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![0.0, 1.0, 0.0],
+			vec![0.0, 0.0, 1.0],
+		];
+		let now = 1_000_000.0;
+		let histories = [vec![now - 1000.0], vec![now - 1000.0], vec![now - 1000.0]];
+		let associations = [Association {
+			source: 0,
+			target: 1,
+			forward_strength: 0.8,
+			backward_strength: 0.4,
+		}];
+
+		for mode in [
+			SpreadingSeedMode::Multiplicative,
+			SpreadingSeedMode::Additive,
+		] {
+			let text = retrieve(
+				&RetrievalInput {
+					probe_embedding: &probe,
+					memory_embeddings: &memories,
+					access_histories_ms: &histories,
+					emotional_weights: &[0.5, 0.5, 0.5],
+					decay_rates: &[0.5, 0.5, 0.5],
+					working_memory_boosts: &[1.0, 1.0, 1.0],
+					associations: &associations,
+					current_time_ms: now,
+					reconsolidation: None,
+				},
+				&RetrievalConfig {
+					min_probability: 0.0,
+					seed_mode: mode,
+					..Default::default()
+				},
+			);
+			let visual = retrieve_visual(
+				&VisualRetrievalInput {
+					probe_embedding: &probe,
+					memory_embeddings: &memories,
+					access_histories_ms: &histories,
+					emotional_weights: &[0.5, 0.5, 0.5],
+					valences: &[],
+					significance_scores: &[0.0, 0.0, 0.0],
+					associations: &associations,
+					current_time_ms: now,
+				},
+				&VisualRetrievalConfig {
+					min_probability: 0.0,
+					seed_mode: mode,
+					..Default::default()
+				},
+			);
+
+			let text_spreading = text.iter().find(|c| c.index == 1).map(|c| c.spreading);
+			let visual_spreading = visual.iter().find(|c| c.index == 1).map(|c| c.spreading);
+			assert!(text_spreading.is_some_and(|s| s > 0.0), "{mode:?}");
+			assert_eq!(text_spreading, visual_spreading, "{mode:?}");
+		}
+	}
+
+	#[test]
+	fn test_retrieve_visual_max_spreading_seeds() {
+		let fixture = SixthSeedFixture::new();
+		let count = fixture.memories.len();
+		let emotional_weights = vec![0.5; count];
+		let significance_scores = vec![0.0; count];
+		let input = VisualRetrievalInput {
+			probe_embedding: &fixture.probe,
+			memory_embeddings: &fixture.memories,
+			access_histories_ms: &fixture.histories,
+			emotional_weights: &emotional_weights,
+			valences: &[],
+			significance_scores: &significance_scores,
+			associations: &fixture.associations,
+			current_time_ms: fixture.now,
+		};
+
+		let surfaced = |max_spreading_seeds| {
+			let config = VisualRetrievalConfig {
+				max_spreading_seeds,
+				..Default::default()
+			};
+			retrieve_visual(&input, &config)
+				.iter()
+				.any(|c| c.index == SixthSeedFixture::TARGET)
+		};
+
+		assert!(!surfaced(5));
+		assert!(surfaced(6));
+	}
+
+	#[test]
+	fn test_visual_memories_through_text_retrieval() {
+		let now = MS_PER_DAY * 10.0;
+		let memory =
+			|id: u32, embedding: Vec<f64>, last_accessed_ms: f64, access_count: u32| VisualMemory {
+				id,
+				description: format!("Frame {id}"),
+				detailed_description: None,
+				embedding,
+				captured_at_ms: 0.0,
+				last_accessed_ms,
+				access_count,
+				emotional_context: EmotionalContext::default(),
+				significance: 0.5,
+				source: VisualSource::Direct,
+				shared_by: None,
+				video_id: None,
+				frame_number: None,
+				is_scene_change: false,
+				objects: vec![],
+				tags: vec![],
+				is_pinned: false,
+			};
+		let memories = vec![
+			memory(0, vec![0.0, 1.0, 0.0], now - 1000.0, 5),
+			memory(1, vec![1.0, 0.0, 0.0], MS_PER_DAY, 1),
+			memory(2, vec![1.0, 0.0, 0.0], now - 1000.0, 5),
+			memory(3, vec![1.0, 0.0, 0.0], 0.0, 0),
+		];
+
+		let owned = visual_memories_to_retrieval_input(&memories, now);
+		assert_eq!(owned.access_histories_ms[2].len(), 5);
+		assert_eq!(owned.access_histories_ms[2][4], now - 1000.0);
+		assert_eq!(owned.access_histories_ms[3], vec![0.0]);
+		assert_eq!(owned.emotional_weights, vec![0.5; 4]);
+
+		let probe = vec![1.0, 0.0, 0.0];
+		let input = owned.as_input(&probe);
+		assert!(input.validate().is_ok());
+
+		let config = crate::retrieval::RetrievalConfig {
+			min_probability: 0.0,
+			..Default::default()
+		};
+		let ranked: Vec<usize> = crate::retrieval::retrieve(&input, &config)
+			.iter()
+			.map(|c| c.index)
+			.collect();
+
+		// Matching, recently and often accessed memory first; the orthogonal one last
+		assert_eq!(ranked.first(), Some(&2));
+		assert_eq!(ranked.last(), Some(&0));
 	}
 }
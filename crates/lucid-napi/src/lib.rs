@@ -7,22 +7,43 @@
 // Memory indices will never exceed u32::MAX in practice
 #![allow(clippy::cast_possible_truncation)]
 
+use std::collections::HashMap;
+
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 use lucid_core::{
+	activation::{CombineMode, SimilarityMetric},
 	location::{
 		compute_association_strength as core_association_strength,
+		compute_batch_decay_with_transitions as core_batch_decay_with_transitions,
 		compute_familiarity as core_compute_familiarity,
 		get_associated_locations as core_get_associated,
-		infer_activity_type as core_infer_activity, is_well_known as core_is_well_known,
-		ActivityInference, ActivityType, LocationAssociation, LocationConfig,
+		increment_familiarity as core_increment_familiarity,
+		infer_activity_type_with_keywords as core_infer_activity,
+		is_well_known as core_is_well_known, ActivityInference, ActivityType, LocationAssociation,
+		LocationConfig, LocationIntuition,
+	},
+	retrieval::{
+		estimate_retrieval_cost as core_estimate_retrieval_cost,
+		retrieve_full_scores as core_retrieve_full_scores,
+		retrieve_multi_probe as core_retrieve_multi_probe,
+		retrieve_with_diagnostics as core_retrieve_with_diagnostics, MultiProbeMode,
+		RetrievalCandidate, RetrievalConfig as CoreConfig, RetrievalInput, SpreadingNormalization,
+		SpreadingSeedMode,
 	},
-	retrieval::{retrieve as core_retrieve, RetrievalConfig as CoreConfig, RetrievalInput},
-	spreading::Association as CoreAssociation,
+	spreading::{Association as CoreAssociation, AssociationMergeMode},
 	visual::{
-		retrieve_visual as core_retrieve_visual, should_prune as core_should_prune, VisualConfig,
-		VisualRetrievalConfig, VisualRetrievalInput,
+		compute_decayed_significance as core_compute_decayed_significance,
+		compute_pruning_candidates as core_compute_pruning_candidates,
+		consolidation_duration_for as core_consolidation_duration_for,
+		infer_emotional_prior as core_infer_emotional_prior,
+		retrieve_multimodal as core_retrieve_multimodal, retrieve_visual as core_retrieve_visual,
+		should_prune_with_scene_change as core_should_prune_with_scene_change,
+		visual_memories_to_retrieval_input as core_visual_memories_to_retrieval_input,
+		ConsolidationState, ConsolidationWindow, EmotionalContext, Modality, PruningCandidate,
+		VisualConfig, VisualConsolidationState, VisualMemory, VisualRetrievalConfig,
+		VisualRetrievalInput, VisualSource,
 	},
 };
 
@@ -38,7 +59,7 @@ pub struct JsAssociation {
 
 /// Configuration for retrieval.
 #[napi(object)]
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct JsRetrievalConfig {
 	/// Decay rate for base-level activation (default: 0.5)
 	pub decay_rate: Option<f64>,
@@ -54,8 +75,48 @@ pub struct JsRetrievalConfig {
 	pub min_probability: Option<f64>,
 	/// Maximum results to return (default: 10)
 	pub max_results: Option<u32>,
+	/// Ranked results to skip before `maxResults`, for paging (default: 0)
+	pub offset: Option<u32>,
 	/// Whether to spread bidirectionally (default: true)
 	pub bidirectional: Option<bool>,
+	/// Multiplier on backward spreading; 1.0 leaves it to edge strength (default: 0.7)
+	pub backward_decay: Option<f64>,
+	/// Spreading seed formula: "multiplicative" or "additive" (default: "multiplicative")
+	pub seed_mode: Option<String>,
+	/// Number of top-activated memories that seed spreading (default: 5)
+	pub max_spreading_seeds: Option<u32>,
+	/// Activation a memory must exceed to seed spreading (default: 0.1 for
+	/// "multiplicative", 0 for "additive")
+	pub seed_activation_floor: Option<f64>,
+	/// Spreading normalization: "none", "max" or "softmax" (default: "none")
+	pub spreading_normalization: Option<String>,
+	/// Softmax temperature when `spreadingNormalization` is "softmax" (default: 1.0)
+	pub spreading_temperature: Option<f64>,
+	/// Most recent accesses per memory summed for base-level, histories oldest first (default: all)
+	pub max_history: Option<u32>,
+	/// Noise scale for reported probabilities only; ranking is unaffected (default: 1.0)
+	pub probability_temperature: Option<f64>,
+	/// Similarity metric: "cosine", "dot", "euclidean" or "manhattan" (default: "cosine")
+	pub similarity_metric: Option<String>,
+	/// Embeddings are unit length, so cosine skips norm computation (default: false)
+	pub embeddings_prenormalized: Option<bool>,
+	/// Activation combination: "multiplicative" or "additive" (default: "multiplicative")
+	pub combine_mode: Option<String>,
+	/// Strength of emotional modulation; 2.0 doubles the spread between
+	/// emotional and neutral memories, 0 disables it (default: 1.0)
+	pub emotional_multiplier_scale: Option<f64>,
+	/// Activation boost for memories with no access history (default: 0)
+	pub cold_start_boost: Option<f64>,
+	/// Latency scaling factor for `latencyMs` (default: 1.0)
+	pub latency_factor: Option<f64>,
+	/// Fill in `normalizedActivation` on each candidate (default: false)
+	pub normalize_activations: Option<bool>,
+	/// Fail `retrieve` when any memory embedding's dimension differs from
+	/// the probe's, listing the offending indices (default: false)
+	pub strict_dimensions: Option<bool>,
+	/// Ceiling on WM-boosted similarity; above 1 lets the boost separate
+	/// memories that already match fully (default: 1.0)
+	pub wm_cap: Option<f64>,
 }
 
 /// Result candidate from retrieval.
@@ -75,6 +136,11 @@ pub struct JsRetrievalCandidate {
 	pub total_activation: f64,
 	/// Retrieval probability (0-1)
 	pub probability: f64,
+	/// Estimated retrieval latency in milliseconds
+	pub latency_ms: f64,
+	/// Total activation scaled to 0-1 across the results, when
+	/// `normalizeActivations` is set
+	pub normalized_activation: Option<f64>,
 }
 
 /// Full retrieval pipeline using ACT-R spreading activation and MINERVA 2.
@@ -97,6 +163,14 @@ pub struct JsRetrievalCandidate {
 /// * `current_time_ms` - Current time in milliseconds
 /// * `associations` - Optional association graph edges
 /// * `config` - Optional retrieval configuration
+///
+/// # Errors
+///
+/// Returns an error if the per-memory arrays have inconsistent lengths. The
+/// emotional weight, decay rate and WM boost arrays may be empty instead.
+/// With `strictDimensions`, also errors on memories whose embedding dimension
+/// differs from the probe's. A `noiseParameter` that isn't positive is
+/// rejected.
 #[napi]
 pub fn retrieve(
 	probe_embedding: Vec<f64>,
@@ -108,39 +182,138 @@ pub fn retrieve(
 	current_time_ms: f64,
 	associations: Option<Vec<JsAssociation>>,
 	config: Option<JsRetrievalConfig>,
-) -> Vec<JsRetrievalCandidate> {
-	let config = config.unwrap_or(JsRetrievalConfig {
-		decay_rate: None,
-		activation_threshold: None,
-		noise_parameter: None,
-		spreading_depth: None,
-		spreading_decay: None,
-		min_probability: None,
-		max_results: None,
-		bidirectional: None,
-	});
+) -> napi::Result<Vec<JsRetrievalCandidate>> {
+	retrieve_with_diagnostics(
+		probe_embedding,
+		memory_embeddings,
+		access_histories_ms,
+		emotional_weights,
+		decay_rates,
+		working_memory_boosts,
+		current_time_ms,
+		associations,
+		config,
+	)
+	.map(|result| result.candidates)
+}
+
+/// Embedding dimension diagnostics from a retrieval pass.
+#[napi(object)]
+pub struct JsRetrievalDiagnostics {
+	/// Dimension of the first non-empty memory embedding (0 if none)
+	pub embedding_dimension: u32,
+	/// Dimension of the probe embedding
+	pub probe_dimension: u32,
+	/// Memories excluded because their dimension differs from `embeddingDimension`
+	pub skipped_mismatched: u32,
+}
+
+/// Retrieval candidates together with diagnostics.
+#[napi(object)]
+pub struct JsRetrievalResult {
+	/// Ranked candidates, as `retrieve` returns them
+	pub candidates: Vec<JsRetrievalCandidate>,
+	/// Embedding dimensions seen and memories skipped for mismatching them
+	pub diagnostics: JsRetrievalDiagnostics,
+}
 
-	let core_config = CoreConfig {
-		decay_rate: config.decay_rate.unwrap_or(0.5),
-		activation_threshold: config.activation_threshold.unwrap_or(0.3),
-		noise_parameter: config.noise_parameter.unwrap_or(0.1),
-		spreading_depth: config.spreading_depth.unwrap_or(3) as usize,
-		spreading_decay: config.spreading_decay.unwrap_or(0.7),
-		min_probability: config.min_probability.unwrap_or(0.1),
-		max_results: config.max_results.unwrap_or(10) as usize,
-		bidirectional: config.bidirectional.unwrap_or(true),
+/// Same as `retrieve`, but also reports the detected embedding dimension and
+/// how many memories were skipped for having a different dimension.
+///
+/// # Errors
+///
+/// Returns an error if the per-memory arrays have inconsistent lengths or
+/// `noiseParameter` isn't positive, or with `strictDimensions`, if any
+/// memory's dimension differs from the probe's.
+#[napi]
+pub fn retrieve_with_diagnostics(
+	probe_embedding: Vec<f64>,
+	memory_embeddings: Vec<Vec<f64>>,
+	access_histories_ms: Vec<Vec<f64>>,
+	emotional_weights: Vec<f64>,
+	decay_rates: Vec<f64>,
+	working_memory_boosts: Vec<f64>,
+	current_time_ms: f64,
+	associations: Option<Vec<JsAssociation>>,
+	config: Option<JsRetrievalConfig>,
+) -> napi::Result<JsRetrievalResult> {
+	let core_config = js_retrieval_config_to_core(config)?;
+	let associations = js_associations_to_core(associations);
+
+	let input = RetrievalInput {
+		probe_embedding: &probe_embedding,
+		memory_embeddings: &memory_embeddings,
+		access_histories_ms: &access_histories_ms,
+		emotional_weights: &emotional_weights,
+		decay_rates: &decay_rates,
+		working_memory_boosts: &working_memory_boosts,
+		associations: &associations,
+		current_time_ms,
+		reconsolidation: None,
 	};
+	input
+		.validate()
+		.map_err(|e| napi::Error::from_reason(e.to_string()))?;
+	if core_config.strict_dimensions {
+		input
+			.check_dimensions()
+			.map_err(|e| napi::Error::from_reason(e.to_string()))?;
+	}
 
-	let associations: Vec<CoreAssociation> = associations
-		.unwrap_or_default()
-		.into_iter()
-		.map(|a| CoreAssociation {
-			source: a.source as usize,
-			target: a.target as usize,
-			forward_strength: a.forward_strength,
-			backward_strength: a.backward_strength,
-		})
-		.collect();
+	let (candidates, diagnostics) = core_retrieve_with_diagnostics(&input, &core_config);
+
+	Ok(JsRetrievalResult {
+		candidates: candidates.into_iter().map(candidate_to_js).collect(),
+		diagnostics: JsRetrievalDiagnostics {
+			embedding_dimension: diagnostics.embedding_dimension as u32,
+			probe_dimension: diagnostics.probe_dimension as u32,
+			skipped_mismatched: diagnostics.skipped_mismatched as u32,
+		},
+	})
+}
+
+/// Every memory's component scores as parallel typed arrays.
+#[napi(object)]
+pub struct JsFullScoreTable {
+	/// Similarity between the probe and each memory
+	pub similarity: Float64Array,
+	/// Base-level activation from access history
+	pub base_level: Float64Array,
+	/// Probe-trace activation (cubed similarity)
+	pub probe_activation: Float64Array,
+	/// Spreading activation from associated memories
+	pub spreading: Float64Array,
+	/// Emotional weight factor
+	pub emotional_weight: Float64Array,
+	/// Combined total activation
+	pub total_activation: Float64Array,
+	/// Retrieval probability (0-1)
+	pub probability: Float64Array,
+}
+
+/// Score every memory without filtering, sorting or truncation.
+///
+/// Intended for dumping scores to analyze or tune retrieval offline; use
+/// `retrieve` on hot paths.
+///
+/// # Errors
+///
+/// Returns an error if the per-memory arrays have inconsistent lengths or
+/// `noiseParameter` isn't positive.
+#[napi]
+pub fn retrieve_full_scores(
+	probe_embedding: Vec<f64>,
+	memory_embeddings: Vec<Vec<f64>>,
+	access_histories_ms: Vec<Vec<f64>>,
+	emotional_weights: Vec<f64>,
+	decay_rates: Vec<f64>,
+	working_memory_boosts: Vec<f64>,
+	current_time_ms: f64,
+	associations: Option<Vec<JsAssociation>>,
+	config: Option<JsRetrievalConfig>,
+) -> napi::Result<JsFullScoreTable> {
+	let core_config = js_retrieval_config_to_core(config)?;
+	let associations = js_associations_to_core(associations);
 
 	let input = RetrievalInput {
 		probe_embedding: &probe_embedding,
@@ -151,22 +324,123 @@ pub fn retrieve(
 		working_memory_boosts: &working_memory_boosts,
 		associations: &associations,
 		current_time_ms,
+		reconsolidation: None,
 	};
+	input
+		.validate()
+		.map_err(|e| napi::Error::from_reason(e.to_string()))?;
+
+	let table = core_retrieve_full_scores(&input, &core_config);
+
+	Ok(JsFullScoreTable {
+		similarity: Float64Array::new(table.similarity),
+		base_level: Float64Array::new(table.base_level),
+		probe_activation: Float64Array::new(table.probe_activation),
+		spreading: Float64Array::new(table.spreading),
+		emotional_weight: Float64Array::new(table.emotional_weight),
+		total_activation: Float64Array::new(table.total_activation),
+		probability: Float64Array::new(table.probability),
+	})
+}
 
-	let candidates = core_retrieve(&input, &core_config);
+/// Approximate cost of a `retrieve` call, from [`estimate_retrieval_cost`].
+#[napi(object)]
+pub struct JsRetrievalCostEstimate {
+	/// Floating-point operations spent on similarity
+	pub similarity_flops: f64,
+	/// Expected nodes reached by spreading
+	pub spreading_node_visits: f64,
+	/// Coarse single-core time in milliseconds
+	pub estimated_ms: f64,
+}
 
-	candidates
-		.into_iter()
-		.map(|c| JsRetrievalCandidate {
-			index: c.index as u32,
-			base_level: c.base_level,
-			probe_activation: c.probe_activation,
-			spreading: c.spreading,
-			emotional_weight: c.emotional_weight,
-			total_activation: c.total_activation,
-			probability: c.probability,
-		})
-		.collect()
+/// Estimate what `retrieve` would cost for a store of this size, to decide
+/// between full retrieval and `retrieveBySimilarityThreshold`.
+///
+/// # Errors
+///
+/// Returns an error if `noiseParameter` isn't positive.
+#[napi]
+pub fn estimate_retrieval_cost(
+	num_memories: u32,
+	embedding_dim: u32,
+	num_associations: u32,
+	config: Option<JsRetrievalConfig>,
+) -> napi::Result<JsRetrievalCostEstimate> {
+	let core_config = js_retrieval_config_to_core(config)?;
+	let estimate = core_estimate_retrieval_cost(
+		num_memories as usize,
+		embedding_dim as usize,
+		num_associations as usize,
+		&core_config,
+	);
+
+	Ok(JsRetrievalCostEstimate {
+		similarity_flops: estimate.similarity_flops,
+		spreading_node_visits: estimate.spreading_node_visits,
+		estimated_ms: estimate.estimated_ms,
+	})
+}
+
+/// Retrieve against several probe embeddings at once (query expansion).
+///
+/// Each memory's similarity to the probes is merged before the nonlinear
+/// step, then retrieval continues as in `retrieve`. `mode` is "max" (match
+/// any probe, the default) or "mean" (match the probes as a whole);
+/// `probeWeights` defaults to 1 for every probe.
+///
+/// # Errors
+///
+/// Returns an error if `probeWeights` doesn't have one entry per probe, the
+/// per-memory arrays have inconsistent lengths, or `noiseParameter` isn't
+/// positive.
+#[napi]
+pub fn retrieve_multi_probe(
+	probes: Vec<Vec<f64>>,
+	probe_weights: Option<Vec<f64>>,
+	mode: Option<String>,
+	memory_embeddings: Vec<Vec<f64>>,
+	access_histories_ms: Vec<Vec<f64>>,
+	emotional_weights: Vec<f64>,
+	decay_rates: Vec<f64>,
+	working_memory_boosts: Vec<f64>,
+	current_time_ms: f64,
+	associations: Option<Vec<JsAssociation>>,
+	config: Option<JsRetrievalConfig>,
+) -> napi::Result<Vec<JsRetrievalCandidate>> {
+	let probe_weights = probe_weights.unwrap_or_default();
+	if !probe_weights.is_empty() && probe_weights.len() != probes.len() {
+		return Err(napi::Error::from_reason(format!(
+			"probeWeights has {} entries, expected {}",
+			probe_weights.len(),
+			probes.len()
+		)));
+	}
+
+	let core_config = js_retrieval_config_to_core(config)?;
+	let associations = js_associations_to_core(associations);
+	let mode = mode
+		.as_deref()
+		.map_or_else(MultiProbeMode::default, parse_multi_probe_mode);
+
+	let input = RetrievalInput {
+		probe_embedding: &[],
+		memory_embeddings: &memory_embeddings,
+		access_histories_ms: &access_histories_ms,
+		emotional_weights: &emotional_weights,
+		decay_rates: &decay_rates,
+		working_memory_boosts: &working_memory_boosts,
+		associations: &associations,
+		current_time_ms,
+		reconsolidation: None,
+	};
+	input
+		.validate()
+		.map_err(|e| napi::Error::from_reason(e.to_string()))?;
+
+	let candidates = core_retrieve_multi_probe(&probes, &probe_weights, mode, &input, &core_config);
+
+	Ok(candidates.into_iter().map(candidate_to_js).collect())
 }
 
 /// Compute cosine similarity between two vectors.
@@ -181,6 +455,25 @@ pub fn cosine_similarity_batch(probe: Vec<f64>, memories: Vec<Vec<f64>>) -> Vec<
 	lucid_core::activation::cosine_similarity_batch(&probe, &memories)
 }
 
+/// The `k` memories most cosine-similar to the probe, most similar first.
+///
+/// Prefer this over sorting `cosineSimilarityBatch` results in JavaScript
+/// when only the best few matter: only `k` results cross into JS.
+#[napi]
+pub fn cosine_similarity_top_k(
+	probe: Vec<f64>,
+	memories: Vec<Vec<f64>>,
+	k: u32,
+) -> Vec<JsSimilarityMatch> {
+	lucid_core::cosine_similarity_top_k(&probe, &memories, k as usize)
+		.into_iter()
+		.map(|(index, similarity)| JsSimilarityMatch {
+			index: index as u32,
+			similarity,
+		})
+		.collect()
+}
+
 /// Compute base-level activation from access history.
 ///
 /// B(m) = ln[Σ(t_k)^(-d)]
@@ -189,6 +482,51 @@ pub fn compute_base_level(access_times_ms: Vec<f64>, current_time_ms: f64, decay
 	lucid_core::compute_base_level(&access_times_ms, current_time_ms, decay)
 }
 
+/// One sample of a forgetting curve from [`preview_decay_curve`].
+#[napi(object)]
+pub struct JsDecayPoint {
+	/// Days after `currentTimeMs`
+	pub age_days: f64,
+	/// Base-level activation at that moment
+	pub activation: f64,
+}
+
+/// Sample the forgetting curve of a single access at `steps` evenly spaced
+/// moments from `currentTimeMs` to `horizonDays` later, for plotting how a
+/// decay rate behaves.
+#[napi]
+pub fn preview_decay_curve(
+	single_access_ms: f64,
+	current_time_ms: f64,
+	decay_rate: f64,
+	steps: u32,
+	horizon_days: f64,
+) -> Vec<JsDecayPoint> {
+	lucid_core::preview_base_level_decay(
+		single_access_ms,
+		current_time_ms,
+		decay_rate,
+		steps as usize,
+		horizon_days,
+	)
+	.into_iter()
+	.map(|(age_days, activation)| JsDecayPoint {
+		age_days,
+		activation,
+	})
+	.collect()
+}
+
+/// Append an access timestamp to a history, dropping the oldest entries
+/// beyond `maxHistory` (unbounded when omitted).
+///
+/// Returns the history to store in place of the old one, sorted oldest first.
+#[napi]
+pub fn record_access(history: Vec<f64>, timestamp_ms: f64, max_history: Option<u32>) -> Vec<f64> {
+	let max_history = max_history.map_or(usize::MAX, |cap| cap as usize);
+	lucid_core::record_access(&history, timestamp_ms, max_history)
+}
+
 /// Apply nonlinear activation (MINERVA 2's cubic function).
 ///
 /// A(i) = S(i)³
@@ -205,6 +543,41 @@ pub fn retrieval_probability(activation: f64, threshold: f64, noise: f64) -> f64
 	lucid_core::retrieval_probability(activation, threshold, noise)
 }
 
+/// A memory matched by [`retrieve_by_similarity_threshold`].
+#[napi(object)]
+pub struct JsSimilarityMatch {
+	/// Memory index
+	pub index: u32,
+	/// Cosine similarity to the probe
+	pub similarity: f64,
+}
+
+/// Find the memories at least `minSimilarity` similar to the probe, most
+/// similar first, without computing full activation.
+///
+/// `maxResults` caps the number returned (default: no limit).
+#[napi]
+pub fn retrieve_by_similarity_threshold(
+	probe_embedding: Vec<f64>,
+	memory_embeddings: Vec<Vec<f64>>,
+	min_similarity: f64,
+	max_results: Option<u32>,
+) -> Vec<JsSimilarityMatch> {
+	let max_results = max_results.map_or(usize::MAX, |max| max as usize);
+	lucid_core::retrieval::retrieve_by_similarity_threshold(
+		&probe_embedding,
+		&memory_embeddings,
+		min_similarity,
+		max_results,
+	)
+	.into_iter()
+	.map(|(index, similarity)| JsSimilarityMatch {
+		index: index as u32,
+		similarity,
+	})
+	.collect()
+}
+
 /// Compute surprise (prediction error) between expected and actual.
 #[napi]
 pub fn compute_surprise(
@@ -223,6 +596,134 @@ pub fn compute_surprise(
 	)
 }
 
+/// Compute surprise for many expected/actual pairs in one call.
+///
+/// # Errors
+///
+/// Returns an error if the input arrays differ in length.
+#[napi]
+pub fn compute_surprise_batch(
+	expected_embeddings: Vec<Vec<f64>>,
+	actual_embeddings: Vec<Vec<f64>>,
+	memory_ages_days: Vec<f64>,
+	memory_strengths: Vec<f64>,
+	base_threshold: f64,
+) -> napi::Result<Vec<f64>> {
+	let n = expected_embeddings.len();
+	for (name, len) in [
+		("actualEmbeddings", actual_embeddings.len()),
+		("memoryAgesDays", memory_ages_days.len()),
+		("memoryStrengths", memory_strengths.len()),
+	] {
+		if len != n {
+			return Err(napi::Error::from_reason(format!(
+				"{name} has {len} entries, expected {n}"
+			)));
+		}
+	}
+
+	Ok(lucid_core::retrieval::compute_surprise_batch(
+		&expected_embeddings,
+		&actual_embeddings,
+		&memory_ages_days,
+		&memory_strengths,
+		base_threshold,
+	))
+}
+
+/// Find the shortest association path from `source` to `target`.
+///
+/// Returns the node indices along the path (including both ends), or an empty
+/// array if `target` is unreachable or either index is out of range. Useful
+/// for explaining why a memory surfaced ("activated through A → B → C").
+#[napi]
+pub fn find_activation_path(
+	num_nodes: u32,
+	associations: Vec<JsAssociation>,
+	source: u32,
+	target: u32,
+) -> Vec<u32> {
+	if source >= num_nodes || target >= num_nodes {
+		return Vec::new();
+	}
+	let associations = js_associations_to_core(Some(associations));
+
+	lucid_core::spreading::find_activation_path(
+		num_nodes as usize,
+		&associations,
+		source as usize,
+		target as usize,
+	)
+	.into_iter()
+	.map(|node| node as u32)
+	.collect()
+}
+
+/// Rank nodes by `PageRank` importance over the association graph.
+///
+/// Returns one score per node index. High scores mark "hub" memories that
+/// many others link into. `iterations` of 0 uses the default of 100.
+///
+/// # Errors
+///
+/// Returns an error if `damping` is not strictly between 0 and 1.
+#[napi]
+pub fn compute_pagerank(
+	num_nodes: u32,
+	associations: Vec<JsAssociation>,
+	damping: f64,
+	iterations: u32,
+) -> napi::Result<Vec<f64>> {
+	if !(damping > 0.0 && damping < 1.0) {
+		return Err(napi::Error::from_reason(format!(
+			"damping must be in (0, 1), got {damping}"
+		)));
+	}
+	let iterations = if iterations == 0 { 100 } else { iterations };
+	let associations = js_associations_to_core(Some(associations));
+
+	Ok(lucid_core::spreading::compute_pagerank(
+		num_nodes as usize,
+		&associations,
+		damping,
+		iterations as usize,
+	))
+}
+
+/// Shape of an association graph, from [`graph_stats`].
+#[napi(object)]
+pub struct JsGraphStats {
+	/// Number of nodes
+	pub num_nodes: u32,
+	/// Number of edges with both endpoints in range
+	pub num_edges: u32,
+	/// Mean out-degree over all nodes
+	pub average_fan_out: f64,
+	/// Largest out-degree of any node
+	pub max_fan_out: u32,
+	/// Nodes with no edges in either direction
+	pub isolated_nodes: u32,
+	/// Whether every node is reachable from every other, ignoring direction
+	pub is_connected: bool,
+}
+
+/// Summarize the association graph (edge count, fan-out, isolated nodes,
+/// connectivity) to guide tuning of the spreading parameters.
+#[napi]
+pub fn graph_stats(num_nodes: u32, associations: Vec<JsAssociation>) -> JsGraphStats {
+	let associations = js_associations_to_core(Some(associations));
+	let stats = lucid_core::spreading::graph_stats(num_nodes as usize, &associations);
+
+	JsGraphStats {
+		num_nodes: stats.num_nodes as u32,
+		num_edges: stats.num_edges as u32,
+		average_fan_out: stats.average_fan_out,
+		max_fan_out: stats.max_fan_out as u32,
+		isolated_nodes: stats.isolated_nodes as u32,
+		is_connected: stats.is_connected,
+	}
+}
+
 // ============================================================================
 // Working Memory
 // ============================================================================
@@ -363,6 +864,8 @@ pub struct JsAssociationDecayConfig {
 	pub reinforcement_boost: Option<f64>,
 	/// Prune threshold (default: 0.1)
 	pub prune_threshold: Option<f64>,
+	/// Strength of edges created by co-access (default: 0.5)
+	pub initial_strength: Option<f64>,
 }
 
 /// Compute decayed association strength.
@@ -395,6 +898,24 @@ pub fn reinforce_association(
 	lucid_core::reinforce_association(current_strength, &core_config)
 }
 
+/// Reinforce all associations among co-accessed memories.
+///
+/// Existing edges between co-accessed nodes get the co-access boost; pairs
+/// without an edge gain one at `initialStrength`. Returns the updated edges.
+#[napi]
+pub fn reinforce_coaccess(
+	associations: Vec<JsAssociation>,
+	coaccessed: Vec<u32>,
+	config: Option<JsAssociationDecayConfig>,
+) -> Vec<JsAssociation> {
+	let core_config = js_assoc_decay_config_to_core(config);
+	let mut associations = js_associations_to_core(Some(associations));
+	let coaccessed: Vec<usize> = coaccessed.into_iter().map(|i| i as usize).collect();
+
+	lucid_core::reinforce_coaccess(&mut associations, &coaccessed, &core_config);
+	associations.into_iter().map(association_to_js).collect()
+}
+
 /// Check if an association should be pruned.
 #[napi]
 pub fn should_prune_association(strength: f64, config: Option<JsAssociationDecayConfig>) -> bool {
@@ -402,6 +923,112 @@ pub fn should_prune_association(strength: f64, config: Option<JsAssociationDecay
 	lucid_core::should_prune_association(strength, &core_config)
 }
 
+/// Decay every edge of an association graph and drop the ones that fall
+/// below `pruneThreshold` in both directions.
+///
+/// `daysSinceReinforced` and `states` are parallel to `associations`.
+///
+/// # Errors
+///
+/// Returns an error if the input arrays differ in length.
+#[napi]
+pub fn decay_associations(
+	associations: Vec<JsAssociation>,
+	days_since_reinforced: Vec<f64>,
+	states: Vec<String>,
+	config: Option<JsAssociationDecayConfig>,
+) -> napi::Result<Vec<JsAssociation>> {
+	let n = associations.len();
+	for (name, len) in [
+		("daysSinceReinforced", days_since_reinforced.len()),
+		("states", states.len()),
+	] {
+		if len != n {
+			return Err(napi::Error::from_reason(format!(
+				"{name} has {len} entries, expected {n}"
+			)));
+		}
+	}
+
+	let core_config = js_assoc_decay_config_to_core(config);
+	let core_states: Vec<_> = states.iter().map(|s| parse_association_state(s)).collect();
+	let associations = js_associations_to_core(Some(associations));
+
+	Ok(lucid_core::decay_associations(
+		&associations,
+		&days_since_reinforced,
+		&core_states,
+		&core_config,
+	)
+	.into_iter()
+	.map(association_to_js)
+	.collect())
+}
+
+/// Association graph after [`compact_graph`].
+#[napi(object)]
+pub struct JsCompactedGraph {
+	/// Surviving edges, using the new node indices
+	pub associations: Vec<JsAssociation>,
+	/// New index of each original node, or null if it was removed
+	pub index_map: Vec<Option<u32>>,
+	/// Number of surviving nodes
+	pub num_nodes: u32,
+}
+
+/// Drop the nodes not marked in `keepMask` and renumber the rest, rewriting
+/// the surviving edges to match.
+///
+/// # Errors
+///
+/// Returns an error if `keepMask` doesn't have `numNodes` entries.
+#[napi]
+pub fn compact_graph(
+	num_nodes: u32,
+	associations: Vec<JsAssociation>,
+	keep_mask: Vec<bool>,
+) -> napi::Result<JsCompactedGraph> {
+	let n = num_nodes as usize;
+	if keep_mask.len() != n {
+		return Err(napi::Error::from_reason(format!(
+			"keepMask has {} entries, expected {n}",
+			keep_mask.len()
+		)));
+	}
+
+	let associations = js_associations_to_core(Some(associations));
+	let (compacted, index_map) = lucid_core::compact_graph(n, &associations, &keep_mask);
+
+	Ok(JsCompactedGraph {
+		associations: compacted.into_iter().map(association_to_js).collect(),
+		index_map: index_map
+			.into_iter()
+			.map(|i| (i != lucid_core::REMOVED_NODE).then_some(i as u32))
+			.collect(),
+		num_nodes: keep_mask.iter().filter(|&&keep| keep).count() as u32,
+	})
+}
+
+/// Collapse duplicate directed `(source, target)` edges into one.
+///
+/// `mode` is "max" (default) to keep the strongest duplicate in each
+/// direction, or "sum" to add their strengths.
+#[napi]
+pub fn merge_associations(
+	associations: Vec<JsAssociation>,
+	mode: Option<String>,
+) -> Vec<JsAssociation> {
+	let mode = mode.map_or_else(AssociationMergeMode::default, |s| {
+		parse_association_merge_mode(&s)
+	});
+	let associations = js_associations_to_core(Some(associations));
+
+	lucid_core::merge_associations_with_mode(&associations, mode)
+		.into_iter()
+		.map(association_to_js)
+		.collect()
+}
+
 // ============================================================================
 // Reconsolidation
 // ============================================================================
@@ -523,26 +1150,52 @@ pub fn create_episode_links(
 	let indices: Vec<usize> = event_memory_indices.iter().map(|&i| i as usize).collect();
 	let links = lucid_core::create_episode_links(&indices, &core_config);
 
-	links
-		.into_iter()
-		.map(|l| JsTemporalLink {
-			source_position: l.source_position as u32,
-			target_position: l.target_position as u32,
-			source_memory: l.source_memory as u32,
-			target_memory: l.target_memory as u32,
-			forward_strength: l.forward_strength,
-			backward_strength: l.backward_strength,
-		})
-		.collect()
+	links.into_iter().map(temporal_link_to_js).collect()
 }
 
-/// Spread activation through temporal links.
+/// Result of consolidating an episode.
+#[napi(object)]
+pub struct JsEpisodeConsolidation {
+	/// Temporal links between the episode's events
+	pub links: Vec<JsTemporalLink>,
+	/// Unit-length centroid of the event embeddings
+	pub summary_embedding: Vec<f64>,
+	/// Time from the first to the last event (ms)
+	pub span_ms: f64,
+}
+
+/// Consolidate an episode: create its temporal links and a summary embedding
+/// (normalized centroid of the event embeddings) in one call.
 #[napi]
-pub fn spread_temporal_activation(
-	num_memories: u32,
-	temporal_links: Vec<JsTemporalLink>,
-	seed_memory: u32,
-	seed_activation: f64,
+pub fn consolidate_episode(
+	event_memory_indices: Vec<u32>,
+	event_embeddings: Vec<Vec<f64>>,
+	event_timestamps_ms: Vec<f64>,
+	config: Option<JsTemporalSpreadingConfig>,
+) -> JsEpisodeConsolidation {
+	let core_config = js_temporal_config_to_core(config);
+	let indices: Vec<usize> = event_memory_indices.iter().map(|&i| i as usize).collect();
+	let episode = lucid_core::consolidate_episode(
+		&indices,
+		&event_embeddings,
+		&event_timestamps_ms,
+		&core_config,
+	);
+
+	JsEpisodeConsolidation {
+		links: episode.links.into_iter().map(temporal_link_to_js).collect(),
+		summary_embedding: episode.summary_embedding,
+		span_ms: episode.span_ms,
+	}
+}
+
+/// Spread activation through temporal links.
+#[napi]
+pub fn spread_temporal_activation(
+	num_memories: u32,
+	temporal_links: Vec<JsTemporalLink>,
+	seed_memory: u32,
+	seed_activation: f64,
 	config: Option<JsTemporalSpreadingConfig>,
 ) -> JsTemporalSpreadingResult {
 	let core_config = js_temporal_config_to_core(config);
@@ -559,19 +1212,48 @@ pub fn spread_temporal_activation(
 		&core_config,
 	);
 
-	JsTemporalSpreadingResult {
-		activations: result.activations,
-		forward_activated: result
-			.forward_activated
-			.into_iter()
-			.map(|i| i as u32)
-			.collect(),
-		backward_activated: result
-			.backward_activated
-			.into_iter()
-			.map(|i| i as u32)
-			.collect(),
+	temporal_result_to_js(result)
+}
+
+/// Spread activation through temporal links from several seed memories at once.
+///
+/// A memory reached from more than one seed keeps its strongest activation.
+///
+/// # Errors
+///
+/// Returns an error if `seedMemories` and `seedActivations` differ in length.
+#[napi]
+pub fn spread_temporal_activation_multi(
+	num_memories: u32,
+	temporal_links: Vec<JsTemporalLink>,
+	seed_memories: Vec<u32>,
+	seed_activations: Vec<f64>,
+	config: Option<JsTemporalSpreadingConfig>,
+) -> napi::Result<JsTemporalSpreadingResult> {
+	if seed_activations.len() != seed_memories.len() {
+		return Err(napi::Error::from_reason(format!(
+			"seedActivations has {} entries, expected {} (one per seedMemories entry)",
+			seed_activations.len(),
+			seed_memories.len()
+		)));
 	}
+
+	let core_config = js_temporal_config_to_core(config);
+	let core_links: Vec<lucid_core::TemporalLink> = temporal_links
+		.into_iter()
+		.map(js_temporal_link_to_core)
+		.collect();
+	let seeds: Vec<usize> = seed_memories.iter().map(|&i| i as usize).collect();
+
+	let result = lucid_core::spread_temporal_activation_from_seeds(
+		num_memories as usize,
+		&core_links,
+		&seeds,
+		&seed_activations,
+		&core_config,
+	);
+
+	Ok(temporal_result_to_js(result))
 }
 
 /// Find temporally adjacent memories.
@@ -650,6 +1332,7 @@ pub fn load_embedding_model(
 	let config = lucid_core::embedding::EmbeddingModelConfig {
 		model_path: model_path.map(std::path::PathBuf::from),
 		tokenizer_path: tokenizer_path.map(std::path::PathBuf::from),
+		..Default::default()
 	};
 
 	match lucid_core::embedding::EmbeddingModel::load(&config) {
@@ -678,6 +1361,7 @@ pub fn is_embedding_model_available(
 	let config = lucid_core::embedding::EmbeddingModelConfig {
 		model_path: model_path.map(std::path::PathBuf::from),
 		tokenizer_path: tokenizer_path.map(std::path::PathBuf::from),
+		..Default::default()
 	};
 	lucid_core::embedding::EmbeddingModel::is_available(&config)
 }
@@ -805,6 +1489,8 @@ pub struct JsLocationConfig {
 	pub time_diff_activity_multiplier: Option<f64>,
 	/// Backward strength factor (default: 0.7)
 	pub backward_strength_factor: Option<f64>,
+	/// Pinned decay rate relative to max decay; 0 = pins never decay (default: 0.0)
+	pub pinned_decay_multiplier: Option<f64>,
 }
 
 /// Associated location result.
@@ -825,18 +1511,53 @@ pub fn location_compute_familiarity(access_count: u32, config: Option<JsLocation
 	core_compute_familiarity(access_count, &cfg)
 }
 
+/// Apply one more access to an existing familiarity value.
+///
+/// Recovers the effective access count from the curve, so it works even
+/// when the access count wasn't tracked.
+#[napi]
+pub fn location_increment_familiarity(
+	current_familiarity: f64,
+	config: Option<JsLocationConfig>,
+) -> f64 {
+	let cfg = js_config_to_core(config);
+	core_increment_familiarity(current_familiarity, &cfg)
+}
+
 /// Infer activity type from context string and optional tool name.
 ///
-/// Precedence: explicit > keyword > tool > default
+/// Precedence: explicit > custom keyword > keyword > tool > default
+///
+/// `customKeywords` maps activity types (e.g. "writing") to extra keywords
+/// for them; unrecognized activity types are ignored. When keywords for
+/// several types match, types are tried in alphabetical order.
 #[napi]
+#[allow(clippy::implicit_hasher)] // napi functions can't be generic
 pub fn location_infer_activity(
 	context: String,
 	tool_name: Option<String>,
 	explicit_type: Option<String>,
+	custom_keywords: Option<HashMap<String, Vec<String>>>,
 ) -> JsActivityInference {
 	let explicit = explicit_type.and_then(|s| parse_activity_type(&s));
 	let tool = tool_name.as_deref();
-	let result = core_infer_activity(&context, tool, explicit);
+
+	let mut custom_keywords: Vec<(String, Vec<String>)> =
+		custom_keywords.unwrap_or_default().into_iter().collect();
+	custom_keywords.sort_by(|a, b| a.0.cmp(&b.0));
+	let keyword_refs: Vec<(ActivityType, Vec<&str>)> = custom_keywords
+		.iter()
+		.filter_map(|(activity, keywords)| {
+			parse_activity_type(activity)
+				.map(|activity| (activity, keywords.iter().map(String::as_str).collect()))
+		})
+		.collect();
+	let extra_keywords: Vec<(ActivityType, &[&str])> = keyword_refs
+		.iter()
+		.map(|(activity, keywords)| (*activity, keywords.as_slice()))
+		.collect();
+
+	let result = core_infer_activity(&context, tool, explicit, &extra_keywords);
 	activity_inference_to_js(result)
 }
 
@@ -871,6 +1592,45 @@ pub fn location_get_associated(
 		.collect()
 }
 
+/// Familiarity of a location after decay.
+#[napi(object)]
+pub struct JsLocationDecay {
+	/// Familiarity after decay
+	pub familiarity: f64,
+	/// Whether decay took the location below the well-known threshold
+	pub demoted: bool,
+}
+
+/// Decay many locations at once, flagging those that stopped being
+/// well-known.
+#[napi]
+pub fn location_batch_decay_with_transitions(
+	locations: Vec<JsLocationIntuition>,
+	current_time_ms: f64,
+	config: Option<JsLocationConfig>,
+) -> Vec<JsLocationDecay> {
+	let cfg = js_config_to_core(config);
+	let locations: Vec<LocationIntuition> = locations
+		.into_iter()
+		.map(|loc| LocationIntuition {
+			id: loc.id,
+			familiarity: loc.familiarity,
+			access_count: loc.access_count,
+			searches_saved: loc.searches_saved,
+			last_accessed_ms: loc.last_accessed_ms,
+			is_pinned: loc.is_pinned,
+		})
+		.collect();
+
+	core_batch_decay_with_transitions(&locations, current_time_ms, &cfg)
+		.into_iter()
+		.map(|(familiarity, demoted)| JsLocationDecay {
+			familiarity,
+			demoted,
+		})
+		.collect()
+}
+
 /// Check if a location is well-known based on familiarity threshold.
 #[napi]
 pub fn location_is_well_known(familiarity: f64, config: Option<JsLocationConfig>) -> bool {
@@ -930,6 +1690,8 @@ pub struct JsVisualMemory {
 	pub video_id: Option<String>,
 	/// Frame number
 	pub frame_number: Option<u32>,
+	/// Frame opens a new scene in its video (default: false)
+	pub is_scene_change: Option<bool>,
 	/// Detected objects
 	pub objects: Vec<String>,
 	/// Tags
@@ -960,6 +1722,20 @@ pub struct JsVisualConfig {
 	pub pruning_stale_days: Option<u32>,
 	/// Preserve keyframes (default: true)
 	pub preserve_keyframes: Option<bool>,
+	/// Preserve scene-change frames (default: true)
+	pub preserve_scene_changes: Option<bool>,
+	/// Similarity at which memories count as duplicates (default: 0.97)
+	pub duplicate_similarity_threshold: Option<f64>,
+	/// Consolidation window for "discord" images in ms (default: 6 hours)
+	pub discord_consolidation_ms: Option<f64>,
+	/// Consolidation window for "sms" images in ms (default: 6 hours)
+	pub sms_consolidation_ms: Option<f64>,
+	/// Consolidation window for "direct" images in ms (default: 24 hours)
+	pub direct_consolidation_ms: Option<f64>,
+	/// Consolidation window for "videoframe" images in ms (default: 1 hour)
+	pub video_frame_consolidation_ms: Option<f64>,
+	/// Consolidation window for "other" images in ms (default: 6 hours)
+	pub other_consolidation_ms: Option<f64>,
 }
 
 /// Configuration for visual retrieval.
@@ -982,10 +1758,23 @@ pub struct JsVisualRetrievalConfig {
 	pub max_results: Option<u32>,
 	/// Bidirectional spreading (default: true)
 	pub bidirectional: Option<bool>,
+	/// Backward spreading multiplier (default: 0.7)
+	pub backward_decay: Option<f64>,
 	/// Emotional boost (default: 0.3)
 	pub emotional_boost: Option<f64>,
+	/// Weight of `|valence|` in the emotional weight (default: 0.0)
+	pub valence_weight: Option<f64>,
 	/// Significance boost (default: 0.2)
 	pub significance_boost: Option<f64>,
+	/// Spreading seed formula: "multiplicative" or "additive" (default: "additive")
+	pub seed_mode: Option<String>,
+	/// Number of top-activated memories that seed spreading (default: 5)
+	pub max_spreading_seeds: Option<u32>,
+	/// Activation a memory must exceed to seed spreading (default: 0.1 for
+	/// "multiplicative", 0 for "additive")
+	pub seed_activation_floor: Option<f64>,
+	/// Latency scaling factor for `latencyMs` (default: 1.0)
+	pub latency_factor: Option<f64>,
 }
 
 /// Result from visual retrieval.
@@ -1007,6 +1796,8 @@ pub struct JsVisualRetrievalCandidate {
 	pub total_activation: f64,
 	/// Retrieval probability
 	pub probability: f64,
+	/// Estimated retrieval latency in milliseconds
+	pub latency_ms: f64,
 }
 
 /// Consolidation state.
@@ -1033,9 +1824,39 @@ pub struct JsPruningCandidate {
 	pub reason: String,
 	/// Pruning score
 	pub score: f64,
+	/// The factors the score was computed from
+	pub breakdown: JsPruningBreakdown,
+}
+
+/// How a pruning score was reached:
+/// `score = stalenessComponent × significanceComponent × (duplicateSimilarity ?? 1)`.
+#[napi(object)]
+#[allow(clippy::struct_excessive_bools)] // Mirrors the core breakdown
+pub struct JsPruningBreakdown {
+	/// Staleness factor (1 for duplicates)
+	pub staleness_component: f64,
+	/// Significance factor
+	pub significance_component: f64,
+	/// Similarity to the kept copy, for duplicates
+	pub duplicate_similarity: Option<f64>,
+	/// Arousal slows this memory's significance decay
+	pub emotional_protection: bool,
+	/// Keyframe kept by `preserveKeyframes`
+	pub keyframe_protected: bool,
+	/// Scene-change frame kept by `preserveSceneChanges`
+	pub scene_change_protected: bool,
+	/// Pinned by the user
+	pub pinned: bool,
 }
 
 /// Retrieve visual memories based on probe embedding.
+///
+/// `valences` (-1 to 1, parallel to the memories) only matter when
+/// `config.valenceWeight` is set.
+///
+/// # Errors
+///
+/// Returns an error if `config.noiseParameter` isn't positive.
 #[napi]
 pub fn visual_retrieve(
 	probe_embedding: Vec<f64>,
@@ -1046,8 +1867,10 @@ pub fn visual_retrieve(
 	current_time_ms: f64,
 	associations: Option<Vec<JsAssociation>>,
 	config: Option<JsVisualRetrievalConfig>,
-) -> Vec<JsVisualRetrievalCandidate> {
-	let config = js_visual_retrieval_config_to_core(config);
+	valences: Option<Vec<f64>>,
+) -> napi::Result<Vec<JsVisualRetrievalCandidate>> {
+	let config = js_visual_retrieval_config_to_core(config)?;
+	let valences = valences.unwrap_or_default();
 
 	let associations: Vec<CoreAssociation> = associations
 		.unwrap_or_default()
@@ -1065,6 +1888,7 @@ pub fn visual_retrieve(
 		memory_embeddings: &memory_embeddings,
 		access_histories_ms: &access_histories_ms,
 		emotional_weights: &emotional_weights,
+		valences: &valences,
 		significance_scores: &significance_scores,
 		associations: &associations,
 		current_time_ms,
@@ -1072,7 +1896,7 @@ pub fn visual_retrieve(
 
 	let candidates = core_retrieve_visual(&input, &config);
 
-	candidates
+	Ok(candidates
 		.into_iter()
 		.map(|c| JsVisualRetrievalCandidate {
 			index: c.index as u32,
@@ -1083,11 +1907,230 @@ pub fn visual_retrieve(
 			significance_boost: c.significance_boost,
 			total_activation: c.total_activation,
 			probability: c.probability,
+			latency_ms: c.latency_ms,
 		})
-		.collect()
+		.collect())
+}
+
+/// Text memories for [`retrieve_multimodal`], as passed to `retrieve`.
+#[napi(object)]
+pub struct JsMultimodalTextInput {
+	/// Text memory embeddings
+	pub memory_embeddings: Vec<Vec<f64>>,
+	/// Access timestamps (ms) for each memory
+	pub access_histories_ms: Vec<Vec<f64>>,
+	/// Emotional weight for each memory (0-1); empty = 0.5 for all
+	pub emotional_weights: Vec<f64>,
+	/// Per-memory decay rates; empty = the config's decay rate
+	pub decay_rates: Vec<f64>,
+	/// Working memory boost for each memory (1.0-2.0); empty = no boost
+	pub working_memory_boosts: Vec<f64>,
+	/// Associations between text memories
+	pub associations: Option<Vec<JsAssociation>>,
+}
+
+/// Visual memories for [`retrieve_multimodal`], as passed to `visualRetrieve`.
+#[napi(object)]
+pub struct JsMultimodalVisualInput {
+	/// Visual memory embeddings
+	pub memory_embeddings: Vec<Vec<f64>>,
+	/// Access timestamps (ms) for each memory
+	pub access_histories_ms: Vec<Vec<f64>>,
+	/// Emotional weight (arousal) for each memory; empty = 0.5 for all
+	pub emotional_weights: Vec<f64>,
+	/// Significance score for each memory; empty = 0.5 for all
+	pub significance_scores: Vec<f64>,
+	/// Emotional valence (-1 to 1) for each memory; missing = neutral
+	pub valences: Option<Vec<f64>>,
+	/// Associations between visual memories
+	pub associations: Option<Vec<JsAssociation>>,
+}
+
+/// A candidate from [`retrieve_multimodal`].
+#[napi(object)]
+pub struct JsMultimodalCandidate {
+	/// "text" or "visual"
+	pub modality: String,
+	/// Memory index within its own store
+	pub index: u32,
+	/// Combined total activation
+	pub total_activation: f64,
+	/// Retrieval probability
+	pub probability: f64,
+	/// Estimated retrieval latency in milliseconds
+	pub latency_ms: f64,
+}
+
+/// Retrieve text and visual memories for one probe as a single list ranked
+/// by total activation.
+///
+/// Each store is scored with its own config; `maxResults` (default 10)
+/// applies to the merged list.
+///
+/// # Errors
+///
+/// Returns an error if the text or visual memory arrays have inconsistent
+/// lengths or either config's `noiseParameter` isn't positive.
+#[napi]
+pub fn retrieve_multimodal(
+	probe_embedding: Vec<f64>,
+	text: JsMultimodalTextInput,
+	visual: JsMultimodalVisualInput,
+	current_time_ms: f64,
+	text_config: Option<JsRetrievalConfig>,
+	visual_config: Option<JsVisualRetrievalConfig>,
+	max_results: Option<u32>,
+) -> napi::Result<Vec<JsMultimodalCandidate>> {
+	let text_associations = js_associations_to_core(text.associations);
+	let text_input = RetrievalInput {
+		probe_embedding: &probe_embedding,
+		memory_embeddings: &text.memory_embeddings,
+		access_histories_ms: &text.access_histories_ms,
+		emotional_weights: &text.emotional_weights,
+		decay_rates: &text.decay_rates,
+		working_memory_boosts: &text.working_memory_boosts,
+		associations: &text_associations,
+		current_time_ms,
+		reconsolidation: None,
+	};
+	text_input
+		.validate()
+		.map_err(|e| napi::Error::from_reason(e.to_string()))?;
+
+	let visual_associations = js_associations_to_core(visual.associations);
+	let valences = visual.valences.unwrap_or_default();
+	let visual_input = VisualRetrievalInput {
+		probe_embedding: &probe_embedding,
+		memory_embeddings: &visual.memory_embeddings,
+		access_histories_ms: &visual.access_histories_ms,
+		emotional_weights: &visual.emotional_weights,
+		valences: &valences,
+		significance_scores: &visual.significance_scores,
+		associations: &visual_associations,
+		current_time_ms,
+	};
+	visual_input
+		.validate()
+		.map_err(|e| napi::Error::from_reason(format!("visual input: {e}")))?;
+
+	let candidates = core_retrieve_multimodal(
+		&text_input,
+		&visual_input,
+		&js_retrieval_config_to_core(text_config)?,
+		&js_visual_retrieval_config_to_core(visual_config)?,
+		max_results.map_or(10, |max| max as usize),
+	);
+
+	Ok(candidates
+		.into_iter()
+		.map(|c| JsMultimodalCandidate {
+			modality: match c.modality {
+				Modality::Text => "text",
+				Modality::Visual => "visual",
+			}
+			.to_string(),
+			index: c.index as u32,
+			total_activation: c.total_activation,
+			probability: c.probability,
+			latency_ms: c.latency_ms,
+		})
+		.collect())
+}
+
+/// Per-memory arrays in the shape `retrieve` expects.
+#[napi(object)]
+pub struct JsOwnedRetrievalInput {
+	/// Memory embeddings, one per visual memory
+	pub memory_embeddings: Vec<Vec<f64>>,
+	/// Approximated access timestamps (ms) for each memory
+	pub access_histories_ms: Vec<Vec<f64>>,
+	/// Emotional weight for each memory, taken from its arousal
+	pub emotional_weights: Vec<f64>,
+	/// Current time in milliseconds
+	pub current_time_ms: f64,
+}
+
+/// Convert visual memories into `retrieve` inputs so they can be ranked
+/// together with text memories.
+///
+/// Access histories are approximated from `lastAccessedMs` and `accessCount`,
+/// and emotional weight is taken from arousal; see the core docs for details.
+///
+/// # Errors
+///
+/// Returns an error if `embeddings` doesn't have one entry per memory.
+#[napi]
+pub fn visual_memories_to_retrieval_input(
+	memories: Vec<JsVisualMemory>,
+	embeddings: Vec<Vec<f64>>,
+	current_time_ms: f64,
+) -> napi::Result<JsOwnedRetrievalInput> {
+	if memories.len() != embeddings.len() {
+		return Err(napi::Error::from_reason(format!(
+			"embeddings has {} entries, expected {}",
+			embeddings.len(),
+			memories.len()
+		)));
+	}
+
+	let memories: Vec<VisualMemory> = memories
+		.into_iter()
+		.zip(embeddings)
+		.map(|(memory, embedding)| js_visual_memory_to_core(memory, embedding))
+		.collect();
+	let owned = core_visual_memories_to_retrieval_input(&memories, current_time_ms);
+
+	Ok(JsOwnedRetrievalInput {
+		memory_embeddings: owned.memory_embeddings,
+		access_histories_ms: owned.access_histories_ms,
+		emotional_weights: owned.emotional_weights,
+		current_time_ms: owned.current_time_ms,
+	})
+}
+
+/// Find visual memories that are candidates for pruning, highest score first.
+///
+/// Each candidate carries the factors its score was computed from, so
+/// callers can show why a memory was flagged. `embeddings` is parallel to
+/// `memories` and is used to find near-duplicates.
+///
+/// # Errors
+///
+/// Returns an error if `embeddings` doesn't have one entry per memory.
+#[napi]
+pub fn visual_compute_pruning_candidates(
+	memories: Vec<JsVisualMemory>,
+	embeddings: Vec<Vec<f64>>,
+	current_time_ms: f64,
+	config: Option<JsVisualConfig>,
+) -> napi::Result<Vec<JsPruningCandidate>> {
+	if memories.len() != embeddings.len() {
+		return Err(napi::Error::from_reason(format!(
+			"embeddings has {} entries, expected {}",
+			embeddings.len(),
+			memories.len()
+		)));
+	}
+
+	let cfg = js_visual_config_to_core(config);
+	let memories: Vec<VisualMemory> = memories
+		.into_iter()
+		.zip(embeddings)
+		.map(|(memory, embedding)| js_visual_memory_to_core(memory, embedding))
+		.collect();
+
+	Ok(
+		core_compute_pruning_candidates(&memories, current_time_ms, &cfg)
+			.into_iter()
+			.map(pruning_candidate_to_js)
+			.collect(),
+	)
 }
 
 /// Check if a visual memory should be pruned.
+///
+/// Scene-change frames are kept when `isSceneChange` is set and
+/// `preserveSceneChanges` is on.
 #[napi]
 pub fn visual_should_prune(
 	significance: f64,
@@ -1095,17 +2138,111 @@ pub fn visual_should_prune(
 	is_pinned: bool,
 	is_keyframe: bool,
 	config: Option<JsVisualConfig>,
+	is_scene_change: Option<bool>,
 ) -> bool {
 	let cfg = js_visual_config_to_core(config);
-	core_should_prune(
+	core_should_prune_with_scene_change(
 		significance,
 		days_since_access,
 		is_pinned,
 		is_keyframe,
+		is_scene_change.unwrap_or(false),
 		&cfg,
 	)
 }
 
+/// Decay a visual memory's significance by the days since it was last
+/// accessed, with slower decay for emotionally arousing memories.
+#[napi]
+pub fn visual_compute_decayed_significance(
+	significance: f64,
+	days_since_access: f64,
+	emotional_arousal: f64,
+	config: Option<JsVisualConfig>,
+) -> f64 {
+	let cfg = js_visual_config_to_core(config);
+	core_compute_decayed_significance(significance, days_since_access, emotional_arousal, &cfg)
+}
+
+/// Length in ms of the consolidation window for a memory from `source`
+/// ("discord", "sms", "direct", "videoframe" or "other").
+#[napi]
+pub fn visual_consolidation_duration(source: String, config: Option<JsVisualConfig>) -> f64 {
+	let cfg = js_visual_config_to_core(config);
+	core_consolidation_duration_for(parse_visual_source(&source), &cfg)
+}
+
+/// Keyword-based guess at the valence and arousal of speech near a frame,
+/// to seed or blend with the vision model's estimate.
+#[napi]
+pub fn visual_infer_emotional_prior(text: String) -> JsEmotionalContext {
+	let emotion = core_infer_emotional_prior(&text);
+	JsEmotionalContext {
+		valence: emotion.valence,
+		arousal: emotion.arousal,
+	}
+}
+
+/// Advance a visual memory's consolidation state to `current_time_ms`.
+///
+/// `window_start_ms`/`window_end_ms` describe the consolidation window, if one
+/// is open. Passing a window for a "fresh" memory starts consolidation; with
+/// no window, a "fresh" memory given its `source` starts consolidating now
+/// over that source's window (see [`visual_consolidation_duration`]). Once the
+/// window has closed the memory becomes "consolidated" with strength 1.
+///
+/// Set `reactivated` when the memory has just been retrieved: a "consolidated"
+/// memory then moves to "reconsolidating" over the given window and its
+/// reactivation count goes up by one. Without it, a window passed for a
+/// "consolidated" memory is left over from an earlier pass and is ignored, so
+/// polling never reactivates.
+#[napi]
+pub fn visual_update_consolidation(
+	state: JsVisualConsolidationState,
+	window_start_ms: Option<f64>,
+	window_end_ms: Option<f64>,
+	current_time_ms: f64,
+	reactivated: Option<bool>,
+	source: Option<String>,
+	config: Option<JsVisualConfig>,
+) -> JsVisualConsolidationState {
+	let mut core_state = VisualConsolidationState {
+		state: parse_consolidation_state(&state.state),
+		window: None,
+		strength: state.strength,
+		reactivation_count: state.reactivation_count,
+	};
+
+	if let (Some(start), Some(end)) = (window_start_ms, window_end_ms) {
+		match core_state.state {
+			ConsolidationState::Fresh => core_state.start_consolidation(start, end - start),
+			ConsolidationState::Consolidated => {
+				if reactivated.unwrap_or(false) {
+					core_state.reactivate(start, end - start);
+				}
+			}
+			labile @ (ConsolidationState::Consolidating | ConsolidationState::Reconsolidating) => {
+				core_state.window = Some(ConsolidationWindow {
+					started_at_ms: start,
+					ends_at_ms: end,
+					state: labile,
+				});
+			}
+		}
+	} else if let (ConsolidationState::Fresh, Some(source)) = (core_state.state, source) {
+		let cfg = js_visual_config_to_core(config);
+		core_state.start_consolidation_for(current_time_ms, parse_visual_source(&source), &cfg);
+	}
+
+	core_state.update(current_time_ms);
+
+	JsVisualConsolidationState {
+		state: format!("{:?}", core_state.state).to_lowercase(),
+		strength: core_state.strength,
+		reactivation_count: core_state.reactivation_count,
+	}
+}
+
 // ============================================================================
 // Video Frame Selection
 // ============================================================================
@@ -1150,6 +2287,20 @@ pub struct JsFrameDescriptionConfig {
 	pub max_description_length: Option<u32>,
 }
 
+/// Configuration for frame selection.
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsFrameSelectionConfig {
+	/// Always include the first and last frame (default: true)
+	pub force_endpoints: Option<bool>,
+	/// Score bonus for keyframes (default: 0.3)
+	pub keyframe_bonus: Option<f64>,
+	/// Score bonus for scene changes (default: 0.5)
+	pub scene_change_bonus: Option<f64>,
+	/// Score bonus for frames inside a transcript segment (default: 0.2)
+	pub transcript_bonus: Option<f64>,
+}
+
 /// Select frames for description, respecting rate limits.
 ///
 /// Prioritizes: keyframes, scene changes, even distribution, transcript moments.
@@ -1162,8 +2313,22 @@ pub fn video_select_frames(
 	frames: Vec<JsFrameCandidate>,
 	max_frames: u32,
 	transcript_segments: Option<Vec<JsTranscriptSegment>>,
+	config: Option<JsFrameSelectionConfig>,
 ) -> Vec<u32> {
-	use lucid_core::visual::{select_frames_for_description, FrameCandidate, TranscriptSegment};
+	use lucid_core::visual::{
+		select_frames_for_description_with_config, FrameCandidate, FrameSelectionConfig,
+		TranscriptSegment,
+	};
+
+	let core_config = config.map_or_else(FrameSelectionConfig::default, |c| {
+		let default = FrameSelectionConfig::default();
+		FrameSelectionConfig {
+			force_endpoints: c.force_endpoints.unwrap_or(default.force_endpoints),
+			keyframe_bonus: c.keyframe_bonus.unwrap_or(default.keyframe_bonus),
+			scene_change_bonus: c.scene_change_bonus.unwrap_or(default.scene_change_bonus),
+			transcript_bonus: c.transcript_bonus.unwrap_or(default.transcript_bonus),
+		}
+	});
 
 	let core_frames: Vec<FrameCandidate> = frames
 		.into_iter()
@@ -1186,8 +2351,12 @@ pub fn video_select_frames(
 			.collect()
 	});
 
-	let result =
-		select_frames_for_description(&core_frames, max_frames as usize, core_segments.as_deref());
+	let result = select_frames_for_description_with_config(
+		&core_frames,
+		max_frames as usize,
+		core_segments.as_deref(),
+		&core_config,
+	);
 
 	result.into_iter().map(|i| i as u32).collect()
 }
@@ -1266,6 +2435,108 @@ pub fn video_prepare_synthesis_prompt(
 // Type Conversions
 // ============================================================================
 
+/// Convert a JS retrieval config, rejecting a noise parameter that isn't
+/// positive.
+fn js_retrieval_config_to_core(js: Option<JsRetrievalConfig>) -> napi::Result<CoreConfig> {
+	let config = js.map_or_else(CoreConfig::default, |js| {
+		let default = CoreConfig::default();
+		CoreConfig {
+			decay_rate: js.decay_rate.unwrap_or(default.decay_rate),
+			activation_threshold: js
+				.activation_threshold
+				.unwrap_or(default.activation_threshold),
+			noise_parameter: js.noise_parameter.unwrap_or(default.noise_parameter),
+			spreading_depth: js
+				.spreading_depth
+				.map_or(default.spreading_depth, |d| d as usize),
+			spreading_decay: js.spreading_decay.unwrap_or(default.spreading_decay),
+			min_probability: js.min_probability.unwrap_or(default.min_probability),
+			max_results: js.max_results.map_or(default.max_results, |m| m as usize),
+			offset: js.offset.map_or(default.offset, |o| o as usize),
+			bidirectional: js.bidirectional.unwrap_or(default.bidirectional),
+			backward_decay: js.backward_decay.unwrap_or(default.backward_decay),
+			seed_mode: js
+				.seed_mode
+				.as_deref()
+				.map_or(default.seed_mode, |s| parse_seed_mode(s, default.seed_mode)),
+			max_spreading_seeds: js
+				.max_spreading_seeds
+				.map_or(default.max_spreading_seeds, |m| m as usize),
+			seed_activation_floor: js.seed_activation_floor,
+			spreading_normalization: js
+				.spreading_normalization
+				.as_deref()
+				.map_or(default.spreading_normalization, |s| {
+					parse_spreading_normalization(s, js.spreading_temperature.unwrap_or(1.0))
+				}),
+			max_history: js
+				.max_history
+				.map_or(default.max_history, |m| Some(m as usize)),
+			probability_temperature: js
+				.probability_temperature
+				.unwrap_or(default.probability_temperature),
+			similarity_metric: js
+				.similarity_metric
+				.as_deref()
+				.map_or(default.similarity_metric, parse_similarity_metric),
+			embeddings_prenormalized: js
+				.embeddings_prenormalized
+				.unwrap_or(default.embeddings_prenormalized),
+			combine_mode: js
+				.combine_mode
+				.as_deref()
+				.map_or(default.combine_mode, parse_combine_mode),
+			emotional_multiplier_scale: js
+				.emotional_multiplier_scale
+				.unwrap_or(default.emotional_multiplier_scale),
+			cold_start_boost: js.cold_start_boost.unwrap_or(default.cold_start_boost),
+			latency_factor: js.latency_factor.unwrap_or(default.latency_factor),
+			normalize_activations: js
+				.normalize_activations
+				.unwrap_or(default.normalize_activations),
+			strict_dimensions: js.strict_dimensions.unwrap_or(default.strict_dimensions),
+			wm_cap: js.wm_cap.unwrap_or(default.wm_cap),
+		}
+	});
+	check_noise_parameter(config.noise_parameter)?;
+	Ok(config)
+}
+
+fn js_associations_to_core(js: Option<Vec<JsAssociation>>) -> Vec<CoreAssociation> {
+	js.unwrap_or_default()
+		.into_iter()
+		.map(|a| CoreAssociation {
+			source: a.source as usize,
+			target: a.target as usize,
+			forward_strength: a.forward_strength,
+			backward_strength: a.backward_strength,
+		})
+		.collect()
+}
+
+const fn association_to_js(a: CoreAssociation) -> JsAssociation {
+	JsAssociation {
+		source: a.source as u32,
+		target: a.target as u32,
+		forward_strength: a.forward_strength,
+		backward_strength: a.backward_strength,
+	}
+}
+
+const fn candidate_to_js(c: RetrievalCandidate) -> JsRetrievalCandidate {
+	JsRetrievalCandidate {
+		index: c.index as u32,
+		base_level: c.base_level,
+		probe_activation: c.probe_activation,
+		spreading: c.spreading,
+		emotional_weight: c.emotional_weight,
+		total_activation: c.total_activation,
+		probability: c.probability,
+		latency_ms: c.latency_ms,
+		normalized_activation: c.normalized_activation,
+	}
+}
+
 fn js_visual_config_to_core(js: Option<JsVisualConfig>) -> VisualConfig {
 	js.map_or_else(VisualConfig::default, |js| {
 		let default = VisualConfig::default();
@@ -1287,14 +2558,76 @@ fn js_visual_config_to_core(js: Option<JsVisualConfig>) -> VisualConfig {
 			pruning_threshold: js.pruning_threshold.unwrap_or(default.pruning_threshold),
 			pruning_stale_days: js.pruning_stale_days.unwrap_or(default.pruning_stale_days),
 			preserve_keyframes: js.preserve_keyframes.unwrap_or(default.preserve_keyframes),
+			preserve_scene_changes: js
+				.preserve_scene_changes
+				.unwrap_or(default.preserve_scene_changes),
+			duplicate_similarity_threshold: js
+				.duplicate_similarity_threshold
+				.unwrap_or(default.duplicate_similarity_threshold),
+			discord_consolidation_ms: js
+				.discord_consolidation_ms
+				.unwrap_or(default.discord_consolidation_ms),
+			sms_consolidation_ms: js
+				.sms_consolidation_ms
+				.unwrap_or(default.sms_consolidation_ms),
+			direct_consolidation_ms: js
+				.direct_consolidation_ms
+				.unwrap_or(default.direct_consolidation_ms),
+			video_frame_consolidation_ms: js
+				.video_frame_consolidation_ms
+				.unwrap_or(default.video_frame_consolidation_ms),
+			other_consolidation_ms: js
+				.other_consolidation_ms
+				.unwrap_or(default.other_consolidation_ms),
 		}
 	})
 }
 
+fn js_visual_memory_to_core(js: JsVisualMemory, embedding: Vec<f64>) -> VisualMemory {
+	VisualMemory {
+		id: js.id,
+		description: js.description,
+		detailed_description: js.detailed_description,
+		embedding,
+		captured_at_ms: js.captured_at_ms,
+		last_accessed_ms: js.last_accessed_ms,
+		access_count: js.access_count,
+		emotional_context: EmotionalContext::new(js.emotional_valence, js.emotional_arousal),
+		significance: js.significance,
+		source: parse_visual_source(&js.source),
+		shared_by: js.shared_by,
+		video_id: js.video_id,
+		frame_number: js.frame_number,
+		is_scene_change: js.is_scene_change.unwrap_or(false),
+		objects: js.objects,
+		tags: js.tags,
+		is_pinned: js.is_pinned,
+	}
+}
+
+fn pruning_candidate_to_js(c: PruningCandidate) -> JsPruningCandidate {
+	JsPruningCandidate {
+		index: c.index as u32,
+		significance: c.significance,
+		days_since_access: c.days_since_access,
+		reason: format!("{:?}", c.reason).to_lowercase(),
+		score: c.score,
+		breakdown: JsPruningBreakdown {
+			staleness_component: c.breakdown.staleness_component,
+			significance_component: c.breakdown.significance_component,
+			duplicate_similarity: c.breakdown.duplicate_similarity,
+			emotional_protection: c.breakdown.emotional_protection,
+			keyframe_protected: c.breakdown.keyframe_protected,
+			scene_change_protected: c.breakdown.scene_change_protected,
+			pinned: c.breakdown.pinned,
+		},
+	}
+}
+
 fn js_visual_retrieval_config_to_core(
 	js: Option<JsVisualRetrievalConfig>,
-) -> VisualRetrievalConfig {
-	js.map_or_else(VisualRetrievalConfig::default, |js| {
+) -> napi::Result<VisualRetrievalConfig> {
+	let config = js.map_or_else(VisualRetrievalConfig::default, |js| {
 		let default = VisualRetrievalConfig::default();
 		VisualRetrievalConfig {
 			decay_rate: js.decay_rate.unwrap_or(default.decay_rate),
@@ -1307,10 +2640,35 @@ fn js_visual_retrieval_config_to_core(
 			min_probability: js.min_probability.unwrap_or(default.min_probability),
 			max_results: js.max_results.unwrap_or(default.max_results as u32) as usize,
 			bidirectional: js.bidirectional.unwrap_or(default.bidirectional),
+			backward_decay: js.backward_decay.unwrap_or(default.backward_decay),
 			emotional_boost: js.emotional_boost.unwrap_or(default.emotional_boost),
+			valence_weight: js.valence_weight.unwrap_or(default.valence_weight),
 			significance_boost: js.significance_boost.unwrap_or(default.significance_boost),
+			seed_mode: js
+				.seed_mode
+				.as_deref()
+				.map_or(default.seed_mode, |s| parse_seed_mode(s, default.seed_mode)),
+			max_spreading_seeds: js
+				.max_spreading_seeds
+				.map_or(default.max_spreading_seeds, |m| m as usize),
+			seed_activation_floor: js.seed_activation_floor,
+			latency_factor: js.latency_factor.unwrap_or(default.latency_factor),
 		}
-	})
+	});
+	check_noise_parameter(config.noise_parameter)?;
+	Ok(config)
+}
+
+/// A zero or negative noise parameter is almost always an unset config
+/// field; core floors it, but callers should hear about it.
+fn check_noise_parameter(noise: f64) -> napi::Result<()> {
+	if noise > 0.0 {
+		Ok(())
+	} else {
+		Err(napi::Error::from_reason(format!(
+			"noiseParameter must be positive, got {noise}"
+		)))
+	}
 }
 
 fn js_config_to_core(js: Option<JsLocationConfig>) -> LocationConfig {
@@ -1343,6 +2701,9 @@ fn js_config_to_core(js: Option<JsLocationConfig>) -> LocationConfig {
 			backward_strength_factor: js
 				.backward_strength_factor
 				.unwrap_or(default.backward_strength_factor),
+			pinned_decay_multiplier: js
+				.pinned_decay_multiplier
+				.unwrap_or(default.pinned_decay_multiplier),
 		}
 	})
 }
@@ -1412,6 +2773,7 @@ fn js_assoc_decay_config_to_core(
 					.unwrap_or(default.tau_reconsolidating_days),
 				reinforcement_boost: c.reinforcement_boost.unwrap_or(default.reinforcement_boost),
 				prune_threshold: c.prune_threshold.unwrap_or(default.prune_threshold),
+				initial_strength: c.initial_strength.unwrap_or(default.initial_strength),
 			}
 		},
 	)
@@ -1437,6 +2799,70 @@ fn js_recon_config_to_core(
 	)
 }
 
+/// Parse a seed mode, falling back to the config's own `default` (which
+/// differs between the text and visual paths) on invalid input.
+fn parse_seed_mode(s: &str, default: SpreadingSeedMode) -> SpreadingSeedMode {
+	match s.to_lowercase().as_str() {
+		"additive" => SpreadingSeedMode::Additive,
+		"multiplicative" => SpreadingSeedMode::Multiplicative,
+		_ => default,
+	}
+}
+
+fn parse_multi_probe_mode(s: &str) -> MultiProbeMode {
+	match s.to_lowercase().as_str() {
+		"mean" => MultiProbeMode::WeightedMean,
+		// "max" and any invalid input defaults to WeightedMax
+		_ => MultiProbeMode::WeightedMax,
+	}
+}
+
+fn parse_visual_source(s: &str) -> VisualSource {
+	match s.to_lowercase().as_str() {
+		"discord" => VisualSource::Discord,
+		"sms" => VisualSource::Sms,
+		"direct" => VisualSource::Direct,
+		"videoframe" => VisualSource::VideoFrame,
+		// "other" and any invalid input defaults to Other
+		_ => VisualSource::Other,
+	}
+}
+
+fn parse_combine_mode(s: &str) -> CombineMode {
+	match s.to_lowercase().as_str() {
+		"additive" => CombineMode::Additive,
+		// "multiplicative" and any invalid input defaults to Multiplicative
+		_ => CombineMode::Multiplicative,
+	}
+}
+
+fn parse_similarity_metric(s: &str) -> SimilarityMetric {
+	match s.to_lowercase().as_str() {
+		"dot" | "dotproduct" => SimilarityMetric::DotProduct,
+		"euclidean" => SimilarityMetric::Euclidean,
+		"manhattan" | "l1" => SimilarityMetric::Manhattan,
+		// "cosine" and any invalid input defaults to Cosine
+		_ => SimilarityMetric::Cosine,
+	}
+}
+
+fn parse_spreading_normalization(s: &str, temperature: f64) -> SpreadingNormalization {
+	match s.to_lowercase().as_str() {
+		"max" => SpreadingNormalization::MaxNormalize,
+		"softmax" => SpreadingNormalization::SoftmaxTemperature(temperature),
+		// "none" and any invalid input defaults to None
+		_ => SpreadingNormalization::None,
+	}
+}
+
+fn parse_association_merge_mode(s: &str) -> AssociationMergeMode {
+	match s.to_lowercase().as_str() {
+		"sum" => AssociationMergeMode::Sum,
+		// "max" and any invalid input defaults to Max
+		_ => AssociationMergeMode::Max,
+	}
+}
+
 fn parse_association_state(s: &str) -> lucid_core::activation::AssociationState {
 	match s.to_lowercase().as_str() {
 		"consolidating" => lucid_core::activation::AssociationState::Consolidating,
@@ -1447,6 +2873,16 @@ fn parse_association_state(s: &str) -> lucid_core::activation::AssociationState
 	}
 }
 
+fn parse_consolidation_state(s: &str) -> ConsolidationState {
+	match s.to_lowercase().as_str() {
+		"consolidating" => ConsolidationState::Consolidating,
+		"consolidated" => ConsolidationState::Consolidated,
+		"reconsolidating" => ConsolidationState::Reconsolidating,
+		// "fresh" and any invalid input defaults to Fresh
+		_ => ConsolidationState::Fresh,
+	}
+}
+
 fn js_temporal_config_to_core(
 	js: Option<JsTemporalSpreadingConfig>,
 ) -> lucid_core::spreading::TemporalSpreadingConfig {
@@ -1469,6 +2905,35 @@ fn js_temporal_config_to_core(
 	)
 }
 
+fn temporal_result_to_js(
+	result: lucid_core::spreading::TemporalSpreadingResult,
+) -> JsTemporalSpreadingResult {
+	JsTemporalSpreadingResult {
+		activations: result.activations,
+		forward_activated: result
+			.forward_activated
+			.into_iter()
+			.map(|i| i as u32)
+			.collect(),
+		backward_activated: result
+			.backward_activated
+			.into_iter()
+			.map(|i| i as u32)
+			.collect(),
+	}
+}
+
+const fn temporal_link_to_js(link: lucid_core::spreading::TemporalLink) -> JsTemporalLink {
+	JsTemporalLink {
+		source_position: link.source_position as u32,
+		target_position: link.target_position as u32,
+		source_memory: link.source_memory as u32,
+		target_memory: link.target_memory as u32,
+		forward_strength: link.forward_strength,
+		backward_strength: link.backward_strength,
+	}
+}
+
 const fn js_temporal_link_to_core(js: JsTemporalLink) -> lucid_core::spreading::TemporalLink {
 	lucid_core::spreading::TemporalLink {
 		source_position: js.source_position as usize,
@@ -1481,7 +2946,7 @@ const fn js_temporal_link_to_core(js: JsTemporalLink) -> lucid_core::spreading::
 }
 
 #[cfg(test)]
-#[allow(clippy::float_cmp, clippy::suboptimal_flops)]
+#[allow(clippy::float_cmp, clippy::suboptimal_flops, clippy::expect_used)]
 mod tests {
 	use super::*;
 
@@ -1492,6 +2957,16 @@ mod tests {
 		assert!((cosine_similarity(a, b) - 1.0).abs() < 1e-10);
 	}
 
+	#[test]
+	fn test_record_access() {
+		let history = vec![1000.0, 2000.0, 3000.0];
+		assert_eq!(
+			record_access(history.clone(), 4000.0, Some(2)),
+			vec![3000.0, 4000.0]
+		);
+		assert_eq!(record_access(history, 4000.0, None).len(), 4);
+	}
+
 	#[test]
 	fn test_retrieve_basic() {
 		let probe = vec![1.0, 0.0, 0.0];
@@ -1515,16 +2990,265 @@ mod tests {
 				spreading_depth: None,
 				spreading_decay: None,
 				max_results: None,
+				offset: None,
 				bidirectional: None,
+				backward_decay: None,
+				seed_mode: None,
+				max_spreading_seeds: None,
+				seed_activation_floor: None,
+				spreading_normalization: None,
+				spreading_temperature: None,
+				max_history: None,
+				probability_temperature: None,
+				similarity_metric: None,
+				embeddings_prenormalized: None,
+				combine_mode: None,
+				emotional_multiplier_scale: None,
+				cold_start_boost: None,
+				latency_factor: None,
+				normalize_activations: None,
+				strict_dimensions: None,
+				wm_cap: None,
 			}),
-		);
+		)
+		.unwrap_or_default();
 
 		assert!(!results.is_empty());
 		assert_eq!(results[0].index, 0);
 	}
 
+	#[test]
+	fn test_zero_noise_parameter_is_rejected() {
+		let result = retrieve(
+			vec![1.0, 0.0],
+			vec![vec![1.0, 0.0]],
+			vec![vec![0.0]],
+			vec![],
+			vec![],
+			vec![],
+			1000.0,
+			None,
+			Some(JsRetrievalConfig {
+				noise_parameter: Some(0.0),
+				..Default::default()
+			}),
+		);
+		assert!(result.is_err_and(|e| e.reason.contains("noiseParameter")));
+	}
+
+	#[test]
+	fn test_retrieve_with_diagnostics_mixed_dimensions() {
+		let now = 1_000_000.0;
+
+		let result = retrieve_with_diagnostics(
+			vec![1.0, 0.0, 0.0],
+			vec![vec![1.0, 0.0, 0.0], vec![1.0, 0.0]],
+			vec![vec![now], vec![now]],
+			vec![0.5, 0.5],
+			vec![0.5, 0.5],
+			vec![1.0, 1.0],
+			now,
+			None,
+			None,
+		);
+
+		let Ok(result) = result else {
+			unreachable!("consistent input should not error");
+		};
+		assert_eq!(result.diagnostics.embedding_dimension, 3);
+		assert_eq!(result.diagnostics.skipped_mismatched, 1);
+		assert!(result.candidates.iter().all(|c| c.index == 0));
+	}
+
+	#[test]
+	fn test_retrieve_rejects_ragged_input() {
+		let now = 1_000_000.0;
+
+		let result = retrieve(
+			vec![1.0, 0.0],
+			vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+			vec![vec![now], vec![now]],
+			vec![0.5],
+			vec![],
+			vec![],
+			now,
+			None,
+			None,
+		);
+
+		let reason = result.err().map(|e| e.reason).unwrap_or_default();
+		assert!(reason.contains("emotional_weights"));
+	}
+
+	#[test]
+	fn test_unknown_seed_mode_keeps_config_default() {
+		for default in [
+			SpreadingSeedMode::Additive,
+			SpreadingSeedMode::Multiplicative,
+		] {
+			assert_eq!(parse_seed_mode("addtive", default), default);
+		}
+		assert_eq!(
+			parse_seed_mode("Additive", SpreadingSeedMode::Multiplicative),
+			SpreadingSeedMode::Additive
+		);
+		assert_eq!(
+			parse_seed_mode("multiplicative", SpreadingSeedMode::Additive),
+			SpreadingSeedMode::Multiplicative
+		);
+
+		let typo = JsRetrievalConfig {
+			seed_mode: Some("addtive".to_string()),
+			..JsRetrievalConfig::default()
+		};
+		let text = js_retrieval_config_to_core(Some(typo)).expect("valid config");
+		assert_eq!(text.seed_mode, CoreConfig::default().seed_mode);
+	}
+
+	#[test]
+	fn test_retrieve_multi_probe() {
+		let now = 1_000_000.0;
+		let config = JsRetrievalConfig {
+			min_probability: Some(0.0),
+			..JsRetrievalConfig::default()
+		};
+
+		let candidates = retrieve_multi_probe(
+			vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+			None,
+			Some("max".to_string()),
+			vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.6, 0.8]],
+			vec![vec![now]; 3],
+			vec![],
+			vec![],
+			vec![],
+			now,
+			None,
+			Some(config),
+		)
+		.expect("consistent probes");
+		let mut top_two: Vec<u32> = candidates.iter().take(2).map(|c| c.index).collect();
+		top_two.sort_unstable();
+		assert_eq!(top_two, vec![0, 1]);
+
+		let mismatched = retrieve_multi_probe(
+			vec![vec![1.0, 0.0]],
+			Some(vec![1.0, 2.0]),
+			None,
+			vec![vec![1.0, 0.0]],
+			vec![vec![now]],
+			vec![],
+			vec![],
+			vec![],
+			now,
+			None,
+			None,
+		);
+		let reason = mismatched.err().map(|e| e.reason).unwrap_or_default();
+		assert!(reason.contains("probeWeights"));
+	}
+
+	#[test]
+	fn test_retrieve_strict_dimensions() {
+		let now = 1_000_000.0;
+		let config = JsRetrievalConfig {
+			strict_dimensions: Some(true),
+			..JsRetrievalConfig::default()
+		};
+
+		let result = retrieve(
+			vec![1.0, 0.0],
+			vec![vec![1.0, 0.0], vec![1.0, 0.0, 0.0]],
+			vec![vec![now], vec![now]],
+			vec![],
+			vec![],
+			vec![],
+			now,
+			None,
+			Some(config),
+		);
+
+		let reason = result.err().map(|e| e.reason).unwrap_or_default();
+		assert!(reason.contains("[1]"));
+	}
+
 	// Location Intuitions tests
 
+	#[test]
+	fn test_retrieve_by_similarity_threshold() {
+		let memories = vec![vec![1.0, 1.0], vec![0.0, 1.0], vec![1.0, 0.0]];
+
+		let matches = retrieve_by_similarity_threshold(vec![1.0, 0.0], memories, 0.5, None);
+		let indices: Vec<u32> = matches.iter().map(|m| m.index).collect();
+		assert_eq!(indices, vec![2, 0]);
+		assert!((matches[0].similarity - 1.0).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_compute_surprise_batch() {
+		let expected = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let actual = vec![vec![1.0, 0.0], vec![1.0, 0.0]];
+
+		let batch = compute_surprise_batch(
+			expected.clone(),
+			actual.clone(),
+			vec![1.0, 10.0],
+			vec![0.5, 0.5],
+			0.5,
+		)
+		.expect("matching lengths");
+		assert_eq!(batch.len(), 2);
+		assert!(
+			(batch[1] - compute_surprise(expected[1].clone(), actual[1].clone(), 10.0, 0.5, 0.5))
+				.abs() < f64::EPSILON
+		);
+
+		assert!(compute_surprise_batch(expected, actual, vec![1.0], vec![0.5, 0.5], 0.5).is_err());
+	}
+
+	#[test]
+	fn test_compute_pagerank() {
+		let associations: Vec<JsAssociation> = [(0, 1), (1, 2), (2, 0)]
+			.into_iter()
+			.map(|(source, target)| JsAssociation {
+				source,
+				target,
+				forward_strength: 1.0,
+				backward_strength: 1.0,
+			})
+			.collect();
+
+		let ranks = compute_pagerank(3, associations.clone(), 0.85, 0).expect("valid damping");
+		assert_eq!(ranks.len(), 3);
+		let avg = ranks.iter().sum::<f64>() / 3.0;
+		for r in &ranks {
+			assert!((r - avg).abs() < 0.01);
+		}
+
+		assert!(compute_pagerank(3, associations.clone(), 0.0, 100).is_err());
+		assert!(compute_pagerank(3, associations, 1.0, 100).is_err());
+	}
+
+	#[test]
+	fn test_find_activation_path() {
+		let associations: Vec<JsAssociation> = [(0, 1), (1, 2), (2, 3)]
+			.into_iter()
+			.map(|(source, target)| JsAssociation {
+				source,
+				target,
+				forward_strength: 1.0,
+				backward_strength: 0.0,
+			})
+			.collect();
+
+		assert_eq!(
+			find_activation_path(4, associations.clone(), 0, 3),
+			vec![0, 1, 2, 3]
+		);
+		assert!(find_activation_path(4, associations.clone(), 3, 0).is_empty());
+		assert!(find_activation_path(4, associations, 0, 9).is_empty());
+	}
+
 	#[test]
 	fn test_location_familiarity() {
 		// First access
@@ -1549,6 +3273,7 @@ mod tests {
 			time_same_activity_multiplier: None,
 			time_diff_activity_multiplier: None,
 			backward_strength_factor: None,
+			pinned_decay_multiplier: None,
 		};
 		// f(10) with k=0.2 = 1 - 1/(1 + 2) = 0.667 > 0.5
 		let f10_fast = location_compute_familiarity(10, Some(config));
@@ -1558,21 +3283,37 @@ mod tests {
 	#[test]
 	fn test_location_infer_activity() {
 		// Keyword inference
-		let result = location_infer_activity("debugging the issue".to_string(), None, None);
+		let result = location_infer_activity("debugging the issue".to_string(), None, None, None);
 		assert_eq!(result.activity_type, "debugging");
 		assert_eq!(result.source, "keyword");
 
 		// Tool inference
-		let result =
-			location_infer_activity("opening file".to_string(), Some("Read".to_string()), None);
+		let result = location_infer_activity(
+			"opening file".to_string(),
+			Some("Read".to_string()),
+			None,
+			None,
+		);
 		assert_eq!(result.activity_type, "reading");
 		assert_eq!(result.source, "tool");
 
+		// Custom keywords beat the tool
+		let custom = HashMap::from([("writing".to_string(), vec!["provision".to_string()])]);
+		let result = location_infer_activity(
+			"provision the cluster".to_string(),
+			Some("Read".to_string()),
+			None,
+			Some(custom),
+		);
+		assert_eq!(result.activity_type, "writing");
+		assert_eq!(result.source, "keyword");
+
 		// Explicit override
 		let result = location_infer_activity(
 			"reading code".to_string(),
 			Some("Read".to_string()),
 			Some("debugging".to_string()),
+			None,
 		);
 		assert_eq!(result.activity_type, "debugging");
 		assert_eq!(result.source, "explicit");
@@ -1591,6 +3332,13 @@ mod tests {
 		assert!(time_same > time_diff);
 	}
 
+	#[test]
+	fn test_location_increment_familiarity() {
+		let before = location_compute_familiarity(10, None);
+		let after = location_increment_familiarity(before, None);
+		assert!((after - location_compute_familiarity(11, None)).abs() < 1e-9);
+	}
+
 	#[test]
 	fn test_location_is_well_known() {
 		assert!(!location_is_well_known(0.5, None));
@@ -1629,4 +3377,323 @@ mod tests {
 		assert_eq!(results[1].location_id, 1);
 		assert_eq!(results[2].location_id, 3);
 	}
+
+	#[test]
+	fn test_merge_associations() {
+		let edge = |forward_strength: f64| JsAssociation {
+			source: 0,
+			target: 1,
+			forward_strength,
+			backward_strength: 0.1,
+		};
+
+		let merged = merge_associations(vec![edge(0.2), edge(0.7), edge(0.4)], None);
+		assert_eq!(merged.len(), 1);
+		assert!((merged[0].forward_strength - 0.7).abs() < 1e-12);
+
+		let summed = merge_associations(
+			vec![edge(0.2), edge(0.7), edge(0.4)],
+			Some("sum".to_string()),
+		);
+		assert_eq!(summed.len(), 1);
+		assert!((summed[0].forward_strength - 1.3).abs() < 1e-12);
+		assert!((summed[0].backward_strength - 0.3).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_compact_graph() {
+		let edge = |source: u32, target: u32| JsAssociation {
+			source,
+			target,
+			forward_strength: 0.5,
+			backward_strength: 0.5,
+		};
+
+		let graph = compact_graph(
+			3,
+			vec![edge(0, 1), edge(1, 2), edge(0, 2)],
+			vec![true, false, true],
+		);
+		let Ok(graph) = graph else {
+			unreachable!("a full-length mask should not error");
+		};
+		assert_eq!(graph.num_nodes, 2);
+		assert_eq!(graph.index_map, vec![Some(0), None, Some(1)]);
+		assert_eq!(graph.associations.len(), 1);
+		assert_eq!(
+			(graph.associations[0].source, graph.associations[0].target),
+			(0, 1)
+		);
+
+		assert!(compact_graph(3, vec![], vec![true]).is_err());
+	}
+
+	#[test]
+	fn test_retrieve_multimodal() {
+		let now = 1_000_000.0;
+		let text = JsMultimodalTextInput {
+			memory_embeddings: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+			access_histories_ms: vec![vec![now - 1000.0]; 2],
+			emotional_weights: vec![],
+			decay_rates: vec![],
+			working_memory_boosts: vec![],
+			associations: None,
+		};
+		let visual = JsMultimodalVisualInput {
+			memory_embeddings: vec![vec![0.9, 0.44]],
+			access_histories_ms: vec![vec![now - 1000.0]],
+			emotional_weights: vec![0.5],
+			significance_scores: vec![0.5],
+			valences: None,
+			associations: None,
+		};
+
+		let Ok(candidates) =
+			retrieve_multimodal(vec![1.0, 0.0], text, visual, now, None, None, Some(2))
+		else {
+			unreachable!("valid input was rejected");
+		};
+		assert_eq!(candidates.len(), 2);
+		assert_eq!(candidates[0].modality, "text");
+		assert_eq!(candidates[0].index, 0);
+		assert_eq!(candidates[1].modality, "visual");
+	}
+
+	#[test]
+	fn test_retrieve_multimodal_rejects_ragged_visual_input() {
+		let now = 1_000_000.0;
+		let text = JsMultimodalTextInput {
+			memory_embeddings: vec![vec![1.0, 0.0]],
+			access_histories_ms: vec![vec![now - 1000.0]],
+			emotional_weights: vec![],
+			decay_rates: vec![],
+			working_memory_boosts: vec![],
+			associations: None,
+		};
+		let visual = JsMultimodalVisualInput {
+			memory_embeddings: vec![vec![0.9, 0.44], vec![0.0, 1.0]],
+			access_histories_ms: vec![vec![now - 1000.0]],
+			emotional_weights: vec![],
+			significance_scores: vec![],
+			valences: None,
+			associations: None,
+		};
+
+		let result = retrieve_multimodal(vec![1.0, 0.0], text, visual, now, None, None, None);
+		let reason = result.err().map(|e| e.reason).unwrap_or_default();
+		assert!(reason.starts_with("visual input:"));
+		assert!(reason.contains("access_histories_ms"));
+	}
+
+	#[test]
+	fn test_visual_compute_pruning_candidates_breakdown() {
+		let day_ms = 86_400_000.0;
+		let now = 400.0 * day_ms;
+		let memory = |id, last_accessed_ms, significance| JsVisualMemory {
+			id,
+			description: String::new(),
+			detailed_description: None,
+			captured_at_ms: 0.0,
+			last_accessed_ms,
+			access_count: 1,
+			emotional_valence: 0.0,
+			emotional_arousal: 0.2,
+			significance,
+			source: "direct".to_string(),
+			shared_by: None,
+			video_id: None,
+			frame_number: None,
+			is_scene_change: None,
+			objects: Vec::new(),
+			tags: Vec::new(),
+			is_pinned: false,
+		};
+		let memories = vec![memory(0, now - 180.0 * day_ms, 0.4), memory(1, now, 0.9)];
+		let embeddings = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+
+		let Ok(candidates) =
+			visual_compute_pruning_candidates(memories.clone(), embeddings, now, None)
+		else {
+			unreachable!("inputs are parallel")
+		};
+		assert_eq!(candidates.len(), 1);
+		let stale = &candidates[0];
+		assert_eq!(stale.index, 0);
+		assert_eq!(stale.reason, "stale");
+		assert!((stale.breakdown.staleness_component - 2.0).abs() < 1e-9);
+		assert!((stale.breakdown.significance_component - 0.6).abs() < 1e-9);
+		assert!(stale.breakdown.duplicate_similarity.is_none());
+		assert!((stale.score - 1.2).abs() < 1e-9);
+
+		assert!(visual_compute_pruning_candidates(memories, vec![], now, None).is_err());
+	}
+
+	#[test]
+	fn test_visual_update_consolidation_crosses_window() {
+		let consolidating = |strength| JsVisualConsolidationState {
+			state: "consolidating".to_string(),
+			strength,
+			reactivation_count: 0,
+		};
+
+		let mid = visual_update_consolidation(
+			consolidating(0.0),
+			Some(0.0),
+			Some(1000.0),
+			500.0,
+			None,
+			None,
+			None,
+		);
+		assert_eq!(mid.state, "consolidating");
+		assert!((mid.strength - 0.5).abs() < 0.001);
+
+		let done = visual_update_consolidation(
+			consolidating(mid.strength),
+			Some(0.0),
+			Some(1000.0),
+			1000.0,
+			None,
+			None,
+			None,
+		);
+		assert_eq!(done.state, "consolidated");
+		assert!((done.strength - 1.0).abs() < f64::EPSILON);
+		assert_eq!(done.reactivation_count, 0);
+
+		// Reactivating a consolidated memory reopens it and counts the reactivation
+		let reactivated = visual_update_consolidation(
+			done,
+			Some(2000.0),
+			Some(3000.0),
+			2100.0,
+			Some(true),
+			None,
+			None,
+		);
+		assert_eq!(reactivated.state, "reconsolidating");
+		assert_eq!(reactivated.reactivation_count, 1);
+
+		// Without a window there is nothing to advance
+		let idle =
+			visual_update_consolidation(consolidating(0.3), None, None, 10_000.0, None, None, None);
+		assert_eq!(idle.state, "consolidating");
+		assert!((idle.strength - 0.3).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_visual_update_consolidation_polling_reactivates_once() {
+		let consolidated = JsVisualConsolidationState {
+			state: "consolidated".to_string(),
+			strength: 1.0,
+			reactivation_count: 0,
+		};
+
+		// The retrieval opens a reconsolidation window and counts once
+		let retrieved = visual_update_consolidation(
+			consolidated,
+			Some(0.0),
+			Some(1000.0),
+			100.0,
+			Some(true),
+			None,
+			None,
+		);
+		assert_eq!(retrieved.reactivation_count, 1);
+
+		// Polling with the same window, open or expired, never counts again
+		let open = visual_update_consolidation(
+			retrieved,
+			Some(0.0),
+			Some(1000.0),
+			500.0,
+			None,
+			None,
+			None,
+		);
+		assert_eq!(open.state, "reconsolidating");
+		assert_eq!(open.reactivation_count, 1);
+
+		let closed =
+			visual_update_consolidation(open, Some(0.0), Some(1000.0), 2000.0, None, None, None);
+		assert_eq!(closed.state, "consolidated");
+		let polled =
+			visual_update_consolidation(closed, Some(0.0), Some(1000.0), 3000.0, None, None, None);
+		assert_eq!(polled.state, "consolidated");
+		assert_eq!(polled.reactivation_count, 1);
+	}
+
+	#[test]
+	fn test_visual_update_consolidation_starts_from_source() {
+		let fresh = || JsVisualConsolidationState {
+			state: "fresh".to_string(),
+			strength: 0.0,
+			reactivation_count: 0,
+		};
+		let duration = visual_consolidation_duration("videoframe".to_string(), None);
+
+		let started = visual_update_consolidation(
+			fresh(),
+			None,
+			None,
+			1000.0,
+			None,
+			Some("videoframe".to_string()),
+			None,
+		);
+		assert_eq!(started.state, "consolidating");
+		assert!(started.strength.abs() < f64::EPSILON);
+
+		// The caller polls with the window the source implies
+		let halfway = visual_update_consolidation(
+			started,
+			Some(1000.0),
+			Some(1000.0 + duration),
+			duration.mul_add(0.5, 1000.0),
+			None,
+			None,
+			None,
+		);
+		assert!((halfway.strength - 0.5).abs() < 0.001);
+
+		// Without a window or a source a fresh memory stays fresh
+		let idle = visual_update_consolidation(fresh(), None, None, 1000.0, None, None, None);
+		assert_eq!(idle.state, "fresh");
+	}
+
+	#[test]
+	fn test_spread_temporal_activation_multi() {
+		let config = || {
+			Some(JsTemporalSpreadingConfig {
+				forward_strength: None,
+				backward_strength: None,
+				distance_decay_rate: None,
+				episode_boost: None,
+				context_persistence: None,
+				max_temporal_distance: Some(1),
+			})
+		};
+		// Chain 0 → 1 → 2 → 3 → 4, seeded at both ends
+		let links = create_episode_links(vec![0, 1, 2, 3, 4], config());
+
+		let result = spread_temporal_activation_multi(
+			5,
+			links.clone(),
+			vec![0, 4],
+			vec![1.0, 0.5],
+			config(),
+		)
+		.expect("matching seeds and weights should spread");
+		assert_eq!(result.forward_activated, vec![1]);
+		assert_eq!(result.backward_activated, vec![3]);
+		assert!(result.activations[1] > 0.0);
+		assert!(result.activations[3] > 0.0);
+		assert!(result.activations[2].abs() < f64::EPSILON);
+
+		let reason = spread_temporal_activation_multi(5, links, vec![0, 4], vec![1.0], config())
+			.err()
+			.map(|e| e.reason)
+			.unwrap_or_default();
+		assert!(reason.starts_with("seedActivations has 1 entries, expected 2"));
+	}
 }
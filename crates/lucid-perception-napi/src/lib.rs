@@ -10,13 +10,20 @@
 use std::path::PathBuf;
 
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
 
 use lucid_perception::{
-	pipeline::{PipelineConfig, VideoProcessingOutput},
-	scene::{FrameCandidate, SceneConfig},
-	transcribe::{TranscriptionConfig, TranscriptionResult},
-	video::{ExtractedFrame, ImageFormat, VideoConfig, VideoMetadata},
+	pipeline::{ChapterBoundaryMode, PipelineConfig, VideoProcessingOutput},
+	scene::{
+		find_nearest_hash, get_representative_frames, get_scene_frames, get_unique_frames,
+		FrameCandidate, PerceptualHash, SceneConfig, ThresholdMode,
+	},
+	transcribe::{TranscriptSegment, TranscriptionConfig, TranscriptionResult},
+	video::{
+		Chapter, ExtractedFrame, ExtractionProgress, FrameSamplingStrategy, ImageFormat,
+		VideoConfig, VideoMetadata,
+	},
 	PerceptionError,
 };
 
@@ -56,6 +63,17 @@ pub struct JsExtractedFrame {
 	pub is_keyframe: bool,
 }
 
+/// A chapter marker.
+#[napi(object)]
+pub struct JsChapter {
+	/// Chapter title
+	pub title: String,
+	/// Start time in seconds
+	pub start_seconds: f64,
+	/// End time in seconds
+	pub end_seconds: f64,
+}
+
 /// Frame with scene detection info.
 #[napi(object)]
 pub struct JsFrameCandidate {
@@ -69,12 +87,27 @@ pub struct JsFrameCandidate {
 	pub is_keyframe: bool,
 	/// Hash as hex string
 	pub hash_hex: String,
+	/// Raw hash bytes (each 0-255), used to compare frames
+	pub hash_bytes: Vec<u32>,
 	/// Is scene change
 	pub is_scene_change: bool,
 	/// Is duplicate
 	pub is_duplicate: bool,
 	/// Distance from previous
 	pub distance_from_previous: u32,
+	/// Fraction of hash bits that differ from the previous frame (0-1)
+	pub scene_change_confidence: f64,
+	/// Sharpness score (0-1)
+	pub quality_score: f64,
+}
+
+/// Closest stored frame hash to a query.
+#[napi(object)]
+pub struct JsNearestFrame {
+	/// Index into the candidate hashes
+	pub index: u32,
+	/// Hamming distance to the query
+	pub distance: u32,
 }
 
 /// Transcript segment.
@@ -127,6 +160,8 @@ pub struct JsVideoProcessingOutput {
 	pub metadata: JsVideoMetadata,
 	/// Frames with scene info
 	pub frames: Vec<JsFrameCandidate>,
+	/// Chapter markers (empty unless chapter boundaries are enabled)
+	pub chapters: Vec<JsChapter>,
 	/// Transcript (if available)
 	pub transcript: Option<JsTranscriptionResult>,
 	/// No audio in video
@@ -135,22 +170,58 @@ pub struct JsVideoProcessingOutput {
 	pub stats: JsProcessingStats,
 }
 
+/// Preview of a frame extraction.
+#[napi(object)]
+pub struct JsExtractionPlan {
+	/// Timestamps (seconds) that will be sampled; empty for keyframes-only
+	pub timestamps: Vec<f64>,
+	/// Expected number of frames
+	pub estimated_frame_count: u32,
+	/// Estimated total size in bytes
+	pub estimated_total_bytes: i64,
+}
+
+/// Progress of a running frame extraction.
+#[napi(object)]
+pub struct JsExtractionProgress {
+	/// Frames extracted so far
+	pub frames_extracted: u32,
+	/// Frames the extraction expects to produce (final count for keyframes)
+	pub total_estimated: u32,
+}
+
 /// Video extraction config.
 #[napi(object)]
 #[derive(Clone)]
 pub struct JsVideoConfig {
 	/// Output directory
 	pub output_dir: Option<String>,
-	/// Max frames (0 = all)
+	/// Max frames (0 = all); the exact frame count for "count" sampling
 	pub max_frames: Option<u32>,
 	/// Interval between frames (seconds)
 	pub interval_seconds: Option<f64>,
 	/// Quality (1-31, lower is better)
 	pub quality: Option<u32>,
-	/// Output format: "jpeg" or "png"
+	/// Output format: "jpeg", "png" or "webp"
 	pub format: Option<String>,
-	/// Extract keyframes only
+	/// Frame sampling: "interval", "count" (`maxFrames` evenly spaced frames)
+	/// or "keyframes" (default: "interval")
+	pub sampling: Option<String>,
+	/// Deprecated: use `sampling: "keyframes"`. Extract keyframes only,
+	/// ignored when `sampling` is set
 	pub keyframes_only: Option<bool>,
+	/// Longest frame side in pixels; larger frames are downscaled
+	pub max_dimension: Option<u32>,
+	/// Start of the range to extract from (seconds, default: 0)
+	pub start_seconds: Option<f64>,
+	/// End of the range to extract from (seconds, default: video duration)
+	pub end_seconds: Option<f64>,
+	/// Drop near-duplicate frames while extracting (default: false)
+	pub dedup_during_extraction: Option<bool>,
+	/// PNG zlib compression level, 0-9 (default: `FFmpeg`'s)
+	pub png_compression: Option<u32>,
+	/// JPEG chroma subsampling: "444", "422" or "420" (default: 4:2:0)
+	pub jpeg_subsampling: Option<String>,
 }
 
 /// Scene detection config.
@@ -160,9 +231,20 @@ pub struct JsSceneConfig {
 	/// Hash size (8 or 16)
 	pub hash_size: Option<u32>,
 	/// Scene change threshold
-	pub scene_threshold: Option<u32>,
+	pub scene_threshold: Option<f64>,
 	/// Duplicate threshold
-	pub duplicate_threshold: Option<u32>,
+	pub duplicate_threshold: Option<f64>,
+	/// Threshold units: "absolute" (differing bits) or "fraction" (of total bits)
+	pub threshold_mode: Option<String>,
+	/// Minimum seconds between scene changes (0 = off)
+	pub min_scene_seconds: Option<f64>,
+	/// Minimum frames between scene changes (0 = off)
+	pub min_scene_gap_frames: Option<u32>,
+	/// Smallest frame width and height in pixels that can be hashed; smaller
+	/// frames are an error (default: 16, 0 = off)
+	pub min_frame_dimension: Option<u32>,
+	/// Drop color right after decoding frames for hashing (default: true)
+	pub grayscale_hashing: Option<bool>,
 }
 
 /// Transcription config.
@@ -177,6 +259,16 @@ pub struct JsTranscriptionConfig {
 	pub threads: Option<u32>,
 	/// Translate to English
 	pub translate: Option<bool>,
+	/// Longest segment in characters; longer ones are split at word
+	/// boundaries (default: 0 = keep Whisper's segmentation)
+	pub max_segment_length: Option<u32>,
+	/// Zero-based audio track to transcribe (default: the default track)
+	pub audio_track_index: Option<u32>,
+	/// Collapse whitespace in segment and full text (default: false)
+	pub clean_segments: Option<bool>,
+	/// Strip "[Music]", "(applause)" and other bracketed annotations
+	/// (default: false)
+	pub filter_nonspeech: Option<bool>,
 }
 
 /// Pipeline config.
@@ -191,8 +283,12 @@ pub struct JsPipelineConfig {
 	pub transcription: Option<JsTranscriptionConfig>,
 	/// Enable scene detection
 	pub enable_scene_detection: Option<bool>,
+	/// Chapter boundaries: "ignore", "replace", or "merge"
+	pub chapter_boundaries: Option<String>,
 	/// Skip transcription
 	pub skip_transcription: Option<bool>,
+	/// Abort processing after this many milliseconds (default: no timeout)
+	pub timeout_ms: Option<u32>,
 }
 
 // ============================================================================
@@ -221,13 +317,41 @@ pub async fn video_check_ffmpeg() -> Result<bool> {
 pub async fn video_get_metadata(video_path: String) -> Result<JsVideoMetadata> {
 	let metadata = lucid_perception::get_video_metadata(&video_path)
 		.await
-		.map_err(perception_error_to_napi)?;
+		.map_err(perception_error_to_napi_async)?;
 
 	Ok(metadata_to_js(metadata))
 }
 
+/// Get chapter markers from a video (empty if it has none).
+///
+/// # Errors
+///
+/// Returns an error if the video cannot be read or `FFprobe` fails.
+#[napi]
+pub async fn video_get_chapters(video_path: String) -> Result<Vec<JsChapter>> {
+	let chapters = lucid_perception::get_video_chapters(&video_path)
+		.await
+		.map_err(perception_error_to_napi_async)?;
+
+	Ok(chapters.into_iter().map(chapter_to_js).collect())
+}
+
+/// Check whether a video has an audio stream, without extracting any audio.
+///
+/// # Errors
+///
+/// Returns an error if the video cannot be read or `FFprobe` fails.
+#[napi]
+pub async fn video_has_audio(video_path: String) -> Result<bool> {
+	lucid_perception::has_audio_stream(&video_path)
+		.await
+		.map_err(perception_error_to_napi_async)
+}
+
 /// Extract frames from a video.
 ///
+/// `sceneConfig` sets the duplicate threshold used by `dedupDuringExtraction`.
+///
 /// # Errors
 ///
 /// Returns an error if frame extraction fails.
@@ -235,16 +359,110 @@ pub async fn video_get_metadata(video_path: String) -> Result<JsVideoMetadata> {
 pub async fn video_extract_frames(
 	video_path: String,
 	config: Option<JsVideoConfig>,
+	scene_config: Option<JsSceneConfig>,
 ) -> Result<Vec<JsExtractedFrame>> {
 	let config = js_video_config_to_core(config);
+	let scene_config = js_scene_config_to_core(scene_config);
+
+	let frames =
+		lucid_perception::extract_frames_with_scene_config(&video_path, &config, &scene_config)
+			.await
+			.map_err(perception_error_to_napi_async)?;
+
+	Ok(frames.into_iter().map(extracted_frame_to_js).collect())
+}
+
+/// Extract frames from a video, calling `callback` with
+/// `{ framesExtracted, totalEstimated }` after each frame.
+///
+/// `sceneConfig` sets the duplicate threshold used by `dedupDuringExtraction`.
+///
+/// # Errors
+///
+/// Returns an error if frame extraction fails.
+#[napi]
+pub async fn video_extract_frames_with_progress(
+	video_path: String,
+	config: Option<JsVideoConfig>,
+	callback: ThreadsafeFunction<JsExtractionProgress, (), JsExtractionProgress, Status, false>,
+	scene_config: Option<JsSceneConfig>,
+) -> Result<Vec<JsExtractedFrame>> {
+	let config = js_video_config_to_core(config);
+	let scene_config = js_scene_config_to_core(scene_config);
+
+	let frames = lucid_perception::extract_frames_with_progress(
+		&video_path,
+		&config,
+		&scene_config,
+		|progress| {
+			let _ = callback.call(
+				extraction_progress_to_js(progress),
+				ThreadsafeFunctionCallMode::NonBlocking,
+			);
+		},
+	)
+	.await
+	.map_err(perception_error_to_napi_async)?;
+
+	Ok(frames.into_iter().map(extracted_frame_to_js).collect())
+}
 
-	let frames = lucid_perception::extract_frames(&video_path, &config)
+/// Extract the sharpest of a few frames sampled across a video, for use as
+/// its thumbnail.
+///
+/// # Errors
+///
+/// Returns an error if no thumbnail frame could be extracted.
+#[napi]
+pub async fn video_extract_thumbnail(
+	video_path: String,
+	config: Option<JsVideoConfig>,
+) -> Result<JsExtractedFrame> {
+	let config = js_video_config_to_core(config);
+
+	let frame = lucid_perception::extract_thumbnail(&video_path, &config)
 		.await
-		.map_err(perception_error_to_napi)?;
+		.map_err(perception_error_to_napi_async)?;
+
+	Ok(extracted_frame_to_js(frame))
+}
+
+/// Extract one frame per entry of `timestamps`, returned in the same order.
+///
+/// # Errors
+///
+/// Returns an error if any of the requested frames fails to extract.
+#[napi]
+pub async fn video_extract_frames_at(
+	video_path: String,
+	timestamps: Vec<f64>,
+	config: Option<JsVideoConfig>,
+) -> Result<Vec<JsExtractedFrame>> {
+	let config = js_video_config_to_core(config);
+
+	let frames = lucid_perception::extract_frames_at(&video_path, &timestamps, &config)
+		.await
+		.map_err(perception_error_to_napi_async)?;
 
 	Ok(frames.into_iter().map(extracted_frame_to_js).collect())
 }
 
+/// Preview which frames an extraction would produce, without running `FFmpeg`.
+#[napi]
+pub fn video_plan_extraction(
+	metadata: JsVideoMetadata,
+	config: Option<JsVideoConfig>,
+) -> JsExtractionPlan {
+	let config = js_video_config_to_core(config);
+	let plan = lucid_perception::plan_extraction(&js_metadata_to_core(metadata), &config);
+
+	JsExtractionPlan {
+		timestamps: plan.timestamps,
+		estimated_frame_count: u32::try_from(plan.estimated_frame_count).unwrap_or(u32::MAX),
+		estimated_total_bytes: i64::try_from(plan.estimated_total_bytes).unwrap_or(i64::MAX),
+	}
+}
+
 /// Transcribe audio from a video.
 ///
 /// # Errors
@@ -259,7 +477,26 @@ pub async fn video_transcribe(
 
 	let result = lucid_perception::transcribe_video(&video_path, &config)
 		.await
-		.map_err(perception_error_to_napi)?;
+		.map_err(perception_error_to_napi_async)?;
+
+	Ok(transcription_to_js(result))
+}
+
+/// Transcribe an audio file, skipping the video demux step.
+///
+/// # Errors
+///
+/// Returns an error if transcription fails or the model is unavailable.
+#[napi]
+pub async fn video_transcribe_audio(
+	audio_path: String,
+	config: Option<JsTranscriptionConfig>,
+) -> Result<JsTranscriptionResult> {
+	let config = js_transcription_config_to_core(config);
+
+	let result = lucid_perception::transcribe_audio_file(&audio_path, &config)
+		.await
+		.map_err(perception_error_to_napi_async)?;
 
 	Ok(transcription_to_js(result))
 }
@@ -278,11 +515,90 @@ pub async fn video_process(
 
 	let output = lucid_perception::process_video(&video_path, &config)
 		.await
-		.map_err(perception_error_to_napi)?;
+		.map_err(perception_error_to_napi_async)?;
 
 	Ok(processing_output_to_js(output))
 }
 
+/// Keep only the frames that start a new scene.
+#[napi]
+pub fn video_get_scene_frames(frames: Vec<JsFrameCandidate>) -> Vec<JsFrameCandidate> {
+	select_frames(frames, get_scene_frames)
+}
+
+/// Drop frames marked as duplicates of the frame before them.
+#[napi]
+pub fn video_get_unique_frames(frames: Vec<JsFrameCandidate>) -> Vec<JsFrameCandidate> {
+	select_frames(frames, get_unique_frames)
+}
+
+/// Pick the frame closest to all the others in each scene, by hash distance.
+///
+/// Frames must be in order, as returned by `videoProcess`, with their
+/// `hashBytes` intact.
+#[napi]
+pub fn video_get_representative_frames(frames: Vec<JsFrameCandidate>) -> Vec<JsFrameCandidate> {
+	select_frames(frames, get_representative_frames)
+}
+
+/// Find the candidate frame hash closest to a query hash, for deduplicating
+/// a new frame against an existing library.
+///
+/// Hashes are the `hashHex` strings from frame candidates. Returns `null` if
+/// there are no candidates.
+///
+/// # Errors
+///
+/// Returns an `INVALID_HASH` error if any hash string doesn't decode.
+#[napi]
+pub fn video_find_nearest_frame(
+	query_hash_hex: String,
+	candidate_hash_hexes: Vec<String>,
+) -> Result<Option<JsNearestFrame>, &'static str> {
+	let query = PerceptualHash::from_hex(&query_hash_hex).map_err(perception_error_to_napi)?;
+	let candidates = candidate_hash_hexes
+		.iter()
+		.map(|hex| PerceptualHash::from_hex(hex))
+		.collect::<lucid_perception::Result<Vec<_>>>()
+		.map_err(perception_error_to_napi)?;
+
+	Ok(
+		find_nearest_hash(&query, &candidates).map(|(index, distance)| JsNearestFrame {
+			index: u32::try_from(index).unwrap_or(u32::MAX),
+			distance,
+		}),
+	)
+}
+
+/// Get transcript text spoken within `window_ms` either side of `timestamp_ms`.
+///
+/// Segments that straddle the window edges are included, so speech running
+/// across a frame isn't lost. Used to give frame description prompts their
+/// surrounding speech.
+#[napi]
+pub fn transcript_text_near(
+	transcript: JsTranscriptionResult,
+	timestamp_ms: i64,
+	window_ms: i64,
+) -> String {
+	js_transcription_to_core(transcript).text_near(timestamp_ms, window_ms)
+}
+
+/// Re-cut a transcript so no segment is longer than `max_chars` characters.
+///
+/// Long segments are split at word boundaries with proportional timestamps,
+/// and very short neighbours are merged. 0 leaves the transcript as is.
+#[napi]
+pub fn transcript_resegment(
+	transcript: JsTranscriptionResult,
+	max_chars: u32,
+) -> JsTranscriptionResult {
+	transcription_to_js(lucid_perception::resegment(
+		&js_transcription_to_core(transcript),
+		max_chars as usize,
+	))
+}
+
 /// Check if Whisper model is available.
 #[napi]
 pub fn video_is_model_available(model_path: Option<String>) -> bool {
@@ -294,6 +610,31 @@ pub fn video_is_model_available(model_path: Option<String>) -> bool {
 	lucid_perception::transcribe::is_model_available(&config)
 }
 
+/// Load a Whisper model (default: the default model path) so later
+/// transcriptions with it skip the loading step.
+///
+/// Models are otherwise loaded on first use and kept for the life of the
+/// process. Returns true once the model is loaded.
+///
+/// # Errors
+///
+/// Returns an error if the model file is missing or can't be loaded.
+#[napi]
+pub fn load_whisper_model(model_path: Option<String>) -> Result<bool, &'static str> {
+	let model_path =
+		model_path.map_or_else(|| TranscriptionConfig::default().model_path, PathBuf::from);
+	lucid_perception::load_whisper_model(&model_path).map_err(perception_error_to_napi)?;
+	Ok(true)
+}
+
+/// Check if a Whisper model (default: the default model path) is loaded.
+#[napi]
+pub fn is_whisper_model_loaded(model_path: Option<String>) -> bool {
+	let model_path =
+		model_path.map_or_else(|| TranscriptionConfig::default().model_path, PathBuf::from);
+	lucid_perception::is_whisper_model_loaded(&model_path)
+}
+
 /// Get the download URL for the default Whisper model.
 #[napi]
 pub fn video_get_model_url() -> String {
@@ -309,12 +650,30 @@ pub fn video_get_default_model_path() -> String {
 		.to_string()
 }
 
+/// Get the Whisper thread count used when `threads` is 0.
+#[napi]
+pub fn video_suggest_whisper_threads() -> u32 {
+	lucid_perception::transcribe::suggest_whisper_threads()
+}
+
 // ============================================================================
 // Type Conversions
 // ============================================================================
 
-fn perception_error_to_napi(e: PerceptionError) -> Error {
-	Error::new(Status::GenericFailure, e.to_string())
+/// Convert a perception error for a sync export: the thrown JS error's `code`
+/// is the stable [`PerceptionError::code`] (e.g. `"NO_AUDIO"`) and its
+/// message the human-readable description.
+fn perception_error_to_napi(e: PerceptionError) -> Error<&'static str> {
+	Error::new(e.code(), e.to_string())
+}
+
+/// Convert a perception error for an async export.
+///
+/// napi can only reject a promise with a built-in `Status`, so the stable
+/// [`PerceptionError::code`] leads the message instead, as in
+/// `"[NO_AUDIO] No audio stream found in video: ..."`.
+fn perception_error_to_napi_async(e: PerceptionError) -> Error {
+	Error::new(Status::GenericFailure, format!("[{}] {e}", e.code()))
 }
 
 fn metadata_to_js(m: VideoMetadata) -> JsVideoMetadata {
@@ -329,6 +688,18 @@ fn metadata_to_js(m: VideoMetadata) -> JsVideoMetadata {
 	}
 }
 
+fn js_metadata_to_core(m: JsVideoMetadata) -> VideoMetadata {
+	VideoMetadata {
+		duration_seconds: m.duration_seconds,
+		frame_rate: m.frame_rate,
+		frame_count: u64::try_from(m.frame_count).unwrap_or(0),
+		width: m.width,
+		height: m.height,
+		codec: m.codec,
+		has_audio: m.has_audio,
+	}
+}
+
 fn extracted_frame_to_js(f: ExtractedFrame) -> JsExtractedFrame {
 	JsExtractedFrame {
 		path: f.path.display().to_string(),
@@ -338,19 +709,73 @@ fn extracted_frame_to_js(f: ExtractedFrame) -> JsExtractedFrame {
 	}
 }
 
+fn extraction_progress_to_js(p: ExtractionProgress) -> JsExtractionProgress {
+	JsExtractionProgress {
+		frames_extracted: u32::try_from(p.frames_extracted).unwrap_or(u32::MAX),
+		total_estimated: u32::try_from(p.total_estimated).unwrap_or(u32::MAX),
+	}
+}
+
+fn chapter_to_js(c: Chapter) -> JsChapter {
+	JsChapter {
+		title: c.title,
+		start_seconds: c.start_seconds,
+		end_seconds: c.end_seconds,
+	}
+}
+
 fn frame_candidate_to_js(f: FrameCandidate) -> JsFrameCandidate {
 	JsFrameCandidate {
 		path: f.frame.path.display().to_string(),
 		timestamp_seconds: f.frame.timestamp_seconds,
 		frame_number: f.frame.frame_number,
 		is_keyframe: f.frame.is_keyframe,
+		hash_bytes: f.hash.bytes.iter().map(|&b| u32::from(b)).collect(),
 		hash_hex: f.hash.hex,
 		is_scene_change: f.is_scene_change,
 		is_duplicate: f.is_duplicate,
 		distance_from_previous: f.distance_from_previous,
+		scene_change_confidence: f.scene_change_confidence,
+		quality_score: f.quality_score,
 	}
 }
 
+fn js_frame_candidate_to_core(f: JsFrameCandidate) -> FrameCandidate {
+	FrameCandidate {
+		frame: ExtractedFrame {
+			path: PathBuf::from(f.path),
+			timestamp_seconds: f.timestamp_seconds,
+			frame_number: f.frame_number,
+			is_keyframe: f.is_keyframe,
+		},
+		hash: PerceptualHash {
+			bytes: f
+				.hash_bytes
+				.iter()
+				.map(|&b| u8::try_from(b).unwrap_or(u8::MAX))
+				.collect(),
+			hex: f.hash_hex,
+		},
+		is_scene_change: f.is_scene_change,
+		is_duplicate: f.is_duplicate,
+		distance_from_previous: f.distance_from_previous,
+		scene_change_confidence: f.scene_change_confidence,
+		quality_score: f.quality_score,
+	}
+}
+
+/// Run one of the scene module's frame filters over JS frames.
+fn select_frames(
+	frames: Vec<JsFrameCandidate>,
+	select: fn(&[FrameCandidate]) -> Vec<&FrameCandidate>,
+) -> Vec<JsFrameCandidate> {
+	let frames: Vec<FrameCandidate> = frames.into_iter().map(js_frame_candidate_to_core).collect();
+	select(&frames)
+		.into_iter()
+		.map(|f| frame_candidate_to_js(f.clone()))
+		.collect()
+}
+
 fn transcription_to_js(t: TranscriptionResult) -> JsTranscriptionResult {
 	JsTranscriptionResult {
 		text: t.text,
@@ -369,10 +794,30 @@ fn transcription_to_js(t: TranscriptionResult) -> JsTranscriptionResult {
 	}
 }
 
+fn js_transcription_to_core(t: JsTranscriptionResult) -> TranscriptionResult {
+	TranscriptionResult {
+		text: t.text,
+		segments: t
+			.segments
+			.into_iter()
+			.map(|s| TranscriptSegment {
+				start_ms: s.start_ms,
+				end_ms: s.end_ms,
+				text: s.text,
+				#[allow(clippy::cast_possible_truncation)]
+				confidence: s.confidence.map(|c| c as f32),
+			})
+			.collect(),
+		detected_language: t.detected_language,
+		duration_seconds: t.duration_seconds,
+	}
+}
+
 fn processing_output_to_js(o: VideoProcessingOutput) -> JsVideoProcessingOutput {
 	JsVideoProcessingOutput {
 		metadata: metadata_to_js(o.metadata),
 		frames: o.frames.into_iter().map(frame_candidate_to_js).collect(),
+		chapters: o.chapters.into_iter().map(chapter_to_js).collect(),
 		transcript: o.transcript.map(transcription_to_js),
 		no_audio: o.no_audio,
 		stats: JsProcessingStats {
@@ -400,9 +845,26 @@ fn js_video_config_to_core(js: Option<JsVideoConfig>) -> VideoConfig {
 			quality: js.quality.unwrap_or(default.quality),
 			format: js.format.as_deref().map_or(default.format, |s| match s {
 				"png" => ImageFormat::Png,
+				"webp" => ImageFormat::Webp,
 				_ => ImageFormat::Jpeg,
 			}),
-			keyframes_only: js.keyframes_only.unwrap_or(default.keyframes_only),
+			sampling: match js.sampling.as_deref() {
+				Some("count") => FrameSamplingStrategy::FixedCount,
+				Some("keyframes") => FrameSamplingStrategy::Keyframes,
+				Some(_) => FrameSamplingStrategy::Interval,
+				None if js.keyframes_only == Some(true) => FrameSamplingStrategy::Keyframes,
+				None => default.sampling,
+			},
+			max_dimension: js.max_dimension.or(default.max_dimension),
+			start_seconds: js.start_seconds.or(default.start_seconds),
+			end_seconds: js.end_seconds.or(default.end_seconds),
+			dedup_during_extraction: js
+				.dedup_during_extraction
+				.unwrap_or(default.dedup_during_extraction),
+			png_compression: js.png_compression.or(default.png_compression),
+			jpeg_subsampling: js.jpeg_subsampling.or(default.jpeg_subsampling),
+			// `keyframesOnly` is already folded into `sampling` above
+			..default
 		}
 	})
 }
@@ -416,6 +878,21 @@ fn js_scene_config_to_core(js: Option<JsSceneConfig>) -> SceneConfig {
 			duplicate_threshold: js
 				.duplicate_threshold
 				.unwrap_or(default.duplicate_threshold),
+			threshold_mode: js.threshold_mode.as_deref().map_or(
+				default.threshold_mode,
+				|s| match s {
+					"fraction" => ThresholdMode::Fraction,
+					_ => ThresholdMode::Absolute,
+				},
+			),
+			min_scene_seconds: js.min_scene_seconds.unwrap_or(default.min_scene_seconds),
+			min_scene_gap_frames: js
+				.min_scene_gap_frames
+				.unwrap_or(default.min_scene_gap_frames),
+			min_frame_dimension: js
+				.min_frame_dimension
+				.unwrap_or(default.min_frame_dimension),
+			grayscale_hashing: js.grayscale_hashing.unwrap_or(default.grayscale_hashing),
 		}
 	})
 }
@@ -431,7 +908,12 @@ fn js_transcription_config_to_core(js: Option<JsTranscriptionConfig>) -> Transcr
 			language: js.language.unwrap_or(default.language),
 			threads: js.threads.unwrap_or(default.threads),
 			translate: js.translate.unwrap_or(default.translate),
-			max_segment_length: default.max_segment_length,
+			max_segment_length: js
+				.max_segment_length
+				.map_or(default.max_segment_length, |m| m as usize),
+			audio_track_index: js.audio_track_index.or(default.audio_track_index),
+			clean_segments: js.clean_segments.unwrap_or(default.clean_segments),
+			filter_nonspeech: js.filter_nonspeech.unwrap_or(default.filter_nonspeech),
 		}
 	})
 }
@@ -448,7 +930,124 @@ fn js_pipeline_config_to_core(js: Option<JsPipelineConfig>) -> PipelineConfig {
 			enable_scene_detection: js
 				.enable_scene_detection
 				.unwrap_or(default.enable_scene_detection),
+			chapter_boundaries: js.chapter_boundaries.as_deref().map_or(
+				default.chapter_boundaries,
+				|s| match s {
+					"replace" => ChapterBoundaryMode::Replace,
+					"merge" => ChapterBoundaryMode::Merge,
+					_ => ChapterBoundaryMode::Ignore,
+				},
+			),
 			skip_transcription: js.skip_transcription.unwrap_or(default.skip_transcription),
+			timeout_ms: js.timeout_ms.map(u64::from).or(default.timeout_ms),
 		}
 	})
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+	use super::*;
+
+	fn candidate(frame_number: u32, hash: u8, is_scene_change: bool) -> FrameCandidate {
+		FrameCandidate {
+			frame: ExtractedFrame {
+				path: PathBuf::from(format!("frame-{frame_number}.jpg")),
+				timestamp_seconds: f64::from(frame_number),
+				frame_number,
+				is_keyframe: false,
+			},
+			hash: PerceptualHash {
+				bytes: vec![hash, 0x00],
+				hex: format!("{hash:02x}00"),
+			},
+			is_scene_change,
+			is_duplicate: false,
+			distance_from_previous: 0,
+			scene_change_confidence: 0.0,
+			quality_score: 1.0,
+		}
+	}
+
+	#[test]
+	fn test_representative_frames_match_core() {
+		let frames = vec![
+			candidate(0, 0x00, true),
+			candidate(1, 0x0F, false),
+			candidate(2, 0x07, false),
+			candidate(3, 0xF0, true),
+			candidate(4, 0xF1, false),
+		];
+		let expected: Vec<u32> = get_representative_frames(&frames)
+			.iter()
+			.map(|f| f.frame.frame_number)
+			.collect();
+
+		let js_frames = frames.into_iter().map(frame_candidate_to_js).collect();
+		let selected: Vec<u32> = video_get_representative_frames(js_frames)
+			.iter()
+			.map(|f| f.frame_number)
+			.collect();
+
+		assert_eq!(selected, expected);
+		assert_eq!(selected, vec![1, 3]);
+	}
+
+	#[test]
+	fn test_errors_keep_message_and_carry_code() {
+		let error = PerceptionError::InvalidHash("zz".to_string());
+		let message = error.to_string();
+
+		let napi_error = perception_error_to_napi(error);
+		assert_eq!(napi_error.status, "INVALID_HASH");
+		assert_eq!(napi_error.reason, message);
+
+		let Err(napi_error) = video_find_nearest_frame("zz".to_string(), Vec::new()) else {
+			unreachable!("an undecodable hash is rejected")
+		};
+		assert_eq!(napi_error.status, "INVALID_HASH");
+	}
+
+	#[test]
+	fn test_transcription_config_maps_max_segment_length() {
+		let js = |max_segment_length| JsTranscriptionConfig {
+			model_path: None,
+			language: None,
+			threads: None,
+			translate: None,
+			max_segment_length,
+			audio_track_index: None,
+			clean_segments: None,
+			filter_nonspeech: None,
+		};
+		let default = TranscriptionConfig::default().max_segment_length;
+		assert_eq!(
+			js_transcription_config_to_core(Some(js(None))).max_segment_length,
+			default
+		);
+		assert_eq!(
+			js_transcription_config_to_core(Some(js(Some(80)))).max_segment_length,
+			80
+		);
+	}
+
+	#[test]
+	fn test_async_errors_lead_with_code() {
+		let missing = std::env::temp_dir().join("lucid-missing-video.mp4");
+		let error = PerceptionError::VideoNotFound(missing.clone());
+		let expected = format!("[VIDEO_NOT_FOUND] {error}");
+		assert_eq!(perception_error_to_napi_async(error).reason, expected);
+
+		let runtime = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()
+			.expect("runtime");
+		let result = runtime.block_on(video_get_metadata(missing.display().to_string()));
+		let napi_error = result.err().expect("a missing video is rejected");
+		assert_eq!(napi_error.reason, expected);
+	}
+}
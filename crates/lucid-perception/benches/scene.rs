@@ -0,0 +1,70 @@
+//! Benchmarks for scene detection
+//!
+//! Compares hashing every frame with a freshly built hasher against
+//! `detect_scene_changes`, which builds one hasher per worker thread.
+
+#![allow(clippy::expect_used)] // Fine in benchmarks
+
+use std::path::Path;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lucid_perception::scene::compute_phash_sized;
+use lucid_perception::{detect_scene_changes, ExtractedFrame, SceneConfig};
+
+/// Write `count` small gradient frames to `dir`
+fn generate_frames(dir: &Path, count: u32) -> Vec<ExtractedFrame> {
+	(0..count)
+		.map(|i| {
+			let path = dir.join(format!("frame_{i:05}.png"));
+			#[allow(clippy::cast_possible_truncation)]
+			image::RgbImage::from_fn(64, 64, |x, y| {
+				let shade = ((x + y + i) % 256) as u8;
+				image::Rgb([shade, 255 - shade, (i % 256) as u8])
+			})
+			.save(&path)
+			.expect("should write frame");
+			ExtractedFrame {
+				path,
+				timestamp_seconds: f64::from(i),
+				frame_number: i,
+				is_keyframe: false,
+			}
+		})
+		.collect()
+}
+
+fn bench_frame_hashing(c: &mut Criterion) {
+	let mut group = c.benchmark_group("frame_hashing");
+	let dir = tempfile::tempdir().expect("should create temp dir");
+	let config = SceneConfig::default();
+
+	for frame_count in &[100, 500] {
+		let frames = generate_frames(dir.path(), *frame_count);
+
+		let _ = group.throughput(Throughput::Elements(u64::from(*frame_count)));
+		let _ = group.bench_with_input(
+			BenchmarkId::new("hasher_per_frame", frame_count),
+			&frames,
+			|bench, frames| {
+				bench.iter(|| {
+					for frame in frames {
+						let _ = black_box(compute_phash_sized(&frame.path, config.hash_size));
+					}
+				});
+			},
+		);
+		let _ = group.bench_with_input(
+			BenchmarkId::new("detect_scene_changes", frame_count),
+			&frames,
+			|bench, frames| {
+				bench.iter(|| detect_scene_changes(black_box(frames), black_box(&config)));
+			},
+		);
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, bench_frame_hashing);
+
+criterion_main!(benches);
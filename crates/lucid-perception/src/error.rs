@@ -51,6 +51,23 @@ pub enum PerceptionError {
 	#[error("Failed to read image: {0}")]
 	ImageReadError(#[from] image::ImageError),
 
+	/// Frame image is too small to give a meaningful perceptual hash.
+	#[error("Frame {path} is {width}x{height}, below the {min_dimension}px minimum")]
+	InvalidFrame {
+		/// Path to the frame image
+		path: PathBuf,
+		/// Image width in pixels
+		width: u32,
+		/// Image height in pixels
+		height: u32,
+		/// Smallest allowed width and height
+		min_dimension: u32,
+	},
+
+	/// Perceptual hash string that doesn't decode.
+	#[error("Invalid perceptual hash: {0:?}")]
+	InvalidHash(String),
+
 	/// I/O error.
 	#[error("I/O error: {0}")]
 	IoError(#[from] std::io::Error),
@@ -69,6 +86,46 @@ pub enum PerceptionError {
 	#[error("Transcription failed: {0}")]
 	TranscriptionFailed(String),
 
+	/// Requested audio track does not exist in the input.
+	#[cfg(feature = "transcription")]
+	#[error("Audio track {index} not found in: {path}")]
+	AudioTrackNotFound {
+		/// Input file
+		path: PathBuf,
+		/// Requested zero-based audio track index
+		index: u32,
+	},
+
+	/// WAV audio in a layout the parser can't convert for Whisper.
+	#[cfg(feature = "transcription")]
+	#[error(
+		"Unsupported WAV format: format {audio_format}, {channels} channel(s), {sample_rate} Hz, \
+		 {bits_per_sample}-bit (expected 16-bit PCM)"
+	)]
+	UnsupportedAudioFormat {
+		/// `fmt ` chunk format tag (1 = PCM)
+		audio_format: u16,
+		/// Channel count
+		channels: u16,
+		/// Sample rate in Hz
+		sample_rate: u32,
+		/// Bits per sample
+		bits_per_sample: u16,
+	},
+
+	/// An English-only Whisper model was asked to transcribe another language.
+	#[cfg(feature = "transcription")]
+	#[error(
+		"Whisper model {model_path} is English-only but language is \"{language}\"; \
+		 use a multilingual model such as ggml-base.bin"
+	)]
+	ModelLanguageMismatch {
+		/// Requested language code
+		language: String,
+		/// English-only model that was configured
+		model_path: PathBuf,
+	},
+
 	/// Task was cancelled.
 	#[error("Operation was cancelled")]
 	Cancelled,
@@ -108,7 +165,149 @@ impl PerceptionError {
 	pub const fn is_recoverable(&self) -> bool {
 		matches!(self, Self::Timeout { .. } | Self::Cancelled)
 	}
+
+	/// Stable machine-readable code for this error.
+	///
+	/// Codes are part of the public API surfaced to JavaScript and must not
+	/// change once published.
+	#[must_use]
+	pub const fn code(&self) -> &'static str {
+		match self {
+			Self::FfmpegNotFound => "FFMPEG_NOT_FOUND",
+			Self::FfprobeNotFound => "FFPROBE_NOT_FOUND",
+			Self::VideoNotFound(_) => "VIDEO_NOT_FOUND",
+			Self::InvalidVideo(_) => "INVALID_VIDEO",
+			Self::FfmpegError { .. } => "FFMPEG_FAILED",
+			Self::FrameExtractionFailed { .. } => "FRAME_EXTRACTION_FAILED",
+			Self::NoVideoStream(_) => "NO_VIDEO",
+			Self::NoAudioStream(_) => "NO_AUDIO",
+			Self::ImageReadError(_) => "IMAGE_READ_FAILED",
+			Self::InvalidFrame { .. } => "INVALID_FRAME",
+			Self::InvalidHash(_) => "INVALID_HASH",
+			Self::IoError(_) => "IO_ERROR",
+			Self::JsonParseError(_) => "PARSE_ERROR",
+			#[cfg(feature = "transcription")]
+			Self::WhisperModelNotFound(_) => "MODEL_NOT_FOUND",
+			#[cfg(feature = "transcription")]
+			Self::TranscriptionFailed(_) => "TRANSCRIPTION_FAILED",
+			#[cfg(feature = "transcription")]
+			Self::AudioTrackNotFound { .. } => "AUDIO_TRACK_NOT_FOUND",
+			#[cfg(feature = "transcription")]
+			Self::UnsupportedAudioFormat { .. } => "UNSUPPORTED_AUDIO_FORMAT",
+			#[cfg(feature = "transcription")]
+			Self::ModelLanguageMismatch { .. } => "MODEL_LANGUAGE_MISMATCH",
+			Self::Cancelled => "CANCELLED",
+			Self::Timeout { .. } => "TIMEOUT",
+		}
+	}
 }
 
 /// Result type alias for perception operations.
 pub type Result<T> = std::result::Result<T, PerceptionError>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_error_codes() {
+		let path = PathBuf::from("video.mp4");
+		let cases = [
+			(PerceptionError::FfmpegNotFound, "FFMPEG_NOT_FOUND"),
+			(PerceptionError::FfprobeNotFound, "FFPROBE_NOT_FOUND"),
+			(
+				PerceptionError::VideoNotFound(path.clone()),
+				"VIDEO_NOT_FOUND",
+			),
+			(PerceptionError::InvalidVideo(path.clone()), "INVALID_VIDEO"),
+			(
+				PerceptionError::FfmpegError {
+					message: "boom".to_string(),
+					exit_code: Some(1),
+				},
+				"FFMPEG_FAILED",
+			),
+			(
+				PerceptionError::FrameExtractionFailed {
+					timestamp: 1.0,
+					reason: "boom".to_string(),
+				},
+				"FRAME_EXTRACTION_FAILED",
+			),
+			(PerceptionError::NoVideoStream(path.clone()), "NO_VIDEO"),
+			(PerceptionError::NoAudioStream(path), "NO_AUDIO"),
+			(
+				PerceptionError::ImageReadError(image::ImageError::IoError(std::io::Error::other(
+					"boom",
+				))),
+				"IMAGE_READ_FAILED",
+			),
+			(
+				PerceptionError::InvalidFrame {
+					path: PathBuf::from("frame.jpg"),
+					width: 1,
+					height: 1,
+					min_dimension: 16,
+				},
+				"INVALID_FRAME",
+			),
+			(
+				PerceptionError::InvalidHash("zz".to_string()),
+				"INVALID_HASH",
+			),
+			(
+				PerceptionError::IoError(std::io::Error::other("boom")),
+				"IO_ERROR",
+			),
+			(
+				PerceptionError::JsonParseError("boom".to_string()),
+				"PARSE_ERROR",
+			),
+			(PerceptionError::Cancelled, "CANCELLED"),
+			(PerceptionError::Timeout { seconds: 5 }, "TIMEOUT"),
+		];
+
+		for (error, code) in cases {
+			assert_eq!(error.code(), code);
+		}
+	}
+
+	#[cfg(feature = "transcription")]
+	#[test]
+	fn test_transcription_error_codes() {
+		assert_eq!(
+			PerceptionError::WhisperModelNotFound(PathBuf::from("model.bin")).code(),
+			"MODEL_NOT_FOUND"
+		);
+		assert_eq!(
+			PerceptionError::TranscriptionFailed("boom".to_string()).code(),
+			"TRANSCRIPTION_FAILED"
+		);
+		assert_eq!(
+			PerceptionError::AudioTrackNotFound {
+				path: PathBuf::from("video.mkv"),
+				index: 2,
+			}
+			.code(),
+			"AUDIO_TRACK_NOT_FOUND"
+		);
+		assert_eq!(
+			PerceptionError::UnsupportedAudioFormat {
+				audio_format: 3,
+				channels: 2,
+				sample_rate: 48000,
+				bits_per_sample: 32,
+			}
+			.code(),
+			"UNSUPPORTED_AUDIO_FORMAT"
+		);
+		assert_eq!(
+			PerceptionError::ModelLanguageMismatch {
+				language: "de".to_string(),
+				model_path: PathBuf::from("ggml-base.en.bin"),
+			}
+			.code(),
+			"MODEL_LANGUAGE_MISMATCH"
+		);
+	}
+}
@@ -13,6 +13,7 @@
 //!
 //! - **Frame Extraction**: Extract frames from videos using `FFmpeg` CLI
 //! - **Scene Detection**: Detect scene changes using perceptual hashing
+//! - **Frame Quality**: Score frame sharpness to skip blurry frames
 //! - **Transcription**: Transcribe audio using Whisper (optional)
 //! - **Pipeline**: Parallel processing of video analysis tasks
 //!
@@ -32,6 +33,7 @@
 //!
 //! ## Optional Features
 //!
+//! - `parallel` (default): Hash scene detection frames across a rayon pool
 //! - `transcription`: Enable Whisper-based audio transcription
 //! - `cuda`: Enable CUDA acceleration for Whisper (requires `transcription`)
 
@@ -40,6 +42,7 @@
 #![allow(clippy::needless_return)]
 
 pub mod error;
+pub mod quality;
 pub mod scene;
 pub mod video;
 
@@ -50,20 +53,29 @@ pub mod pipeline;
 
 // Re-exports for convenience
 pub use error::{PerceptionError, Result};
+pub use quality::compute_frame_quality;
 pub use scene::{
-	compute_phash, detect_scene_changes, hamming_distance, FrameCandidate, SceneConfig,
+	compute_phash, detect_scene_changes, find_nearest_hash, hamming_distance, FrameCandidate,
+	PerceptualHash, SceneConfig, SceneDetector, ThresholdMode,
 };
 pub use video::{
-	check_ffmpeg, check_ffprobe, extract_frame_at, extract_frames, get_video_metadata,
-	ExtractedFrame, ImageFormat, VideoConfig, VideoMetadata,
+	check_ffmpeg, check_ffprobe, extract_frame_at, extract_frame_at_with_max_dimension,
+	extract_frames, extract_frames_at, extract_frames_with_progress,
+	extract_frames_with_scene_config, extract_thumbnail, get_video_chapters, get_video_metadata,
+	has_audio_stream, plan_extraction, Chapter, ExtractedFrame, ExtractionPlan, ExtractionProgress,
+	FrameSamplingStrategy, ImageFormat, VideoConfig, VideoMetadata,
 };
 
 #[cfg(feature = "transcription")]
 pub use transcribe::{
-	transcribe_video, TranscriptSegment, TranscriptionConfig, TranscriptionResult,
+	clean_transcript, is_audio_file, is_whisper_model_loaded, load_whisper_model, resegment,
+	resolve_model_for_language, suggest_whisper_threads, transcribe_audio_file, transcribe_video,
+	TranscriptSegment, TranscriptionConfig, TranscriptionResult,
 };
 
-pub use pipeline::{process_video, process_video_sync, PipelineConfig, VideoProcessingOutput};
+pub use pipeline::{
+	process_video, process_video_sync, ChapterBoundaryMode, PipelineConfig, VideoProcessingOutput,
+};
 
 /// Library version.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -3,15 +3,18 @@
 //! This module coordinates frame extraction, scene detection, and transcription
 //! to run in parallel where possible.
 
+use std::future::Future;
 use std::path::Path;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 use crate::error::{PerceptionError, Result};
 use crate::scene::{detect_scene_changes, FrameCandidate, SceneConfig};
 use crate::video::{
-	extract_frames, get_video_metadata, ExtractedFrame, VideoConfig, VideoMetadata,
+	extract_frames_with_scene_config, get_video_chapters, get_video_metadata, Chapter,
+	ExtractedFrame, VideoConfig, VideoMetadata,
 };
 
 #[cfg(feature = "transcription")]
@@ -21,6 +24,18 @@ use crate::transcribe::{transcribe_video, TranscriptionConfig, TranscriptionResu
 // Configuration
 // ============================================================================
 
+/// How embedded chapter markers are used as scene boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChapterBoundaryMode {
+	/// Don't read chapters; scene changes come from pHash detection only
+	#[default]
+	Ignore,
+	/// Chapter starts are the only scene boundaries (when the video has chapters)
+	Replace,
+	/// Chapter starts are added to the pHash-detected scene changes
+	Merge,
+}
+
 /// Configuration for the full video processing pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineConfig {
@@ -37,9 +52,16 @@ pub struct PipelineConfig {
 	/// Whether to run scene detection
 	pub enable_scene_detection: bool,
 
+	/// How chapter markers contribute to scene boundaries
+	pub chapter_boundaries: ChapterBoundaryMode,
+
 	/// Whether to skip transcription even if configured
 	#[cfg(feature = "transcription")]
 	pub skip_transcription: bool,
+
+	/// Give up on the whole pipeline after this many milliseconds
+	/// (`None` waits indefinitely)
+	pub timeout_ms: Option<u64>,
 }
 
 impl Default for PipelineConfig {
@@ -50,8 +72,10 @@ impl Default for PipelineConfig {
 			#[cfg(feature = "transcription")]
 			transcription: Some(TranscriptionConfig::default()),
 			enable_scene_detection: true,
+			chapter_boundaries: ChapterBoundaryMode::default(),
 			#[cfg(feature = "transcription")]
 			skip_transcription: false,
+			timeout_ms: None,
 		}
 	}
 }
@@ -69,6 +93,9 @@ pub struct VideoProcessingOutput {
 	/// Extracted frames with hashes and scene detection info
 	pub frames: Vec<FrameCandidate>,
 
+	/// Chapter markers (only read when `chapter_boundaries` isn't `Ignore`)
+	pub chapters: Vec<Chapter>,
+
 	/// Transcription result (if transcription was enabled and successful)
 	#[cfg(feature = "transcription")]
 	pub transcript: Option<TranscriptionResult>,
@@ -110,17 +137,47 @@ pub struct ProcessingStats {
 ///
 /// This runs frame extraction and transcription in parallel using `tokio::join!`.
 ///
+/// With `config.timeout_ms` set, processing is abandoned once the deadline
+/// passes and any `FFmpeg` process still running is killed. A Whisper run
+/// already in progress can't be interrupted and finishes in the background.
+///
 /// # Errors
 ///
 /// Returns an error if video metadata cannot be read, frame extraction fails,
-/// or transcription fails (when enabled).
+/// or transcription fails (when enabled), or [`PerceptionError::Timeout`] if
+/// the timeout expires first.
 #[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
 pub async fn process_video(
 	video_path: impl AsRef<Path>,
 	config: &PipelineConfig,
 ) -> Result<VideoProcessingOutput> {
-	let video_path = video_path.as_ref();
+	with_timeout(config.timeout_ms, run_pipeline(video_path.as_ref(), config)).await
+}
 
+/// Run `future`, failing with [`PerceptionError::Timeout`] if it hasn't
+/// finished within `timeout_ms`.
+///
+/// On expiry the future is dropped, which kills any child process it spawned
+/// with `kill_on_drop`.
+async fn with_timeout<T>(
+	timeout_ms: Option<u64>,
+	future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+	let Some(timeout_ms) = timeout_ms else {
+		return future.await;
+	};
+
+	tokio::time::timeout(Duration::from_millis(timeout_ms), future)
+		.await
+		.unwrap_or_else(|_| {
+			warn!(timeout_ms, "Video processing timed out");
+			Err(PerceptionError::Timeout {
+				seconds: timeout_ms.div_ceil(1000),
+			})
+		})
+}
+
+async fn run_pipeline(video_path: &Path, config: &PipelineConfig) -> Result<VideoProcessingOutput> {
 	// Get video metadata first
 	let metadata = get_video_metadata(video_path).await?;
 	debug!(?metadata, "Got video metadata");
@@ -141,7 +198,8 @@ pub async fn process_video(
 
 		let frames_task = async {
 			let start = std::time::Instant::now();
-			let result = extract_frames(video_path, &config.video).await;
+			let result =
+				extract_frames_with_scene_config(video_path, &config.video, &config.scene).await;
 			#[allow(clippy::cast_possible_truncation)]
 			let elapsed = start.elapsed().as_millis() as u64;
 			(result, elapsed)
@@ -178,7 +236,8 @@ pub async fn process_video(
 	#[cfg(not(feature = "transcription"))]
 	let frames_result = {
 		let start = std::time::Instant::now();
-		let result = extract_frames(video_path, &config.video).await;
+		let result =
+			extract_frames_with_scene_config(video_path, &config.video, &config.scene).await;
 		#[allow(clippy::cast_possible_truncation)]
 		let elapsed = start.elapsed().as_millis() as u64;
 		(result, elapsed)
@@ -207,6 +266,8 @@ pub async fn process_video(
 				is_scene_change: true, // Treat all as scene changes if detection disabled
 				is_duplicate: false,
 				distance_from_previous: 0,
+				scene_change_confidence: 1.0,
+				quality_score: 1.0, // Frames aren't decoded, so quality is unmeasured
 			})
 			.collect()
 	};
@@ -215,28 +276,40 @@ pub async fn process_video(
 		stats.scene_detection_time_ms = scene_start.elapsed().as_millis() as u64;
 	}
 
+	let mut frame_candidates = frame_candidates;
+	let chapters = if config.chapter_boundaries == ChapterBoundaryMode::Ignore {
+		Vec::new()
+	} else {
+		let chapters = get_video_chapters(video_path).await.unwrap_or_else(|e| {
+			warn!(?e, "Failed to read chapters, using scene detection only");
+			Vec::new()
+		});
+		apply_chapter_boundaries(&mut frame_candidates, &chapters, config.chapter_boundaries);
+		chapters
+	};
+
 	stats.scene_changes = frame_candidates
 		.iter()
 		.filter(|f| f.is_scene_change)
 		.count();
 	stats.duplicates = frame_candidates.iter().filter(|f| f.is_duplicate).count();
 
+	// The probe already listed the streams, so a missing audio track is known
+	// without waiting on (or interpreting) the transcription attempt
+	let no_audio = !metadata.has_audio;
+
 	// Process transcription result
 	#[cfg(feature = "transcription")]
-	let (transcript, no_audio) = {
+	let transcript = {
 		let (result, transcription_time) = transcript_result;
 		stats.transcription_time_ms = transcription_time;
 		match result {
-			Ok(Some(t)) => (Some(t), false),
-			Ok(None) => (None, !metadata.has_audio),
-			Err(e) if e.is_no_audio() => (None, true),
+			Ok(t) => t,
+			Err(e) if e.is_no_audio() => None,
 			Err(e) => return Err(e),
 		}
 	};
 
-	#[cfg(not(feature = "transcription"))]
-	let no_audio = !metadata.has_audio;
-
 	debug!(
 		frames = stats.frames_extracted,
 		scene_changes = stats.scene_changes,
@@ -247,6 +320,7 @@ pub async fn process_video(
 	Ok(VideoProcessingOutput {
 		metadata,
 		frames: frame_candidates,
+		chapters,
 		#[cfg(feature = "transcription")]
 		transcript,
 		no_audio,
@@ -254,6 +328,35 @@ pub async fn process_video(
 	})
 }
 
+/// Mark the first frame at or after each chapter start as a scene change.
+///
+/// With `Replace`, existing scene-change flags are cleared first (the first
+/// frame stays a boundary). Videos without chapters are left untouched.
+fn apply_chapter_boundaries(
+	frames: &mut [FrameCandidate],
+	chapters: &[Chapter],
+	mode: ChapterBoundaryMode,
+) {
+	if chapters.is_empty() || mode == ChapterBoundaryMode::Ignore {
+		return;
+	}
+
+	if mode == ChapterBoundaryMode::Replace {
+		for (i, frame) in frames.iter_mut().enumerate() {
+			frame.is_scene_change = i == 0;
+		}
+	}
+
+	for chapter in chapters {
+		if let Some(frame) = frames
+			.iter_mut()
+			.find(|f| f.frame.timestamp_seconds >= chapter.start_seconds)
+		{
+			frame.is_scene_change = true;
+		}
+	}
+}
+
 /// Synchronous wrapper for `process_video` (blocks the current thread).
 ///
 /// Use this when calling from a synchronous context. For async code,
@@ -291,6 +394,36 @@ mod tests {
 		assert_eq!(config.video.max_frames, 100);
 	}
 
+	#[tokio::test]
+	async fn test_with_timeout() {
+		let expired = with_timeout(Some(10), std::future::pending::<Result<()>>()).await;
+		assert!(matches!(
+			expired,
+			Err(PerceptionError::Timeout { seconds: 1 })
+		));
+
+		assert!(with_timeout(Some(10_000), async { Ok(()) }).await.is_ok());
+		assert!(with_timeout(None, async { Ok(()) }).await.is_ok());
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_timeout_kills_child_process() {
+		let start = std::time::Instant::now();
+		let result = with_timeout(Some(50), async {
+			tokio::process::Command::new("sleep")
+				.arg("30")
+				.kill_on_drop(true)
+				.status()
+				.await
+				.map_err(PerceptionError::IoError)
+		})
+		.await;
+
+		assert!(matches!(result, Err(PerceptionError::Timeout { .. })));
+		assert!(start.elapsed() < Duration::from_secs(10));
+	}
+
 	#[test]
 	fn test_processing_stats_default() {
 		let stats = ProcessingStats {
@@ -304,4 +437,75 @@ mod tests {
 
 		assert_eq!(stats.frames_extracted, 0);
 	}
+
+	fn frames_at(timestamps: &[f64], scene_changes: &[bool]) -> Vec<FrameCandidate> {
+		timestamps
+			.iter()
+			.zip(scene_changes)
+			.map(|(&timestamp_seconds, &is_scene_change)| FrameCandidate {
+				frame: ExtractedFrame {
+					path: std::path::PathBuf::new(),
+					timestamp_seconds,
+					frame_number: 0,
+					is_keyframe: false,
+				},
+				hash: crate::scene::PerceptualHash {
+					bytes: vec![],
+					hex: String::new(),
+				},
+				is_scene_change,
+				is_duplicate: false,
+				distance_from_previous: 0,
+				scene_change_confidence: 0.0,
+				quality_score: 1.0,
+			})
+			.collect()
+	}
+
+	fn chapter(start_seconds: f64, end_seconds: f64) -> Chapter {
+		Chapter {
+			title: String::new(),
+			start_seconds,
+			end_seconds,
+		}
+	}
+
+	fn scene_flags(frames: &[FrameCandidate]) -> Vec<bool> {
+		frames.iter().map(|f| f.is_scene_change).collect()
+	}
+
+	#[test]
+	fn test_chapter_boundaries_replace() {
+		let mut frames = frames_at(
+			&[0.0, 1.0, 2.0, 3.0, 4.0],
+			&[true, true, false, false, false],
+		);
+		let chapters = [chapter(0.0, 2.5), chapter(2.5, 5.0)];
+
+		apply_chapter_boundaries(&mut frames, &chapters, ChapterBoundaryMode::Replace);
+
+		assert_eq!(scene_flags(&frames), vec![true, false, false, true, false]);
+	}
+
+	#[test]
+	fn test_chapter_boundaries_merge() {
+		let mut frames = frames_at(
+			&[0.0, 1.0, 2.0, 3.0, 4.0],
+			&[true, true, false, false, false],
+		);
+		let chapters = [chapter(0.0, 2.5), chapter(2.5, 5.0)];
+
+		apply_chapter_boundaries(&mut frames, &chapters, ChapterBoundaryMode::Merge);
+
+		assert_eq!(scene_flags(&frames), vec![true, true, false, true, false]);
+	}
+
+	#[test]
+	fn test_chapter_boundaries_without_chapters() {
+		let mut frames = frames_at(&[0.0, 1.0], &[true, true]);
+
+		apply_chapter_boundaries(&mut frames, &[], ChapterBoundaryMode::Replace);
+
+		assert_eq!(scene_flags(&frames), vec![true, true]);
+	}
 }
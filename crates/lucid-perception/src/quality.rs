@@ -0,0 +1,130 @@
+//! Frame quality scoring.
+//!
+//! Blurry frames (motion blur, focus pulls, fades) make poor descriptions,
+//! so each frame gets a sharpness score that frame selection can weigh.
+//!
+//! ## Algorithm
+//!
+//! 1. Convert the frame to grayscale
+//! 2. Apply a 4-neighbour Laplacian, which responds to edges
+//! 3. Take the variance of the response: sharp frames have many strong
+//!    edges and a high variance, blurred frames a low one
+//! 4. Map the variance to 0-1 with `v / (v + SHARPNESS_MIDPOINT)`
+
+use std::path::Path;
+
+use image::DynamicImage;
+use tracing::instrument;
+
+use crate::error::Result;
+
+/// Laplacian variance that maps to a quality score of 0.5.
+///
+/// Around 100 is the usual cut-off between blurry and acceptable for 8-bit
+/// frames, so anything below it scores under 0.5.
+pub const SHARPNESS_MIDPOINT: f64 = 100.0;
+
+/// Compute a normalized sharpness score (0 = featureless or fully blurred,
+/// approaching 1 = very sharp) for an image file.
+///
+/// # Errors
+///
+/// Returns an error if the image cannot be read or decoded.
+#[instrument(skip_all, fields(path = %image_path.as_ref().display()))]
+pub fn compute_frame_quality(image_path: impl AsRef<Path>) -> Result<f64> {
+	let image = image::open(image_path.as_ref())?;
+	Ok(frame_quality(&image))
+}
+
+/// Sharpness score of an already-decoded image; see [`compute_frame_quality`].
+#[must_use]
+pub fn frame_quality(image: &DynamicImage) -> f64 {
+	let variance = laplacian_variance(image);
+	variance / (variance + SHARPNESS_MIDPOINT)
+}
+
+/// Variance of the 4-neighbour Laplacian over the image interior.
+///
+/// Images smaller than 3x3 have no interior and return 0.
+fn laplacian_variance(image: &DynamicImage) -> f64 {
+	let gray = image.to_luma8();
+	let (width, height) = gray.dimensions();
+	if width < 3 || height < 3 {
+		return 0.0;
+	}
+
+	let at = |x: u32, y: u32| f64::from(gray.get_pixel(x, y)[0]);
+
+	let mut sum = 0.0;
+	let mut sum_sq = 0.0;
+	for y in 1..height - 1 {
+		for x in 1..width - 1 {
+			let response = 4.0f64.mul_add(
+				-at(x, y),
+				at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1),
+			);
+			sum += response;
+			sum_sq = response.mul_add(response, sum_sq);
+		}
+	}
+
+	let count = f64::from((width - 2) * (height - 2));
+	let mean = sum / count;
+	mean.mul_add(-mean, sum_sq / count).max(0.0)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+	use super::*;
+	use image::{GrayImage, Luma};
+
+	/// 64x64 checkerboard of 4px squares: lots of hard edges.
+	fn checkerboard() -> DynamicImage {
+		DynamicImage::ImageLuma8(GrayImage::from_fn(64, 64, |x, y| {
+			if (x / 4 + y / 4) % 2 == 0 {
+				Luma([0])
+			} else {
+				Luma([255])
+			}
+		}))
+	}
+
+	#[test]
+	fn test_sharp_scores_higher_than_blurred() {
+		let sharp = checkerboard();
+		let blurred = sharp.blur(3.0);
+
+		let sharp_score = frame_quality(&sharp);
+		let blurred_score = frame_quality(&blurred);
+
+		assert!(sharp_score > 0.9);
+		assert!(blurred_score < sharp_score);
+		assert!((0.0..=1.0).contains(&blurred_score));
+	}
+
+	#[test]
+	fn test_flat_and_tiny_images_score_zero() {
+		let flat = DynamicImage::ImageLuma8(GrayImage::from_pixel(32, 32, Luma([128])));
+		assert!(frame_quality(&flat).abs() < f64::EPSILON);
+
+		let tiny = DynamicImage::ImageLuma8(GrayImage::from_pixel(2, 2, Luma([0])));
+		assert!(frame_quality(&tiny).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_compute_frame_quality_from_file() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let path = dir.path().join("checkerboard.png");
+		checkerboard().save(&path).expect("save checkerboard");
+
+		let score = compute_frame_quality(&path).expect("score saved frame");
+		assert!((score - frame_quality(&checkerboard())).abs() < 1e-12);
+
+		assert!(compute_frame_quality(dir.path().join("missing.png")).is_err());
+	}
+}
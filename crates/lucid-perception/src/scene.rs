@@ -12,11 +12,16 @@
 
 use std::path::Path;
 
-use image_hasher::{HashAlg, HasherConfig, ImageHash};
+use image_hasher::{HashAlg, Hasher, HasherConfig, ImageHash};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
+use zune_jpeg::zune_core::bytestream::ZCursor;
+use zune_jpeg::zune_core::colorspace::ColorSpace;
+use zune_jpeg::zune_core::options::DecoderOptions;
+use zune_jpeg::JpegDecoder;
 
 use crate::error::{PerceptionError, Result};
+use crate::quality::frame_quality;
 use crate::video::ExtractedFrame;
 
 // ============================================================================
@@ -32,19 +37,94 @@ pub struct SceneConfig {
 
 	/// Hamming distance threshold for scene change detection
 	/// Higher = fewer scene changes detected
-	pub scene_threshold: u32,
+	pub scene_threshold: f64,
 
 	/// Minimum distance to consider frames as duplicates
 	/// Lower = more aggressive duplicate detection
-	pub duplicate_threshold: u32,
+	pub duplicate_threshold: f64,
+
+	/// How `scene_threshold` and `duplicate_threshold` are interpreted
+	#[serde(default)]
+	pub threshold_mode: ThresholdMode,
+
+	/// Minimum seconds between scene changes
+	/// Changes closer than this to the previous one are merged into it (0 = off)
+	#[serde(default)]
+	pub min_scene_seconds: f64,
+
+	/// Minimum frames between scene changes
+	/// A change fewer frames after the previous one keeps its distance but is
+	/// not flagged, so flicker can't produce one-frame scenes (0 = off)
+	#[serde(default)]
+	pub min_scene_gap_frames: u32,
+
+	/// Smallest width and height in pixels a frame must have to be hashed
+	/// Tiny or truncated images hash to noise, so they are rejected (0 = off)
+	#[serde(default = "default_min_frame_dimension")]
+	pub min_frame_dimension: u32,
+
+	/// Decode frames to grayscale instead of full color
+	/// `DoubleGradient` only looks at luma, so JPEG frames are decoded
+	/// straight to the luma plane. The resize then runs on luma rather than
+	/// RGB, so hashes can differ slightly from color hashing
+	#[serde(default = "default_grayscale_hashing")]
+	pub grayscale_hashing: bool,
 }
 
 impl Default for SceneConfig {
 	fn default() -> Self {
 		Self {
-			hash_size: 8,           // 64-bit hash (8x8)
-			scene_threshold: 12,    // ~20% of bits different = scene change
-			duplicate_threshold: 3, // <=5% different = duplicate
+			hash_size: 8,             // 64-bit hash (8x8)
+			scene_threshold: 12.0,    // ~20% of bits different = scene change
+			duplicate_threshold: 3.0, // <=5% different = duplicate
+			threshold_mode: ThresholdMode::Absolute,
+			min_scene_seconds: 0.0,
+			min_scene_gap_frames: 0,
+			min_frame_dimension: MIN_FRAME_DIMENSION,
+			grayscale_hashing: GRAYSCALE_HASHING,
+		}
+	}
+}
+
+/// Default [`SceneConfig::min_frame_dimension`], also used by
+/// [`compute_phash`] and [`compute_phash_sized`].
+const MIN_FRAME_DIMENSION: u32 = 16;
+
+/// Serde default for [`SceneConfig::min_frame_dimension`] in older configs.
+const fn default_min_frame_dimension() -> u32 {
+	MIN_FRAME_DIMENSION
+}
+
+/// Default [`SceneConfig::grayscale_hashing`], also used by [`compute_phash`]
+/// and [`compute_phash_sized`].
+const GRAYSCALE_HASHING: bool = true;
+
+/// Serde default for [`SceneConfig::grayscale_hashing`] in older configs.
+const fn default_grayscale_hashing() -> bool {
+	GRAYSCALE_HASHING
+}
+
+/// Units of the scene and duplicate thresholds.
+///
+/// A 16x16 hash has four times the bits of an 8x8 one, so absolute bit counts
+/// tuned for one hash size are far too strict or too loose for the other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThresholdMode {
+	/// Thresholds are counts of differing bits
+	#[default]
+	Absolute,
+	/// Thresholds are fractions (0-1) of the hash's total bits
+	Fraction,
+}
+
+impl ThresholdMode {
+	/// Convert a threshold to a bit count for a hash of `total_bits` bits.
+	#[must_use]
+	pub fn bits(self, threshold: f64, total_bits: usize) -> f64 {
+		match self {
+			Self::Absolute => threshold,
+			#[allow(clippy::cast_precision_loss)]
+			Self::Fraction => threshold * total_bits as f64,
 		}
 	}
 }
@@ -71,6 +151,20 @@ impl PerceptualHash {
 		Self { bytes, hex }
 	}
 
+	/// Rebuild a hash from its [`hex`](Self::hex) string.
+	///
+	/// That string is `image_hasher`'s base64 encoding despite the field name,
+	/// so this decodes base64, not hexadecimal.
+	///
+	/// # Errors
+	///
+	/// Returns [`PerceptionError::InvalidHash`] if the string doesn't decode.
+	pub fn from_hex(hex: &str) -> Result<Self> {
+		ImageHash::<Box<[u8]>>::from_base64(hex)
+			.map(|hash| Self::from_image_hash(&hash))
+			.map_err(|_| PerceptionError::InvalidHash(hex.to_string()))
+	}
+
 	/// Compute Hamming distance to another hash.
 	#[must_use]
 	pub fn distance(&self, other: &Self) -> u32 {
@@ -82,42 +176,113 @@ impl PerceptualHash {
 ///
 /// # Errors
 ///
-/// Returns an error if the image cannot be read or decoded.
+/// Returns an error if the image cannot be read or decoded, or is smaller
+/// than 16 pixels on either side.
 #[instrument(skip_all, fields(path = %image_path.as_ref().display()))]
 pub fn compute_phash(image_path: impl AsRef<Path>) -> Result<PerceptualHash> {
-	let image_path = image_path.as_ref();
-
-	let image = image::open(image_path)?;
-
-	let hasher = HasherConfig::new()
-		.hash_alg(HashAlg::DoubleGradient)
-		.hash_size(8, 8)
-		.to_hasher();
-
-	let hash = hasher.hash_image(&image);
-
-	Ok(PerceptualHash::from_image_hash(&hash))
+	hash_image_file(
+		&build_hasher(8),
+		image_path.as_ref(),
+		MIN_FRAME_DIMENSION,
+		GRAYSCALE_HASHING,
+	)
 }
 
 /// Compute perceptual hash with custom size.
 ///
+/// Builds a new hasher on each call; to hash many frames, prefer
+/// [`detect_scene_changes`], which builds one and reuses it.
+///
 /// # Errors
 ///
-/// Returns an error if the image cannot be read or decoded.
+/// Returns an error if the image cannot be read or decoded, or is smaller
+/// than 16 pixels on either side.
 #[instrument(skip_all, fields(path = %image_path.as_ref().display(), size = hash_size))]
 pub fn compute_phash_sized(image_path: impl AsRef<Path>, hash_size: u32) -> Result<PerceptualHash> {
-	let image_path = image_path.as_ref();
-
-	let image = image::open(image_path)?;
+	hash_image_file(
+		&build_hasher(hash_size),
+		image_path.as_ref(),
+		MIN_FRAME_DIMENSION,
+		GRAYSCALE_HASHING,
+	)
+}
 
-	let hasher = HasherConfig::new()
+/// Build the hasher used for all frame hashes.
+///
+/// Hashers hold no per-image state, so one can be shared across any number
+/// of frames with the same `hash_size`.
+fn build_hasher(hash_size: u32) -> Hasher {
+	HasherConfig::new()
 		.hash_alg(HashAlg::DoubleGradient)
 		.hash_size(hash_size, hash_size)
-		.to_hasher();
+		.to_hasher()
+}
+
+fn hash_image_file(
+	hasher: &Hasher,
+	image_path: &Path,
+	min_dimension: u32,
+	grayscale: bool,
+) -> Result<PerceptualHash> {
+	let image = open_frame(image_path, min_dimension, grayscale)?;
+	Ok(hash_image(hasher, &image))
+}
+
+/// Open a frame image, rejecting ones smaller than `min_dimension` on
+/// either side. With `grayscale`, JPEG frames are decoded to luma only and
+/// other formats are converted after decoding.
+fn open_frame(
+	image_path: &Path,
+	min_dimension: u32,
+	grayscale: bool,
+) -> Result<image::DynamicImage> {
+	let luma = grayscale.then(|| decode_jpeg_luma(image_path)).flatten();
+	let image = if let Some(luma) = luma {
+		image::DynamicImage::ImageLuma8(luma)
+	} else {
+		let image = image::open(image_path)?;
+		if grayscale {
+			image::DynamicImage::ImageLuma8(image.to_luma8())
+		} else {
+			image
+		}
+	};
+	if image.width() < min_dimension || image.height() < min_dimension {
+		return Err(PerceptionError::InvalidFrame {
+			path: image_path.to_path_buf(),
+			width: image.width(),
+			height: image.height(),
+			min_dimension,
+		});
+	}
+	Ok(image)
+}
 
-	let hash = hasher.hash_image(&image);
+/// Decode a JPEG straight to its luma plane, skipping chroma upsampling and
+/// color conversion. Returns `None` for anything that isn't a JPEG the
+/// decoder can emit as luma, so the caller falls back to `image::open`.
+fn decode_jpeg_luma(image_path: &Path) -> Option<image::GrayImage> {
+	let bytes = std::fs::read(image_path).ok()?;
+	if !bytes.starts_with(&[0xFF, 0xD8]) {
+		return None;
+	}
+	let options = DecoderOptions::default().jpeg_set_out_colorspace(ColorSpace::Luma);
+	let mut decoder = JpegDecoder::new_with_options(ZCursor::new(&bytes), options);
+	decoder.decode_headers().ok()?;
+	if decoder.output_colorspace() != Some(ColorSpace::Luma) {
+		return None;
+	}
+	let (width, height) = decoder.dimensions()?;
+	let pixels = decoder.decode().ok()?;
+	image::GrayImage::from_raw(
+		u32::try_from(width).ok()?,
+		u32::try_from(height).ok()?,
+		pixels,
+	)
+}
 
-	Ok(PerceptualHash::from_image_hash(&hash))
+fn hash_image(hasher: &Hasher, image: &image::DynamicImage) -> PerceptualHash {
+	PerceptualHash::from_image_hash(&hasher.hash_image(image))
 }
 
 // ============================================================================
@@ -136,6 +301,23 @@ pub fn hamming_distance(a: &[u8], b: &[u8]) -> u32 {
 		.sum()
 }
 
+/// Find the candidate hash closest to `query`, for deduplicating a new frame
+/// against a stored library.
+///
+/// Returns the index and Hamming distance of the nearest candidate (the
+/// earliest on ties), or `None` if there are no candidates.
+#[must_use]
+pub fn find_nearest_hash(
+	query: &PerceptualHash,
+	candidates: &[PerceptualHash],
+) -> Option<(usize, u32)> {
+	candidates
+		.iter()
+		.map(|candidate| query.distance(candidate))
+		.enumerate()
+		.min_by_key(|&(_, distance)| distance)
+}
+
 // ============================================================================
 // Frame Candidate
 // ============================================================================
@@ -157,6 +339,14 @@ pub struct FrameCandidate {
 
 	/// Hamming distance from previous frame (0 for first frame)
 	pub distance_from_previous: u32,
+
+	/// Graded scene-change signal: the fraction of hash bits that differ
+	/// from the previous frame (0 for the first frame). Unlike
+	/// `is_scene_change`, this ignores thresholds and `min_scene_seconds`.
+	pub scene_change_confidence: f64,
+
+	/// Sharpness score (0-1, higher = sharper); see [`crate::quality`]
+	pub quality_score: f64,
 }
 
 // ============================================================================
@@ -169,7 +359,8 @@ pub struct FrameCandidate {
 ///
 /// # Errors
 ///
-/// Returns an error if any frame image cannot be read or hashed.
+/// Returns an error if any frame image cannot be read or hashed, or is
+/// smaller than `config.min_frame_dimension`.
 #[instrument(skip_all, fields(num_frames = frames.len()))]
 pub fn detect_scene_changes(
 	frames: &[ExtractedFrame],
@@ -179,45 +370,243 @@ pub fn detect_scene_changes(
 		return Ok(Vec::new());
 	}
 
-	let mut candidates = Vec::with_capacity(frames.len());
-	let mut previous_hash: Option<PerceptualHash> = None;
+	// Hashing and scoring are independent per frame; only the comparison
+	// against the previous frame needs to run in order
+	let analyzed = analyze_frames(frames, config)?;
 
-	for frame in frames {
-		let hash = compute_phash_sized(&frame.path, config.hash_size)?;
+	let mut detector = SceneDetector::new(config.clone());
+	let candidates: Vec<FrameCandidate> = frames
+		.iter()
+		.zip(analyzed)
+		.map(|(frame, (hash, quality_score))| detector.push_hash(frame, hash, quality_score))
+		.collect();
 
-		let (is_scene_change, is_duplicate, distance) = previous_hash.as_ref().map_or(
-			(true, false, 0), // First frame is always a scene boundary
-			|prev| {
-				let dist = hash.distance(prev);
-				(
-					dist >= config.scene_threshold,
-					dist <= config.duplicate_threshold,
-					dist,
-				)
-			},
-		);
+	let scene_changes = candidates.iter().filter(|c| c.is_scene_change).count();
+	let duplicates = candidates.iter().filter(|c| c.is_duplicate).count();
+	debug!(scene_changes, duplicates, "Scene detection complete");
+
+	Ok(candidates)
+}
+
+/// Decode, hash and score one frame.
+fn analyze_frame(
+	hasher: &Hasher,
+	frame: &ExtractedFrame,
+	config: &SceneConfig,
+) -> Result<(PerceptualHash, f64)> {
+	let image = open_frame(
+		&frame.path,
+		config.min_frame_dimension,
+		config.grayscale_hashing,
+	)?;
+	Ok((hash_image(hasher, &image), frame_quality(&image)))
+}
+
+/// Hash and score every frame across the rayon pool, one hasher per worker.
+#[cfg(feature = "parallel")]
+fn analyze_frames(
+	frames: &[ExtractedFrame],
+	config: &SceneConfig,
+) -> Result<Vec<(PerceptualHash, f64)>> {
+	use rayon::prelude::*;
+
+	frames
+		.par_iter()
+		.map_init(
+			|| build_hasher(config.hash_size),
+			|hasher, frame| analyze_frame(hasher, frame, config),
+		)
+		.collect()
+}
+
+/// Hash and score every frame in order with a single hasher.
+#[cfg(not(feature = "parallel"))]
+fn analyze_frames(
+	frames: &[ExtractedFrame],
+	config: &SceneConfig,
+) -> Result<Vec<(PerceptualHash, f64)>> {
+	let hasher = build_hasher(config.hash_size);
+	frames
+		.iter()
+		.map(|frame| analyze_frame(&hasher, frame, config))
+		.collect()
+}
+
+/// Incremental scene detector that processes one frame at a time.
+///
+/// Keeps only the previous frame's hash (plus the hasher and the start of the
+/// current scene), so frames can be fed as ffmpeg emits them without holding
+/// the whole video in memory. Produces the same candidates as
+/// [`detect_scene_changes`] over the same frames.
+pub struct SceneDetector {
+	config: SceneConfig,
+	hasher: Hasher,
+	previous_hash: Option<PerceptualHash>,
+	/// Timestamp of the last surviving scene change, for `min_scene_seconds`
+	last_scene_start: Option<f64>,
+	/// Position of the last surviving scene change, for `min_scene_gap_frames`
+	last_scene_frame: Option<u64>,
+	/// Frames pushed since creation or the last reset
+	frames_pushed: u64,
+}
+
+impl SceneDetector {
+	/// Create a detector. The first frame pushed is always a scene boundary.
+	#[must_use]
+	pub fn new(config: SceneConfig) -> Self {
+		Self {
+			hasher: build_hasher(config.hash_size),
+			config,
+			previous_hash: None,
+			last_scene_start: None,
+			last_scene_frame: None,
+			frames_pushed: 0,
+		}
+	}
+
+	/// The configuration this detector was built with.
+	#[must_use]
+	pub const fn config(&self) -> &SceneConfig {
+		&self.config
+	}
+
+	/// Hash and score one frame and classify it against the previous one.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the frame image cannot be read or decoded, or is
+	/// smaller than `min_frame_dimension`. The detector state is left
+	/// unchanged in that case.
+	pub fn push(&mut self, frame: &ExtractedFrame) -> Result<FrameCandidate> {
+		let (hash, quality_score) = analyze_frame(&self.hasher, frame, &self.config)?;
+		Ok(self.push_hash(frame, hash, quality_score))
+	}
+
+	/// Classify a frame whose hash and quality score were already computed.
+	///
+	/// The hash must have been computed with this detector's `hash_size`.
+	pub fn push_hash(
+		&mut self,
+		frame: &ExtractedFrame,
+		hash: PerceptualHash,
+		quality_score: f64,
+	) -> FrameCandidate {
+		let (mut is_scene_change, is_duplicate, distance, confidence) =
+			self.previous_hash.as_ref().map_or(
+				(true, false, 0, 0.0), // First frame is always a scene boundary
+				|prev| {
+					let dist = hash.distance(prev);
+					let total_bits = hash.bytes.len() * 8;
+					let mode = self.config.threshold_mode;
+					#[allow(clippy::cast_precision_loss)]
+					let confidence = if total_bits == 0 {
+						0.0
+					} else {
+						(f64::from(dist) / total_bits as f64).clamp(0.0, 1.0)
+					};
+					(
+						f64::from(dist) >= mode.bits(self.config.scene_threshold, total_bits),
+						f64::from(dist) <= mode.bits(self.config.duplicate_threshold, total_bits),
+						dist,
+						confidence,
+					)
+				},
+			);
+
+		// Merge changes that come too soon after the previous surviving one, so
+		// flashes and fast cuts collapse into one scene
+		if is_scene_change {
+			let timestamp = frame.timestamp_seconds;
+			let too_soon = self
+				.last_scene_start
+				.is_some_and(|start| timestamp - start < self.config.min_scene_seconds)
+				|| self.last_scene_frame.is_some_and(|last| {
+					self.frames_pushed - last < u64::from(self.config.min_scene_gap_frames)
+				});
+			if too_soon {
+				is_scene_change = false;
+			} else {
+				self.last_scene_start = Some(timestamp);
+				self.last_scene_frame = Some(self.frames_pushed);
+			}
+		}
+		self.frames_pushed += 1;
 
 		debug!(
 			frame = frame.frame_number,
 			distance, is_scene_change, is_duplicate, "Processed frame"
 		);
 
-		candidates.push(FrameCandidate {
+		self.previous_hash = Some(hash.clone());
+
+		FrameCandidate {
 			frame: frame.clone(),
-			hash: hash.clone(),
+			hash,
 			is_scene_change,
 			is_duplicate,
 			distance_from_previous: distance,
-		});
+			scene_change_confidence: confidence,
+			quality_score,
+		}
+	}
 
-		previous_hash = Some(hash);
+	/// Forget the previous frame, so the next frame starts a new scene.
+	pub fn reset(&mut self) {
+		self.previous_hash = None;
+		self.last_scene_start = None;
+		self.last_scene_frame = None;
+		self.frames_pushed = 0;
 	}
+}
 
-	let scene_changes = candidates.iter().filter(|c| c.is_scene_change).count();
-	let duplicates = candidates.iter().filter(|c| c.is_duplicate).count();
-	debug!(scene_changes, duplicates, "Scene detection complete");
+/// Running duplicate check against the last kept frame.
+///
+/// Unlike [`SceneDetector`], which compares each frame with the one before
+/// it, this compares with the last frame that was *kept*, so a slow drift
+/// across many near-identical frames still ends up keeping a frame once it
+/// has moved far enough.
+pub(crate) struct DuplicateFilter {
+	hasher: Hasher,
+	threshold: f64,
+	mode: ThresholdMode,
+	min_dimension: u32,
+	grayscale: bool,
+	last_kept: Option<PerceptualHash>,
+}
 
-	Ok(candidates)
+impl DuplicateFilter {
+	/// Create a filter using `config`'s hash size and duplicate threshold.
+	pub(crate) fn new(config: &SceneConfig) -> Self {
+		Self {
+			hasher: build_hasher(config.hash_size),
+			threshold: config.duplicate_threshold,
+			mode: config.threshold_mode,
+			min_dimension: config.min_frame_dimension,
+			grayscale: config.grayscale_hashing,
+			last_kept: None,
+		}
+	}
+
+	/// Whether the frame at `image_path` duplicates the last kept frame.
+	///
+	/// Frames that are not duplicates become the new reference. Frames that
+	/// can't be hashed are never treated as duplicates.
+	pub(crate) fn is_duplicate(&mut self, image_path: &Path) -> bool {
+		let Ok(hash) =
+			hash_image_file(&self.hasher, image_path, self.min_dimension, self.grayscale)
+		else {
+			return false;
+		};
+
+		let duplicate = self.last_kept.as_ref().is_some_and(|kept| {
+			let total_bits = hash.bytes.len() * 8;
+			f64::from(hash.distance(kept)) <= self.mode.bits(self.threshold, total_bits)
+		});
+		if !duplicate {
+			self.last_kept = Some(hash);
+		}
+		duplicate
+	}
 }
 
 /// Get only the scene change frames (filtering out duplicates and intermediate frames).
@@ -302,6 +691,7 @@ fn find_scene_representative(scene_frames: &[FrameCandidate]) -> Option<&FrameCa
 // ============================================================================
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
 	use super::*;
 
@@ -326,12 +716,301 @@ mod tests {
 		assert_eq!(hamming_distance(&a, &b), 4);
 	}
 
+	#[test]
+	fn test_find_nearest_hash() {
+		let hash = |bytes: Vec<u8>| PerceptualHash {
+			bytes,
+			hex: String::new(),
+		};
+		let query = hash(vec![0xFF, 0x00]);
+		let candidates = [
+			hash(vec![0x00, 0x00]), // 8 bits away
+			hash(vec![0xF0, 0x01]), // 5
+			hash(vec![0xFF, 0x03]), // 2
+			hash(vec![0x0F, 0xFF]), // 12
+			hash(vec![0xFC, 0x00]), // 2, but later
+		];
+
+		assert_eq!(find_nearest_hash(&query, &candidates), Some((2, 2)));
+		assert_eq!(find_nearest_hash(&query, &[]), None);
+	}
+
+	#[test]
+	fn test_hash_string_round_trip() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let path = dir.path().join("gradient.png");
+		let image = image::RgbImage::from_fn(32, 32, |x, y| {
+			image::Rgb([
+				u8::try_from(x * 8).unwrap_or(0),
+				u8::try_from(y * 8).unwrap_or(0),
+				64,
+			])
+		});
+		image.save(&path).expect("save gradient");
+
+		let hash = compute_phash(&path).expect("hashing a 32x32 image succeeds");
+		let parsed =
+			PerceptualHash::from_hex(&hash.hex).expect("a computed hash string parses back");
+		assert_eq!(parsed.bytes, hash.bytes);
+		assert!(matches!(
+			PerceptualHash::from_hex("not a hash!"),
+			Err(PerceptionError::InvalidHash(_))
+		));
+	}
+
 	#[test]
 	fn test_scene_config_default() {
 		let config = SceneConfig::default();
 		assert_eq!(config.hash_size, 8);
-		assert_eq!(config.scene_threshold, 12);
-		assert_eq!(config.duplicate_threshold, 3);
+		assert!((config.scene_threshold - 12.0).abs() < f64::EPSILON);
+		assert!((config.duplicate_threshold - 3.0).abs() < f64::EPSILON);
+		assert_eq!(config.threshold_mode, ThresholdMode::Absolute);
+		assert!(config.min_scene_seconds.abs() < f64::EPSILON);
+	}
+
+	/// Load the default config as JSON without the `removed` fields, as a
+	/// release from before those fields would have saved it.
+	fn older_scene_config(removed: &[&str]) -> SceneConfig {
+		let mut saved = serde_json::to_value(SceneConfig::default()).expect("serialize");
+		let fields = saved.as_object_mut().expect("config is an object");
+		for field in removed {
+			let _ = fields.remove(*field);
+		}
+		serde_json::from_value(saved).expect("deserialize")
+	}
+
+	#[test]
+	fn test_older_scene_configs_load() {
+		let loaded = older_scene_config(&[
+			"threshold_mode",
+			"min_scene_seconds",
+			"min_scene_gap_frames",
+			"min_frame_dimension",
+			"grayscale_hashing",
+		]);
+		assert_eq!(loaded.threshold_mode, ThresholdMode::Absolute);
+		assert!(loaded.min_scene_seconds.abs() < f64::EPSILON);
+		assert_eq!(loaded.min_scene_gap_frames, 0);
+		assert_eq!(loaded.min_frame_dimension, MIN_FRAME_DIMENSION);
+		assert_eq!(loaded.grayscale_hashing, GRAYSCALE_HASHING);
+	}
+
+	fn frame_at(timestamp_seconds: f64) -> ExtractedFrame {
+		ExtractedFrame {
+			path: std::path::PathBuf::from(format!("frame-{timestamp_seconds}.jpg")),
+			timestamp_seconds,
+			frame_number: 0,
+			is_keyframe: false,
+		}
+	}
+
+	/// Feed frames whose hashes alternate between all-zero and all-one bits,
+	/// so every frame after the first is a raw scene change.
+	fn alternating_scene_flags(timestamps: &[f64], min_scene_seconds: f64) -> Vec<bool> {
+		let mut detector = SceneDetector::new(SceneConfig {
+			min_scene_seconds,
+			..Default::default()
+		});
+		timestamps
+			.iter()
+			.enumerate()
+			.map(|(i, &timestamp)| {
+				let byte = if i % 2 == 0 { 0x00 } else { 0xFF };
+				let hash = PerceptualHash {
+					bytes: vec![byte; 8],
+					hex: String::new(),
+				};
+				detector
+					.push_hash(&frame_at(timestamp), hash, 1.0)
+					.is_scene_change
+			})
+			.collect()
+	}
+
+	fn candidate_with_hash(
+		timestamp_seconds: f64,
+		hash: u8,
+		is_scene_change: bool,
+	) -> FrameCandidate {
+		FrameCandidate {
+			frame: frame_at(timestamp_seconds),
+			hash: PerceptualHash {
+				bytes: vec![hash],
+				hex: String::new(),
+			},
+			is_scene_change,
+			is_duplicate: timestamp_seconds > 3.5,
+			distance_from_previous: 0,
+			scene_change_confidence: 0.0,
+			quality_score: 1.0,
+		}
+	}
+
+	#[test]
+	fn test_frame_filters() {
+		let candidates = vec![
+			candidate_with_hash(0.0, 0x00, true),
+			candidate_with_hash(1.0, 0x0F, false),
+			candidate_with_hash(2.0, 0x07, false),
+			candidate_with_hash(3.0, 0xF0, true),
+			candidate_with_hash(4.0, 0xF0, false),
+		];
+		let timestamps = |frames: Vec<&FrameCandidate>| {
+			frames
+				.iter()
+				.map(|f| f.frame.timestamp_seconds)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(timestamps(get_scene_frames(&candidates)), vec![0.0, 3.0]);
+		assert_eq!(
+			timestamps(get_unique_frames(&candidates)),
+			vec![0.0, 1.0, 2.0, 3.0]
+		);
+		// 0x0F is closest on average to the rest of the first scene
+		assert_eq!(
+			timestamps(get_representative_frames(&candidates)),
+			vec![1.0, 3.0]
+		);
+		assert!(get_representative_frames(&[]).is_empty());
+	}
+
+	#[test]
+	fn test_suppress_rapid_scene_changes() {
+		let flags = alternating_scene_flags(&[0.0, 5.0, 5.2, 5.4, 9.0], 1.0);
+		assert_eq!(flags, vec![true, true, false, false, true]);
+	}
+
+	#[test]
+	fn test_min_scene_gap_frames() {
+		let mut detector = SceneDetector::new(SceneConfig {
+			min_scene_gap_frames: 3,
+			..Default::default()
+		});
+		let candidates: Vec<FrameCandidate> = (0..4u8)
+			.map(|i| {
+				let hash = PerceptualHash {
+					bytes: vec![if i % 2 == 0 { 0x00 } else { 0xFF }; 8],
+					hex: String::new(),
+				};
+				detector.push_hash(&frame_at(f64::from(i)), hash, 1.0)
+			})
+			.collect();
+
+		let flags: Vec<bool> = candidates.iter().map(|c| c.is_scene_change).collect();
+		assert_eq!(flags, vec![true, false, false, true]);
+		// Suppressed changes still report how far they moved
+		assert!(candidates[1..]
+			.iter()
+			.all(|c| c.distance_from_previous == 64));
+	}
+
+	#[test]
+	fn test_suppress_rapid_scene_changes_disabled() {
+		let flags = alternating_scene_flags(&[0.0, 0.1, 0.2], 0.0);
+		assert!(flags.iter().all(|&f| f));
+	}
+
+	#[test]
+	fn test_scene_detector_duplicates_and_reset() {
+		let mut detector = SceneDetector::new(SceneConfig::default());
+		let hash = |bytes: Vec<u8>| PerceptualHash {
+			bytes,
+			hex: String::new(),
+		};
+
+		let first = detector.push_hash(&frame_at(0.0), hash(vec![0x00; 8]), 1.0);
+		let repeat = detector.push_hash(&frame_at(1.0), hash(vec![0x00; 8]), 1.0);
+		assert!(first.is_scene_change);
+		assert!(!repeat.is_scene_change);
+		assert!(repeat.is_duplicate);
+
+		detector.reset();
+		let after_reset = detector.push_hash(&frame_at(2.0), hash(vec![0x00; 8]), 1.0);
+		assert!(after_reset.is_scene_change);
+		assert_eq!(after_reset.distance_from_previous, 0);
+	}
+
+	#[test]
+	fn test_scene_change_confidence_rises_with_distance() {
+		let mut detector = SceneDetector::new(SceneConfig::default());
+		let hash = |bytes: Vec<u8>| PerceptualHash {
+			bytes,
+			hex: String::new(),
+		};
+
+		// Each frame differs from the previous in 4, 16, then 64 of 64 bits
+		let confidences: Vec<f64> = [
+			vec![0x00; 8],
+			vec![0x0F, 0, 0, 0, 0, 0, 0, 0],
+			vec![0xF0, 0xFF, 0, 0, 0, 0, 0, 0],
+			vec![0x0F, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+		]
+		.into_iter()
+		.zip(0u32..)
+		.map(|(bytes, i)| {
+			detector
+				.push_hash(&frame_at(f64::from(i)), hash(bytes), 1.0)
+				.scene_change_confidence
+		})
+		.collect();
+
+		assert_eq!(confidences, vec![0.0, 4.0 / 64.0, 16.0 / 64.0, 1.0]);
+	}
+
+	/// Scene change and duplicate flags for a frame whose hash differs from
+	/// the previous one in `differing_bits` of `hash_size * hash_size` bits.
+	fn classify(config: SceneConfig, differing_bits: usize) -> (bool, bool) {
+		let total_bytes = (config.hash_size * config.hash_size / 8) as usize;
+		let mut detector = SceneDetector::new(config);
+		let hash = |bytes: Vec<u8>| PerceptualHash {
+			bytes,
+			hex: String::new(),
+		};
+
+		let mut changed = vec![0x00; total_bytes];
+		for bit in 0..differing_bits {
+			changed[bit / 8] |= 1 << (bit % 8);
+		}
+
+		let _ = detector.push_hash(&frame_at(0.0), hash(vec![0x00; total_bytes]), 1.0);
+		let candidate = detector.push_hash(&frame_at(1.0), hash(changed), 1.0);
+		(candidate.is_scene_change, candidate.is_duplicate)
+	}
+
+	#[test]
+	fn test_absolute_thresholds_ignore_hash_size() {
+		let config = |hash_size| SceneConfig {
+			hash_size,
+			..Default::default()
+		};
+
+		// 12 differing bits is a scene change at either size
+		assert_eq!(classify(config(8), 12), (true, false));
+		assert_eq!(classify(config(16), 12), (true, false));
+		assert_eq!(classify(config(8), 3), (false, true));
+		assert_eq!(classify(config(16), 3), (false, true));
+	}
+
+	#[test]
+	fn test_fraction_thresholds_scale_with_hash_size() {
+		let config = |hash_size| SceneConfig {
+			hash_size,
+			scene_threshold: 0.2,
+			duplicate_threshold: 0.05,
+			threshold_mode: ThresholdMode::Fraction,
+			..Default::default()
+		};
+
+		// 8x8: 64 bits, so 13 bits is a change and 3 bits a duplicate
+		assert_eq!(classify(config(8), 13), (true, false));
+		assert_eq!(classify(config(8), 12), (false, false));
+		assert_eq!(classify(config(8), 3), (false, true));
+
+		// 16x16: 256 bits, so the same 13 bits is now well under 20%
+		assert_eq!(classify(config(16), 13), (false, false));
+		assert_eq!(classify(config(16), 52), (true, false));
+		assert_eq!(classify(config(16), 12), (false, true));
 	}
 
 	#[test]
@@ -348,4 +1027,173 @@ mod tests {
 		// 0xFF ^ 0xF0 = 0x0F (4 bits) + 0x00 ^ 0x0F = 0x0F (4 bits) = 8 bits
 		assert_eq!(hash1.distance(&hash2), 8);
 	}
+
+	/// Write `count` distinct 32x32 frames to `dir`.
+	fn write_frames(dir: &Path, count: u8) -> Vec<ExtractedFrame> {
+		(0..count)
+			.map(|i| {
+				let path = dir.join(format!("frame_{i}.png"));
+				let image = image::RgbImage::from_fn(32, 32, |x, y| {
+					let shade = u8::try_from((x * 8 + y * u32::from(i) * 4) % 256).unwrap_or(0);
+					image::Rgb([shade, shade.wrapping_mul(i), 255 - shade])
+				});
+				image.save(&path).expect("save frame");
+				ExtractedFrame {
+					path,
+					timestamp_seconds: f64::from(i),
+					frame_number: u32::from(i),
+					is_keyframe: false,
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_batch_detection_matches_incremental() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let frames = write_frames(dir.path(), 16);
+		assert_eq!(frames.len(), 16);
+
+		let config = SceneConfig::default();
+		let batch = detect_scene_changes(&frames, &config).expect("batch detection");
+
+		let mut detector = SceneDetector::new(config);
+		let incremental: Vec<FrameCandidate> = frames
+			.iter()
+			.map(|f| detector.push(f).expect("incremental detection"))
+			.collect();
+
+		assert_eq!(batch.len(), frames.len());
+		assert_eq!(batch.len(), incremental.len());
+		for (a, b) in batch.iter().zip(&incremental) {
+			assert_eq!(a.frame.path, b.frame.path);
+			assert_eq!(a.hash.bytes, b.hash.bytes);
+			assert_eq!(a.is_scene_change, b.is_scene_change);
+			assert_eq!(a.is_duplicate, b.is_duplicate);
+			assert_eq!(a.distance_from_previous, b.distance_from_previous);
+			assert!((a.quality_score - b.quality_score).abs() < f64::EPSILON);
+		}
+	}
+
+	#[test]
+	fn test_shared_hasher_matches_per_frame_hashes() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let frames = write_frames(dir.path(), 4);
+		assert_eq!(frames.len(), 4);
+
+		for hash_size in [8, 16] {
+			let config = SceneConfig {
+				hash_size,
+				..Default::default()
+			};
+			let candidates = detect_scene_changes(&frames, &config).expect("scene detection");
+			assert_eq!(candidates.len(), frames.len());
+
+			for candidate in &candidates {
+				let expected = compute_phash_sized(&candidate.frame.path, hash_size)
+					.expect("per-frame hash")
+					.bytes;
+				assert!(!expected.is_empty());
+				assert_eq!(candidate.hash.bytes, expected);
+			}
+		}
+	}
+
+	#[test]
+	fn test_grayscale_hashing_keeps_scene_decisions() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let mut frames = write_frames(dir.path(), 2);
+		assert_eq!(frames.len(), 2);
+		// A repeat of the second frame, so the pair yields a duplicate too
+		frames.push(ExtractedFrame {
+			timestamp_seconds: 2.0,
+			frame_number: 2,
+			..frames[1].clone()
+		});
+
+		let decisions = |grayscale_hashing| {
+			let config = SceneConfig {
+				grayscale_hashing,
+				..Default::default()
+			};
+			detect_scene_changes(&frames, &config)
+				.expect("scene detection")
+				.iter()
+				.map(|c| (c.is_scene_change, c.is_duplicate))
+				.collect::<Vec<_>>()
+		};
+
+		let color = decisions(false);
+		assert_eq!(color.len(), 3);
+		assert_eq!(decisions(true), color);
+	}
+
+	#[test]
+	fn test_jpeg_frames_decode_straight_to_luma() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let image = image::RgbImage::from_fn(40, 24, |x, y| {
+			let shade = u8::try_from((x * 6 + y * 3) % 256).unwrap_or(0);
+			image::Rgb([shade, 255 - shade, shade / 2])
+		});
+		let jpeg = dir.path().join("frame.jpg");
+		let png = dir.path().join("frame.png");
+		image.save(&jpeg).expect("save jpeg");
+		image.save(&png).expect("save png");
+
+		// The JPEG Y plane uses BT.601 weights, unlike `to_luma8`
+		let luma = decode_jpeg_luma(&jpeg).expect("jpeg decodes to luma");
+		let reference = image::open(&jpeg).expect("open jpeg").to_rgb8();
+		assert_eq!(luma.dimensions(), reference.dimensions());
+		let total_diff: f64 = luma
+			.pixels()
+			.zip(reference.pixels())
+			.map(|(y, rgb)| {
+				let [r, g, b] = rgb.0.map(f64::from);
+				let expected = 0.114_f64.mul_add(b, 0.299_f64.mul_add(r, 0.587 * g));
+				(f64::from(y.0[0]) - expected).abs()
+			})
+			.sum();
+		// Chroma subsampling blurs the RGB round trip, so compare on average
+		let mean_diff = total_diff / f64::from(luma.width() * luma.height());
+		assert!(mean_diff < 2.0, "luma differs by {mean_diff} on average");
+
+		assert!(decode_jpeg_luma(&png).is_none());
+		let frame = open_frame(&png, MIN_FRAME_DIMENSION, true).expect("png falls back");
+		assert!(matches!(frame, image::DynamicImage::ImageLuma8(_)));
+	}
+
+	#[test]
+	fn test_small_frames_are_rejected() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let path = dir.path().join("tiny.png");
+		image::RgbImage::from_pixel(4, 4, image::Rgb([128, 64, 32]))
+			.save(&path)
+			.expect("save tiny frame");
+		let frame = ExtractedFrame {
+			path: path.clone(),
+			timestamp_seconds: 0.0,
+			frame_number: 0,
+			is_keyframe: false,
+		};
+
+		assert!(matches!(
+			compute_phash(&path),
+			Err(PerceptionError::InvalidFrame {
+				width: 4,
+				height: 4,
+				min_dimension: 16,
+				..
+			})
+		));
+		assert!(matches!(
+			detect_scene_changes(std::slice::from_ref(&frame), &SceneConfig::default()),
+			Err(PerceptionError::InvalidFrame { .. })
+		));
+
+		let lenient = SceneConfig {
+			min_frame_dimension: 0,
+			..Default::default()
+		};
+		assert!(SceneDetector::new(lenient).push(&frame).is_ok());
+	}
 }
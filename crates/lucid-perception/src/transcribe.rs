@@ -4,6 +4,7 @@
 //! the whisper-rs bindings. It supports:
 //!
 //! - Audio extraction from video files
+//! - Direct transcription of audio files (WAV, MP3, M4A, ...)
 //! - Multiple Whisper model sizes
 //! - Timestamped transcript segments
 //!
@@ -12,13 +13,19 @@
 //! Whisper models are downloaded during installation to `~/.lucid/models/`.
 //! The default model is `ggml-base.en.bin` (English-only, ~74MB).
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, TryLockError};
 
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncReadExt;
 use tokio::process::Command;
 use tracing::{debug, instrument, warn};
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use whisper_rs::{
+	FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+	WhisperToken,
+};
 
 use crate::error::{PerceptionError, Result};
 
@@ -41,8 +48,20 @@ pub struct TranscriptionConfig {
 	/// Whether to translate to English
 	pub translate: bool,
 
-	/// Maximum segment length in characters
+	/// Maximum segment length in characters (0 = keep Whisper's own
+	/// segmentation); see [`resegment`]
 	pub max_segment_length: usize,
+
+	/// Zero-based audio track to transcribe (`None` = the default track)
+	pub audio_track_index: Option<u32>,
+
+	/// Collapse runs of whitespace in segment and full text; see
+	/// [`clean_transcript`]. Off by default so output matches Whisper's text
+	pub clean_segments: bool,
+
+	/// Strip bracketed non-speech annotations such as "[Music]" or
+	/// "(applause)"; see [`clean_transcript`]
+	pub filter_nonspeech: bool,
 }
 
 impl Default for TranscriptionConfig {
@@ -53,17 +72,84 @@ impl Default for TranscriptionConfig {
 			threads: 0,
 			translate: false,
 			max_segment_length: 0,
+			audio_track_index: None,
+			clean_segments: false,
+			filter_nonspeech: false,
 		}
 	}
 }
 
-/// Get the default Whisper model path.
-fn default_model_path() -> PathBuf {
+/// English-only base model, the one the install script downloads.
+const ENGLISH_MODEL_FILE: &str = "ggml-base.en.bin";
+
+/// Multilingual base model.
+const MULTILINGUAL_MODEL_FILE: &str = "ggml-base.bin";
+
+/// Directory the install script downloads Whisper models to.
+fn default_models_dir() -> PathBuf {
 	dirs::home_dir()
 		.unwrap_or_else(|| PathBuf::from("."))
 		.join(".lucid")
 		.join("models")
-		.join("ggml-base.en.bin")
+}
+
+/// Get the default Whisper model path.
+fn default_model_path() -> PathBuf {
+	default_models_dir().join(ENGLISH_MODEL_FILE)
+}
+
+/// Pick the base Whisper model in `base_dir` suited to `language`.
+///
+/// English uses the English-only model, falling back to the multilingual one
+/// when only that has been downloaded. Any other language, including
+/// `"auto"`, needs the multilingual model.
+#[must_use]
+pub fn resolve_model_for_language(base_dir: impl AsRef<Path>, language: &str) -> PathBuf {
+	let base_dir = base_dir.as_ref();
+	let multilingual = base_dir.join(MULTILINGUAL_MODEL_FILE);
+	if language != "en" {
+		return multilingual;
+	}
+
+	let english = base_dir.join(ENGLISH_MODEL_FILE);
+	if !english.exists() && multilingual.exists() {
+		multilingual
+	} else {
+		english
+	}
+}
+
+/// Whether `model_path` names an English-only model (`*.en.bin` and the like).
+fn is_english_only_model(model_path: &Path) -> bool {
+	model_path
+		.file_name()
+		.is_some_and(|name| name.to_string_lossy().contains(".en."))
+}
+
+/// Check that the configured model exists and can transcribe the configured
+/// language.
+///
+/// An English-only model given another language transcribes it as garbled
+/// English rather than failing, so that combination is rejected up front.
+/// `"auto"` is allowed: an English-only model simply detects English.
+fn check_model(config: &TranscriptionConfig) -> Result<()> {
+	if !config.model_path.exists() {
+		return Err(PerceptionError::WhisperModelNotFound(
+			config.model_path.clone(),
+		));
+	}
+
+	if config.language != "en"
+		&& config.language != "auto"
+		&& is_english_only_model(&config.model_path)
+	{
+		return Err(PerceptionError::ModelLanguageMismatch {
+			language: config.language.clone(),
+			model_path: config.model_path.clone(),
+		});
+	}
+
+	Ok(())
 }
 
 /// Get the URL to download the default model.
@@ -78,6 +164,139 @@ pub fn is_model_available(config: &TranscriptionConfig) -> bool {
 	config.model_path.exists()
 }
 
+/// Upper bound on suggested Whisper threads; whisper.cpp stops scaling past this.
+const MAX_SUGGESTED_WHISPER_THREADS: u32 = 8;
+
+/// Suggest a Whisper thread count for this machine.
+///
+/// Used when `TranscriptionConfig::threads` is 0. The standard library only
+/// reports logical CPUs, so the count is halved to approximate physical cores
+/// (Whisper is compute-bound and gains nothing from SMT siblings), then
+/// clamped to `1..=8` since whisper.cpp throughput plateaus beyond that.
+#[must_use]
+pub fn suggest_whisper_threads() -> u32 {
+	let logical = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+	let physical = u32::try_from(logical / 2).unwrap_or(u32::MAX);
+	physical.clamp(1, MAX_SUGGESTED_WHISPER_THREADS)
+}
+
+// ============================================================================
+// Model Cache
+// ============================================================================
+
+/// Loaded models keyed by the path they were loaded from.
+///
+/// Each path gets its own slot. The map lock is only held to find the slot,
+/// and the slot lock is held while its model loads, so concurrent first uses
+/// of one model load it once without blocking other models.
+struct ModelCache<T> {
+	slots: Mutex<HashMap<PathBuf, ModelSlot<T>>>,
+}
+
+/// A cached model, empty until its first load succeeds.
+type ModelSlot<T> = Arc<Mutex<Option<Arc<T>>>>;
+
+impl<T> Default for ModelCache<T> {
+	fn default() -> Self {
+		Self {
+			slots: Mutex::default(),
+		}
+	}
+}
+
+impl<T> ModelCache<T> {
+	/// Get the model for `path`, calling `load` if it isn't cached yet.
+	/// A failed load leaves the slot empty so a later call can retry.
+	fn get_or_load(&self, path: &Path, load: impl FnOnce() -> Result<T>) -> Result<Arc<T>> {
+		let slot = Arc::clone(
+			self.slots
+				.lock()
+				.unwrap_or_else(PoisonError::into_inner)
+				.entry(path.to_path_buf())
+				.or_default(),
+		);
+		let mut model = slot.lock().unwrap_or_else(PoisonError::into_inner);
+		if let Some(model) = model.as_ref() {
+			return Ok(Arc::clone(model));
+		}
+		let loaded = Arc::new(load()?);
+		*model = Some(Arc::clone(&loaded));
+		Ok(loaded)
+	}
+
+	/// Whether the model for `path` has finished loading.
+	fn contains(&self, path: &Path) -> bool {
+		let Some(slot) = self
+			.slots
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(path)
+			.cloned()
+		else {
+			return false;
+		};
+		// A slot that is locked is still loading
+		let loaded = match slot.try_lock() {
+			Ok(model) => model.is_some(),
+			Err(TryLockError::Poisoned(e)) => e.into_inner().is_some(),
+			Err(TryLockError::WouldBlock) => false,
+		};
+		loaded
+	}
+}
+
+/// Loaded Whisper models.
+///
+/// Loading a model reads and parses the whole file, which costs far more
+/// than transcribing a short clip, so every model is loaded once and shared.
+static WHISPER_MODELS: OnceLock<ModelCache<WhisperContext>> = OnceLock::new();
+
+/// Get the cached Whisper context for `model_path`, loading it on first use.
+fn whisper_context(model_path: &Path) -> Result<Arc<WhisperContext>> {
+	WHISPER_MODELS
+		.get_or_init(ModelCache::default)
+		.get_or_load(model_path, || {
+			if !model_path.exists() {
+				return Err(PerceptionError::WhisperModelNotFound(
+					model_path.to_path_buf(),
+				));
+			}
+
+			let ctx = WhisperContext::new_with_params(
+				model_path.to_str().ok_or_else(|| {
+					PerceptionError::TranscriptionFailed("Invalid model path".to_string())
+				})?,
+				WhisperContextParameters::default(),
+			)
+			.map_err(|e| {
+				PerceptionError::TranscriptionFailed(format!("Failed to load model: {e}"))
+			})?;
+
+			debug!(model = %model_path.display(), "Loaded Whisper model");
+			Ok(ctx)
+		})
+}
+
+/// Load a Whisper model into the cache ahead of the first transcription.
+///
+/// Transcription loads models on demand, so this is only needed to move the
+/// loading cost to startup. Loading an already-loaded model is a no-op.
+///
+/// # Errors
+///
+/// Returns an error if the model file is missing or can't be loaded.
+pub fn load_whisper_model(model_path: impl AsRef<Path>) -> Result<()> {
+	whisper_context(model_path.as_ref()).map(|_| ())
+}
+
+/// Check whether the Whisper model at `model_path` is already loaded.
+#[must_use]
+pub fn is_whisper_model_loaded(model_path: impl AsRef<Path>) -> bool {
+	WHISPER_MODELS
+		.get()
+		.is_some_and(|models| models.contains(model_path.as_ref()))
+}
+
 // ============================================================================
 // Transcript Types
 // ============================================================================
@@ -145,24 +364,282 @@ impl TranscriptionResult {
 	}
 
 	/// Get text within a time range.
+	///
+	/// Only segments that lie entirely inside the range are included; see
+	/// [`Self::text_overlapping_range`] to keep segments straddling its edges.
 	#[must_use]
 	pub fn text_in_range(&self, start_ms: i64, end_ms: i64) -> String {
+		self.joined_text(|s| s.start_ms >= start_ms && s.end_ms <= end_ms)
+	}
+
+	/// Get text of every segment that overlaps a time range.
+	///
+	/// Segments that merely touch the range (ending exactly at `start_ms` or
+	/// starting exactly at `end_ms`) are left out.
+	#[must_use]
+	pub fn text_overlapping_range(&self, start_ms: i64, end_ms: i64) -> String {
+		self.joined_text(|s| s.start_ms < end_ms && s.end_ms > start_ms)
+	}
+
+	/// Get text spoken within `window_ms` either side of `timestamp_ms`, for
+	/// giving a frame its surrounding speech.
+	#[must_use]
+	pub fn text_near(&self, timestamp_ms: i64, window_ms: i64) -> String {
+		let window_ms = window_ms.max(0);
+		self.text_overlapping_range(
+			timestamp_ms.saturating_sub(window_ms),
+			timestamp_ms.saturating_add(window_ms),
+		)
+	}
+
+	fn joined_text(&self, include: impl Fn(&TranscriptSegment) -> bool) -> String {
 		self.segments
 			.iter()
-			.filter(|s| s.start_ms >= start_ms && s.end_ms <= end_ms)
+			.filter(|s| include(s))
 			.map(|s| s.text.as_str())
 			.collect::<Vec<_>>()
 			.join(" ")
 	}
 }
 
+// ============================================================================
+// Resegmentation
+// ============================================================================
+
+/// [`resegment`] merges segments shorter than `max_chars / SHORT_SEGMENT_DIVISOR`.
+const SHORT_SEGMENT_DIVISOR: usize = 4;
+
+/// Re-cut a transcript so no segment is longer than `max_chars` characters.
+///
+/// Whisper's own segments range from one-word fragments to long run-ons.
+/// Segments under a quarter of `max_chars` are first merged into their
+/// neighbour when the combined text still fits; then overlong segments are
+/// split at word boundaries, each piece getting a share of the segment's
+/// time proportional to where it starts in the text. A single word longer
+/// than `max_chars` is kept whole. `max_chars` of 0 changes nothing.
+#[must_use]
+pub fn resegment(result: &TranscriptionResult, max_chars: usize) -> TranscriptionResult {
+	let segments = if max_chars == 0 {
+		result.segments.clone()
+	} else {
+		merge_short_segments(&result.segments, max_chars)
+			.into_iter()
+			.flat_map(|segment| split_segment(segment, max_chars))
+			.collect()
+	};
+
+	TranscriptionResult {
+		text: result.text.clone(),
+		segments,
+		detected_language: result.detected_language.clone(),
+		duration_seconds: result.duration_seconds,
+	}
+}
+
+/// Merge each very short segment into the previous one (or the previous
+/// very short segment into it) when the joined text fits in `max_chars`.
+fn merge_short_segments(
+	segments: &[TranscriptSegment],
+	max_chars: usize,
+) -> Vec<TranscriptSegment> {
+	let short = max_chars / SHORT_SEGMENT_DIVISOR;
+	let mut merged: Vec<TranscriptSegment> = Vec::with_capacity(segments.len());
+
+	for segment in segments {
+		let len = segment.text.chars().count();
+		match merged.last_mut() {
+			Some(previous) => {
+				let previous_len = previous.text.chars().count();
+				if (len < short || previous_len < short) && previous_len + 1 + len <= max_chars {
+					previous.confidence = merged_confidence(
+						(previous.confidence, previous_len),
+						(segment.confidence, len),
+					);
+					previous.text.push(' ');
+					previous.text.push_str(&segment.text);
+					previous.end_ms = segment.end_ms;
+				} else {
+					merged.push(segment.clone());
+				}
+			}
+			None => merged.push(segment.clone()),
+		}
+	}
+
+	merged
+}
+
+/// Length-weighted confidence of two merged segments.
+#[allow(clippy::cast_precision_loss)]
+fn merged_confidence(a: (Option<f32>, usize), b: (Option<f32>, usize)) -> Option<f32> {
+	match (a, b) {
+		((Some(a), a_len), (Some(b), b_len)) if a_len + b_len > 0 => {
+			Some(a.mul_add(a_len as f32, b * b_len as f32) / (a_len + b_len) as f32)
+		}
+		((confidence, _), (None, _)) | ((None, _), (confidence, _)) => confidence,
+		((Some(a), _), (Some(_), _)) => Some(a),
+	}
+}
+
+/// Split an overlong segment at word boundaries into pieces of at most
+/// `max_chars`, with proportionally interpolated timestamps.
+fn split_segment(segment: TranscriptSegment, max_chars: usize) -> Vec<TranscriptSegment> {
+	let total = segment.text.chars().count();
+	if total <= max_chars {
+		return vec![segment];
+	}
+
+	// Greedily pack words into pieces, remembering each piece's char offset
+	let mut pieces: Vec<(usize, String)> = Vec::new();
+	for (offset, word) in words_with_offsets(&segment.text) {
+		match pieces.last_mut() {
+			Some((_, piece)) if piece.chars().count() + 1 + word.chars().count() <= max_chars => {
+				piece.push(' ');
+				piece.push_str(word);
+			}
+			_ => pieces.push((offset, word.to_string())),
+		}
+	}
+
+	#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+	let time_at = |offset: usize| {
+		let duration = (segment.end_ms - segment.start_ms) as f64;
+		segment.start_ms + (duration * offset as f64 / total as f64).round() as i64
+	};
+	let boundaries: Vec<i64> = pieces
+		.iter()
+		.skip(1)
+		.map(|&(offset, _)| time_at(offset))
+		.chain(std::iter::once(segment.end_ms))
+		.collect();
+
+	pieces
+		.into_iter()
+		.zip(boundaries.iter().copied())
+		.scan(segment.start_ms, |start_ms, ((_, text), end_ms)| {
+			let piece = TranscriptSegment {
+				start_ms: *start_ms,
+				end_ms,
+				text,
+				confidence: segment.confidence,
+			};
+			*start_ms = end_ms;
+			Some(piece)
+		})
+		.collect()
+}
+
+/// Whitespace-separated words of `text` with their char offsets.
+fn words_with_offsets(text: &str) -> Vec<(usize, &str)> {
+	let mut words = Vec::new();
+	let mut word_start: Option<(usize, usize)> = None;
+
+	for (char_offset, (byte_offset, c)) in text.char_indices().enumerate() {
+		if c.is_whitespace() {
+			if let Some((start_char, start_byte)) = word_start.take() {
+				words.push((start_char, &text[start_byte..byte_offset]));
+			}
+		} else if word_start.is_none() {
+			word_start = Some((char_offset, byte_offset));
+		}
+	}
+	if let Some((start_char, start_byte)) = word_start {
+		words.push((start_char, &text[start_byte..]));
+	}
+
+	words
+}
+
+// ============================================================================
+// Cleanup
+// ============================================================================
+
+/// Tidy a transcript's text before it is embedded.
+///
+/// Whitespace in every segment is collapsed to single spaces and trimmed.
+/// With `filter_nonspeech`, annotations in square brackets or parentheses
+/// ("[Music]", "(applause)") are removed first; an opening bracket with no
+/// matching close is left alone. Segments left empty are dropped, and the
+/// full text is rebuilt from the remaining segments.
+#[must_use]
+pub fn clean_transcript(
+	result: &TranscriptionResult,
+	filter_nonspeech: bool,
+) -> TranscriptionResult {
+	let segments: Vec<TranscriptSegment> = result
+		.segments
+		.iter()
+		.filter_map(|segment| {
+			let text = if filter_nonspeech {
+				collapse_whitespace(&strip_annotations(&segment.text))
+			} else {
+				collapse_whitespace(&segment.text)
+			};
+			(!text.is_empty()).then(|| TranscriptSegment {
+				text,
+				..segment.clone()
+			})
+		})
+		.collect();
+
+	TranscriptionResult {
+		text: segments
+			.iter()
+			.map(|s| s.text.as_str())
+			.collect::<Vec<_>>()
+			.join(" "),
+		segments,
+		detected_language: result.detected_language.clone(),
+		duration_seconds: result.duration_seconds,
+	}
+}
+
+/// Replace each `[...]` and `(...)` span with a space.
+fn strip_annotations(text: &str) -> String {
+	let mut stripped = String::with_capacity(text.len());
+	let mut rest = text;
+
+	while let Some(open) = rest.find(['[', '(']) {
+		let close = if rest[open..].starts_with('[') {
+			']'
+		} else {
+			')'
+		};
+		let Some(len) = rest[open..].find(close) else {
+			break;
+		};
+		stripped.push_str(&rest[..open]);
+		stripped.push(' ');
+		rest = &rest[open + len + 1..];
+	}
+
+	stripped.push_str(rest);
+	stripped
+}
+
+/// Trim `text` and collapse internal whitespace runs to single spaces.
+fn collapse_whitespace(text: &str) -> String {
+	text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 // ============================================================================
 // Audio Extraction
 // ============================================================================
 
+/// `FFmpeg` arguments selecting an audio track (none selects the default track).
+fn audio_map_args(audio_track_index: Option<u32>) -> Vec<String> {
+	audio_track_index.map_or_else(Vec::new, |index| {
+		vec!["-map".to_string(), format!("0:a:{index}")]
+	})
+}
+
 /// Extract audio from a video file to WAV format for Whisper.
 #[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
-async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Path>) -> Result<()> {
+async fn extract_audio(
+	video_path: impl AsRef<Path>,
+	output_path: impl AsRef<Path>,
+	audio_track_index: Option<u32>,
+) -> Result<()> {
 	let video_path = video_path.as_ref();
 	let output_path = output_path.as_ref();
 
@@ -178,6 +655,7 @@ async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Pat
 			"-i",
 		])
 		.arg(video_path)
+		.args(audio_map_args(audio_track_index))
 		.args([
 			"-vn", // No video
 			"-acodec",
@@ -190,6 +668,7 @@ async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Pat
 		.arg(output_path)
 		.stdout(Stdio::null())
 		.stderr(Stdio::piped())
+		.kill_on_drop(true)
 		.output()
 		.await
 		.map_err(|_| PerceptionError::FfmpegNotFound)?;
@@ -197,6 +676,16 @@ async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Pat
 	if !output.status.success() {
 		let stderr = String::from_utf8_lossy(&output.stderr);
 
+		// A -map that selects nothing means the requested track doesn't exist
+		if let Some(index) = audio_track_index {
+			if stderr.contains("matches no streams") {
+				return Err(PerceptionError::AudioTrackNotFound {
+					path: video_path.to_path_buf(),
+					index,
+				});
+			}
+		}
+
 		// Check for "no audio" error
 		if stderr.contains("does not contain any stream")
 			|| stderr.contains("Output file is empty")
@@ -220,6 +709,90 @@ async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Pat
 	Ok(())
 }
 
+/// File extensions treated as audio-only input.
+const AUDIO_EXTENSIONS: [&str; 8] = ["wav", "mp3", "m4a", "aac", "flac", "ogg", "opus", "wma"];
+
+/// Whether a path looks like an audio-only file, judged by its extension.
+#[must_use]
+pub fn is_audio_file(path: impl AsRef<Path>) -> bool {
+	path.as_ref()
+		.extension()
+		.and_then(|ext| ext.to_str())
+		.is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Sample rate Whisper expects.
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// `audio_format` value for uncompressed PCM.
+const WAV_FORMAT_PCM: u16 = 1;
+
+/// Sample layout from a WAV file's `fmt ` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WavFormat {
+	audio_format: u16,
+	channels: u16,
+	sample_rate: u32,
+	bits_per_sample: u16,
+}
+
+impl WavFormat {
+	/// 16kHz mono 16-bit PCM, which Whisper can read as-is.
+	const fn is_whisper_ready(self) -> bool {
+		self.audio_format == WAV_FORMAT_PCM
+			&& self.channels == 1
+			&& self.sample_rate == WHISPER_SAMPLE_RATE
+			&& self.bits_per_sample == 16
+	}
+}
+
+/// Walk a WAV file's RIFF chunks, returning its format and its sample data.
+///
+/// The data chunk is cut short if `wav` is (e.g. only the header was read).
+/// Either is `None` if the chunk wasn't found or `wav` isn't a RIFF/WAVE file.
+fn wav_chunks(wav: &[u8]) -> (Option<WavFormat>, Option<&[u8]>) {
+	let mut format = None;
+	let mut samples = None;
+	if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+		return (format, samples);
+	}
+
+	let read_u16 = |at: usize| u16::from_le_bytes([wav[at], wav[at + 1]]);
+	let read_u32 = |at: usize| u32::from_le_bytes([wav[at], wav[at + 1], wav[at + 2], wav[at + 3]]);
+
+	let mut pos = 12;
+	while pos + 8 <= wav.len() && (format.is_none() || samples.is_none()) {
+		let chunk_size = read_u32(pos + 4) as usize;
+		let body = pos + 8;
+
+		match &wav[pos..pos + 4] {
+			b"fmt " if chunk_size >= 16 && body + 16 <= wav.len() => {
+				format = Some(WavFormat {
+					audio_format: read_u16(body),
+					channels: read_u16(body + 2),
+					sample_rate: read_u32(body + 4),
+					bits_per_sample: read_u16(body + 14),
+				});
+			}
+			b"data" => samples = Some(&wav[body..body.saturating_add(chunk_size).min(wav.len())]),
+			_ => {}
+		}
+
+		// Chunks are padded to a 2-byte boundary
+		pos = body.saturating_add(chunk_size + chunk_size % 2);
+	}
+
+	(format, samples)
+}
+
+/// Whether a WAV header describes 16kHz mono 16-bit PCM, which Whisper can
+/// read without transcoding.
+fn is_whisper_ready_wav(header: &[u8]) -> bool {
+	wav_chunks(header)
+		.0
+		.is_some_and(WavFormat::is_whisper_ready)
+}
+
 // ============================================================================
 // Transcription
 // ============================================================================
@@ -237,36 +810,78 @@ pub async fn transcribe_video(
 ) -> Result<TranscriptionResult> {
 	let video_path = video_path.as_ref();
 
-	// Check if model exists
-	if !config.model_path.exists() {
-		return Err(PerceptionError::WhisperModelNotFound(
-			config.model_path.clone(),
-		));
+	check_model(config)?;
+
+	debug!("Extracting audio from video");
+	transcribe_via_temp_wav(video_path, config).await
+}
+
+/// Transcribe an audio file (WAV, MP3, M4A, ...).
+///
+/// A WAV that is already 16kHz mono 16-bit PCM is handed to Whisper as-is;
+/// anything else is transcoded to that format first. Non-audio inputs fall
+/// back to the same demux path as [`transcribe_video`].
+///
+/// # Errors
+///
+/// Returns an error if the Whisper model is not found, the file can't be read
+/// or transcoded, or transcription fails.
+#[instrument(skip_all, fields(audio = %audio_path.as_ref().display()))]
+pub async fn transcribe_audio_file(
+	audio_path: impl AsRef<Path>,
+	config: &TranscriptionConfig,
+) -> Result<TranscriptionResult> {
+	let audio_path = audio_path.as_ref();
+
+	check_model(config)?;
+
+	// A WAV only has one track, so any other selection goes through FFmpeg
+	if is_audio_file(audio_path) && config.audio_track_index.unwrap_or(0) == 0 {
+		let mut header = [0u8; 4096];
+		let mut file = tokio::fs::File::open(audio_path).await?;
+		let len = file.read(&mut header).await?;
+
+		if is_whisper_ready_wav(&header[..len]) {
+			debug!("Audio is already 16kHz mono PCM, skipping transcode");
+			return run_whisper(audio_path.to_path_buf(), config).await;
+		}
 	}
 
+	debug!("Transcoding audio");
+	transcribe_via_temp_wav(audio_path, config).await
+}
+
+/// Convert any input with an audio stream to a temporary 16kHz mono WAV and
+/// transcribe it.
+async fn transcribe_via_temp_wav(
+	input_path: &Path,
+	config: &TranscriptionConfig,
+) -> Result<TranscriptionResult> {
 	// Create temp file for audio
 	let temp_dir = std::env::temp_dir().join("lucid-transcribe");
 	tokio::fs::create_dir_all(&temp_dir).await?;
 
 	let audio_path = temp_dir.join(format!("{}.wav", uuid::Uuid::new_v4()));
 
-	// Extract audio
-	debug!("Extracting audio from video");
-	extract_audio(video_path, &audio_path).await?;
-
-	// Clone paths for the closure and cleanup
-	let audio_path_for_cleanup = audio_path.clone();
+	extract_audio(input_path, &audio_path, config.audio_track_index).await?;
 
-	// Run transcription in blocking task (Whisper is CPU-bound)
-	let config = config.clone();
-	let result = tokio::task::spawn_blocking(move || transcribe_audio_sync(&audio_path, &config))
-		.await
-		.map_err(|e| PerceptionError::TranscriptionFailed(e.to_string()))??;
+	let result = run_whisper(audio_path.clone(), config).await;
 
 	// Clean up temp file
-	let _ = tokio::fs::remove_file(&audio_path_for_cleanup).await;
+	let _ = tokio::fs::remove_file(&audio_path).await;
 
-	Ok(result)
+	result
+}
+
+/// Run Whisper on a 16kHz mono WAV in a blocking task (Whisper is CPU-bound).
+async fn run_whisper(
+	audio_path: PathBuf,
+	config: &TranscriptionConfig,
+) -> Result<TranscriptionResult> {
+	let config = config.clone();
+	tokio::task::spawn_blocking(move || transcribe_audio_sync(&audio_path, &config))
+		.await
+		.map_err(|e| PerceptionError::TranscriptionFailed(e.to_string()))?
 }
 
 /// Synchronous transcription (for use in blocking context).
@@ -274,20 +889,13 @@ fn transcribe_audio_sync(
 	audio_path: &Path,
 	config: &TranscriptionConfig,
 ) -> Result<TranscriptionResult> {
-	// Load Whisper model
-	let ctx = WhisperContext::new_with_params(
-		config.model_path.to_str().ok_or_else(|| {
-			PerceptionError::TranscriptionFailed("Invalid model path".to_string())
-		})?,
-		WhisperContextParameters::default(),
-	)
-	.map_err(|e| PerceptionError::TranscriptionFailed(format!("Failed to load model: {e}")))?;
+	let ctx = whisper_context(&config.model_path)?;
 
 	// Read audio file
 	let audio_data = std::fs::read(audio_path)?;
 
 	// Parse WAV header and get samples
-	let samples = parse_wav_samples(&audio_data).map_err(PerceptionError::TranscriptionFailed)?;
+	let samples = parse_wav_samples(&audio_data)?;
 
 	// Create state
 	let mut state = ctx.create_state().map_err(|e| {
@@ -303,10 +911,12 @@ fn transcribe_audio_sync(
 	}
 
 	// Set thread count
-	if config.threads > 0 {
-		#[allow(clippy::cast_possible_wrap)]
-		params.set_n_threads(config.threads as i32);
-	}
+	let threads = if config.threads > 0 {
+		config.threads
+	} else {
+		suggest_whisper_threads()
+	};
+	params.set_n_threads(i32::try_from(threads).unwrap_or(i32::MAX));
 
 	// Enable translation if requested
 	params.set_translate(config.translate);
@@ -330,6 +940,9 @@ fn transcribe_audio_sync(
 	let mut segments = Vec::with_capacity(num_segments as usize);
 	let mut full_text = String::new();
 
+	// Token ids from end-of-text upward are special (timestamps, language, ...)
+	let token_eot = ctx.token_eot();
+
 	for i in 0..num_segments {
 		let start_ms = state.full_get_segment_t0(i).map_err(|e| {
 			PerceptionError::TranscriptionFailed(format!("Failed to get segment start: {e}"))
@@ -346,6 +959,8 @@ fn transcribe_audio_sync(
 		let text = text.trim().to_string();
 
 		if !text.is_empty() {
+			let confidence = segment_confidence(&state, i, token_eot);
+
 			if !full_text.is_empty() {
 				full_text.push(' ');
 			}
@@ -355,67 +970,151 @@ fn transcribe_audio_sync(
 				start_ms,
 				end_ms,
 				text,
-				confidence: None,
+				confidence,
 			});
 		}
 	}
 
 	#[allow(clippy::cast_precision_loss)]
-	let duration_seconds = samples.len() as f64 / 16000.0;
+	let duration_seconds = samples.len() as f64 / f64::from(WHISPER_SAMPLE_RATE);
 
-	Ok(TranscriptionResult {
+	let result = TranscriptionResult {
 		text: full_text,
 		segments,
 		detected_language: None,
 		duration_seconds,
+	};
+
+	let result = if config.clean_segments || config.filter_nonspeech {
+		clean_transcript(&result, config.filter_nonspeech)
+	} else {
+		result
+	};
+
+	Ok(if config.max_segment_length > 0 {
+		resegment(&result, config.max_segment_length)
+	} else {
+		result
 	})
 }
 
-/// Parse WAV file and extract f32 samples.
-fn parse_wav_samples(data: &[u8]) -> std::result::Result<Vec<f32>, String> {
-	// Simple WAV parser - expects 16-bit PCM, 16kHz, mono
+/// Average probability of a segment's text tokens, or `None` when the model
+/// reports none.
+fn segment_confidence(state: &WhisperState, segment: i32, token_eot: WhisperToken) -> Option<f32> {
+	let num_tokens = state.full_n_tokens(segment).ok()?;
+	let probabilities: Vec<f32> = (0..num_tokens)
+		.filter(|&t| {
+			state
+				.full_get_token_id(segment, t)
+				.is_ok_and(|id| id < token_eot)
+		})
+		.filter_map(|t| state.full_get_token_prob(segment, t).ok())
+		.collect();
+
+	average_token_probability(&probabilities)
+}
+
+/// Mean of the valid (finite, within [0, 1]) token probabilities.
+fn average_token_probability(probabilities: &[f32]) -> Option<f32> {
+	let (sum, count) = probabilities
+		.iter()
+		.filter(|p| p.is_finite() && (0.0..=1.0).contains(*p))
+		.fold((0.0_f64, 0_u32), |(sum, count), &p| {
+			(sum + f64::from(p), count + 1)
+		});
+
+	#[allow(clippy::cast_possible_truncation)]
+	(count > 0).then(|| (sum / f64::from(count)) as f32)
+}
+
+/// Parse a 16-bit PCM WAV file into 16kHz mono f32 samples.
+///
+/// Multi-channel audio is downmixed by averaging channels, and other sample
+/// rates are linearly resampled to 16kHz.
+fn parse_wav_samples(data: &[u8]) -> Result<Vec<f32>> {
 	if data.len() < 44 {
-		return Err("WAV file too short".to_string());
+		return Err(PerceptionError::TranscriptionFailed(
+			"WAV file too short".to_string(),
+		));
 	}
-
-	// Check RIFF header
 	if &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
-		return Err("Not a valid WAV file".to_string());
+		return Err(PerceptionError::TranscriptionFailed(
+			"Not a valid WAV file".to_string(),
+		));
 	}
 
-	// Find data chunk
-	let mut pos = 12;
-	while pos + 8 < data.len() {
-		let chunk_id = &data[pos..pos + 4];
-		#[allow(clippy::cast_possible_truncation)]
-		let chunk_size = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
-			as usize;
-
-		if chunk_id == b"data" {
-			let samples_start = pos + 8;
-			let samples_end = (samples_start + chunk_size).min(data.len());
-			let samples_data = &data[samples_start..samples_end];
-
-			// Convert i16 to f32
-			let samples: Vec<f32> = samples_data
-				.chunks_exact(2)
-				.map(|chunk| {
-					let sample = i16::from_le_bytes([chunk[0], chunk[1]]);
-					f32::from(sample) / 32768.0
-				})
-				.collect();
+	let (format, samples_data) = wav_chunks(data);
+	let format = format.ok_or_else(|| {
+		PerceptionError::TranscriptionFailed("No fmt chunk found in WAV file".to_string())
+	})?;
+	let samples_data = samples_data.ok_or_else(|| {
+		PerceptionError::TranscriptionFailed("No data chunk found in WAV file".to_string())
+	})?;
 
-			return Ok(samples);
-		}
+	if format.audio_format != WAV_FORMAT_PCM
+		|| format.bits_per_sample != 16
+		|| format.channels == 0
+		|| format.sample_rate == 0
+	{
+		return Err(PerceptionError::UnsupportedAudioFormat {
+			audio_format: format.audio_format,
+			channels: format.channels,
+			sample_rate: format.sample_rate,
+			bits_per_sample: format.bits_per_sample,
+		});
+	}
 
-		pos += 8 + chunk_size;
-		// Align to 2-byte boundary
-		if chunk_size % 2 != 0 {
-			pos += 1;
-		}
+	// Average each frame's channels into one sample
+	let channels = usize::from(format.channels);
+	let scale = 32768.0 * f32::from(format.channels);
+	let mono: Vec<f32> = samples_data
+		.chunks_exact(2 * channels)
+		.map(|frame| {
+			frame
+				.chunks_exact(2)
+				.map(|s| f32::from(i16::from_le_bytes([s[0], s[1]])))
+				.sum::<f32>()
+				/ scale
+		})
+		.collect();
+
+	Ok(resample_linear(
+		mono,
+		format.sample_rate,
+		WHISPER_SAMPLE_RATE,
+	))
+}
+
+/// Resample by linear interpolation between neighbouring samples.
+///
+/// There is no anti-aliasing filter, which is fine for speech recognition
+/// but not for anything that needs faithful high frequencies.
+fn resample_linear(samples: Vec<f32>, from_rate: u32, to_rate: u32) -> Vec<f32> {
+	if from_rate == to_rate || samples.is_empty() {
+		return samples;
 	}
 
-	Err("No data chunk found in WAV file".to_string())
+	let step = f64::from(from_rate) / f64::from(to_rate);
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		clippy::cast_precision_loss
+	)]
+	let output_len = (samples.len() as f64 / step).floor() as usize;
+
+	(0..output_len)
+		.map(|i| {
+			#[allow(clippy::cast_precision_loss)]
+			let position = i as f64 * step;
+			#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+			let index = position as usize;
+			#[allow(clippy::cast_possible_truncation)]
+			let fraction = (position - position.floor()) as f32;
+			let current = samples[index];
+			let next = samples.get(index + 1).copied().unwrap_or(current);
+			(next - current).mul_add(fraction, current)
+		})
+		.collect()
 }
 
 // ============================================================================
@@ -423,6 +1122,7 @@ fn parse_wav_samples(data: &[u8]) -> std::result::Result<Vec<f32>, String> {
 // ============================================================================
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
 	use super::*;
 
@@ -440,6 +1140,107 @@ mod tests {
 		assert!((segment.duration_seconds() - 1.5).abs() < f64::EPSILON);
 	}
 
+	fn transcript(segments: Vec<TranscriptSegment>) -> TranscriptionResult {
+		TranscriptionResult {
+			text: segments
+				.iter()
+				.map(|s| s.text.as_str())
+				.collect::<Vec<_>>()
+				.join(" "),
+			segments,
+			detected_language: None,
+			duration_seconds: 10.0,
+		}
+	}
+
+	#[test]
+	fn test_resegment_splits_long_segment_proportionally() {
+		let result = transcript(vec![TranscriptSegment {
+			start_ms: 1000,
+			end_ms: 5000,
+			text: "aaaa bbbb cccc dddd".to_string(), // 19 chars
+			confidence: Some(0.8),
+		}]);
+
+		let resegmented = resegment(&result, 10);
+		let pieces: Vec<(i64, i64, &str)> = resegmented
+			.segments
+			.iter()
+			.map(|s| (s.start_ms, s.end_ms, s.text.as_str()))
+			.collect();
+
+		// "cccc" starts at char 10 of 19, so 10/19 of the way through 4000ms
+		assert_eq!(
+			pieces,
+			vec![(1000, 3105, "aaaa bbbb"), (3105, 5000, "cccc dddd")]
+		);
+		assert!(resegmented
+			.segments
+			.iter()
+			.all(|s| s.confidence == Some(0.8)));
+		assert_eq!(resegmented.text, result.text);
+	}
+
+	#[test]
+	fn test_resegment_merges_short_segments() {
+		let segment = |start_ms, end_ms, text: &str| TranscriptSegment {
+			start_ms,
+			end_ms,
+			text: text.to_string(),
+			confidence: None,
+		};
+		let result = transcript(vec![
+			segment(0, 500, "Hi"),
+			segment(500, 2000, "there, friend"),
+			segment(2000, 4000, "how has your week been"),
+		]);
+
+		let resegmented = resegment(&result, 20);
+		let pieces: Vec<(i64, i64, &str)> = resegmented
+			.segments
+			.iter()
+			.map(|s| (s.start_ms, s.end_ms, s.text.as_str()))
+			.collect();
+
+		assert_eq!(
+			pieces,
+			vec![
+				(0, 2000, "Hi there, friend"),
+				(2000, 3636, "how has your week"),
+				(3636, 4000, "been"),
+			]
+		);
+		assert_eq!(resegment(&result, 0).segments.len(), 3);
+	}
+
+	#[test]
+	fn test_clean_transcript_removes_nonspeech() {
+		let segment = |start_ms, end_ms, text: &str| TranscriptSegment {
+			start_ms,
+			end_ms,
+			text: text.to_string(),
+			confidence: None,
+		};
+		let result = transcript(vec![
+			segment(0, 1000, "[Music]"),
+			segment(1000, 3000, "  so   we [Music] begin (applause) here "),
+			segment(3000, 4000, "an (unclosed aside"),
+		]);
+
+		let cleaned = clean_transcript(&result, true);
+		let texts: Vec<&str> = cleaned.segments.iter().map(|s| s.text.as_str()).collect();
+		assert_eq!(texts, vec!["so we begin here", "an (unclosed aside"]);
+		assert_eq!(cleaned.segments[0].start_ms, 1000);
+		assert_eq!(cleaned.text, "so we begin here an (unclosed aside");
+
+		let whitespace_only = clean_transcript(&result, false);
+		assert_eq!(whitespace_only.segments.len(), 3);
+		assert_eq!(
+			whitespace_only.segments[1].text,
+			"so we [Music] begin (applause) here"
+		);
+	}
+
 	#[test]
 	fn test_transcription_result_text_in_range() {
 		let result = TranscriptionResult {
@@ -466,12 +1267,295 @@ mod tests {
 		assert_eq!(result.text_in_range(0, 2000), "Hello world");
 	}
 
+	#[test]
+	fn test_transcription_result_text_overlapping_range() {
+		let segment = |start_ms, end_ms, text: &str| TranscriptSegment {
+			start_ms,
+			end_ms,
+			text: text.to_string(),
+			confidence: None,
+		};
+		let result = TranscriptionResult {
+			text: "one two three".to_string(),
+			segments: vec![
+				segment(0, 1500, "one"),
+				segment(1500, 2500, "two"),
+				segment(2500, 4000, "three"),
+			],
+			detected_language: None,
+			duration_seconds: 4.0,
+		};
+
+		// A 1-3s window straddles "one" and "three"
+		assert_eq!(result.text_in_range(1000, 3000), "two");
+		assert_eq!(result.text_overlapping_range(1000, 3000), "one two three");
+
+		// Touching the window edge isn't overlap
+		assert_eq!(result.text_overlapping_range(1500, 2500), "two");
+
+		assert_eq!(result.text_near(1200, 200), "one");
+		assert_eq!(result.text_near(2500, 100), "two three");
+		assert_eq!(result.text_near(10_000, 500), "");
+	}
+
 	#[test]
 	fn test_config_default() {
 		let config = TranscriptionConfig::default();
 		assert_eq!(config.language, "en");
 		assert_eq!(config.threads, 0);
 		assert!(!config.translate);
+		assert!(!config.clean_segments);
+		assert!(!config.filter_nonspeech);
+	}
+
+	#[test]
+	fn test_suggest_whisper_threads_in_range() {
+		let cores = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+		let suggested = suggest_whisper_threads();
+		assert!(suggested >= 1);
+		assert!(suggested as usize <= cores);
+	}
+
+	fn wav_header(channels: u16, sample_rate: u32, bits_per_sample: u16) -> Vec<u8> {
+		let mut header = Vec::new();
+		header.extend_from_slice(b"RIFF");
+		header.extend_from_slice(&36u32.to_le_bytes());
+		header.extend_from_slice(b"WAVE");
+		header.extend_from_slice(b"fmt ");
+		header.extend_from_slice(&16u32.to_le_bytes());
+		header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+		header.extend_from_slice(&channels.to_le_bytes());
+		header.extend_from_slice(&sample_rate.to_le_bytes());
+		let block_align = channels * bits_per_sample / 8;
+		header.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+		header.extend_from_slice(&block_align.to_le_bytes());
+		header.extend_from_slice(&bits_per_sample.to_le_bytes());
+		header.extend_from_slice(b"data");
+		header.extend_from_slice(&0u32.to_le_bytes());
+		header
+	}
+
+	/// A complete WAV file holding interleaved 16-bit `samples`.
+	fn wav_file(channels: u16, sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+		let mut wav = wav_header(channels, sample_rate, 16);
+		let data_len = u32::try_from(samples.len() * 2).unwrap_or(u32::MAX);
+		let size_at = wav.len() - 4;
+		wav[size_at..].copy_from_slice(&data_len.to_le_bytes());
+		for sample in samples {
+			wav.extend_from_slice(&sample.to_le_bytes());
+		}
+		wav
+	}
+
+	#[test]
+	fn test_parse_wav_samples_mono_16khz() {
+		let wav = wav_file(1, 16000, &[0, 16384, -16384]);
+		let samples = parse_wav_samples(&wav).expect("valid wav");
+		assert_eq!(samples, vec![0.0, 0.5, -0.5]);
+	}
+
+	#[test]
+	fn test_parse_wav_samples_downmixes_stereo() {
+		// Left/right pairs average to 0.5, 0.0 and -0.25
+		let wav = wav_file(2, 16000, &[16384, 16384, 16384, -16384, 0, -16384]);
+		let samples = parse_wav_samples(&wav).expect("valid wav");
+		assert_eq!(samples, vec![0.5, 0.0, -0.25]);
+	}
+
+	#[test]
+	fn test_parse_wav_samples_resamples() {
+		// 32kHz ramp halves in length at 16kHz, keeping every other sample
+		let ramp: Vec<i16> = (0..8).map(|i| i * 1024).collect();
+		let samples = parse_wav_samples(&wav_file(1, 32000, &ramp)).expect("valid wav");
+		assert_eq!(samples.len(), 4);
+		for (i, sample) in samples.iter().enumerate() {
+			assert!((sample - f32::from(ramp[i * 2]) / 32768.0).abs() < 1e-6);
+		}
+
+		// 8kHz doubles in length, interpolating between samples
+		let samples = parse_wav_samples(&wav_file(1, 8000, &[0, 1024])).expect("valid wav");
+		assert_eq!(samples.len(), 4);
+		assert!((samples[1] - 512.0 / 32768.0).abs() < 1e-6);
+	}
+
+	#[test]
+	fn test_parse_wav_samples_rejects_unsupported_formats() {
+		let mut eight_bit = wav_header(1, 16000, 8);
+		eight_bit.extend_from_slice(&[0; 16]);
+		assert!(matches!(
+			parse_wav_samples(&eight_bit),
+			Err(PerceptionError::UnsupportedAudioFormat {
+				bits_per_sample: 8,
+				..
+			})
+		));
+
+		let mut float = wav_file(1, 16000, &[0; 8]);
+		float[20..22].copy_from_slice(&3u16.to_le_bytes()); // IEEE float
+		assert!(matches!(
+			parse_wav_samples(&float),
+			Err(PerceptionError::UnsupportedAudioFormat {
+				audio_format: 3,
+				..
+			})
+		));
+
+		assert!(parse_wav_samples(b"not a wav file at all, just some bytes here").is_err());
+	}
+
+	#[test]
+	fn test_is_whisper_ready_wav() {
+		assert!(is_whisper_ready_wav(&wav_header(1, 16000, 16)));
+		assert!(!is_whisper_ready_wav(&wav_header(2, 16000, 16)));
+		assert!(!is_whisper_ready_wav(&wav_header(1, 44100, 16)));
+		assert!(!is_whisper_ready_wav(&wav_header(1, 16000, 8)));
+		assert!(!is_whisper_ready_wav(b"ID3\x04 not a wav file"));
+	}
+
+	#[test]
+	fn test_audio_map_args() {
+		assert!(audio_map_args(None).is_empty());
+		assert_eq!(audio_map_args(Some(0)), vec!["-map", "0:a:0"]);
+		assert_eq!(audio_map_args(Some(2)), vec!["-map", "0:a:2"]);
+	}
+
+	#[test]
+	fn test_is_audio_file() {
+		assert!(is_audio_file("voice-note.m4a"));
+		assert!(is_audio_file("/podcasts/Episode.MP3"));
+		assert!(!is_audio_file("clip.mp4"));
+		assert!(!is_audio_file("no-extension"));
+	}
+
+	#[test]
+	fn test_average_token_probability() {
+		let average = average_token_probability(&[0.9, 0.8, 0.7, 0.6]);
+		assert!(average.is_some_and(|p| (p - 0.75).abs() < 1e-6));
+
+		// Invalid probabilities are ignored
+		let average = average_token_probability(&[0.5, f32::NAN, 1.5, -0.1]);
+		assert!(average.is_some_and(|p| (p - 0.5).abs() < 1e-6));
+
+		assert!(average_token_probability(&[]).is_none());
+		assert!(average_token_probability(&[f32::NAN]).is_none());
+	}
+
+	#[test]
+	fn test_resolve_model_for_language() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let english = dir.path().join(ENGLISH_MODEL_FILE);
+		let multilingual = dir.path().join(MULTILINGUAL_MODEL_FILE);
+
+		assert_eq!(resolve_model_for_language(dir.path(), "en"), english);
+		assert_eq!(resolve_model_for_language(dir.path(), "de"), multilingual);
+		assert_eq!(resolve_model_for_language(dir.path(), "auto"), multilingual);
+
+		// English falls back to the multilingual model when it's all there is
+		assert!(std::fs::write(&multilingual, b"").is_ok());
+		assert_eq!(resolve_model_for_language(dir.path(), "en"), multilingual);
+		assert!(std::fs::write(&english, b"").is_ok());
+		assert_eq!(resolve_model_for_language(dir.path(), "en"), english);
+	}
+
+	#[test]
+	fn test_check_model_language() {
+		let dir = tempfile::tempdir().expect("temp dir");
+		let english = dir.path().join(ENGLISH_MODEL_FILE);
+		let multilingual = dir.path().join(MULTILINGUAL_MODEL_FILE);
+		assert!(std::fs::write(&english, b"").is_ok());
+		assert!(std::fs::write(&multilingual, b"").is_ok());
+
+		let config = |model_path: &Path, language: &str| TranscriptionConfig {
+			model_path: model_path.to_path_buf(),
+			language: language.to_string(),
+			..Default::default()
+		};
+
+		assert!(check_model(&config(&english, "en")).is_ok());
+		assert!(check_model(&config(&english, "auto")).is_ok());
+		assert!(matches!(
+			check_model(&config(&english, "de")),
+			Err(PerceptionError::ModelLanguageMismatch { .. })
+		));
+		assert!(check_model(&config(&multilingual, "de")).is_ok());
+		assert!(matches!(
+			check_model(&config(&dir.path().join("missing.bin"), "en")),
+			Err(PerceptionError::WhisperModelNotFound(_))
+		));
+	}
+
+	#[test]
+	fn test_whisper_context_reports_missing_model() {
+		let missing = std::env::temp_dir().join("lucid-missing-whisper-model.bin");
+		assert!(matches!(
+			load_whisper_model(&missing),
+			Err(PerceptionError::WhisperModelNotFound(_))
+		));
+		assert!(!is_whisper_model_loaded(&missing));
+	}
+
+	#[test]
+	fn test_model_cache_loads_once_per_path() {
+		let cache = ModelCache::default();
+		let path = Path::new("model-a.bin");
+		let loads = std::sync::atomic::AtomicUsize::new(0);
+		let load = || {
+			let _ = loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			Ok(7_u32)
+		};
+
+		assert!(!cache.contains(path));
+		let first = cache.get_or_load(path, load).expect("first load");
+		let second = cache.get_or_load(path, load).expect("cached load");
+		assert!(Arc::ptr_eq(&first, &second));
+		assert_eq!(loads.load(std::sync::atomic::Ordering::SeqCst), 1);
+		assert!(cache.contains(path));
+		assert!(!cache.contains(Path::new("model-b.bin")));
+	}
+
+	#[test]
+	fn test_model_cache_retries_failed_loads() {
+		let cache = ModelCache::default();
+		let path = Path::new("model.bin");
+
+		let failed = cache.get_or_load(path, || {
+			Err(PerceptionError::WhisperModelNotFound(path.to_path_buf()))
+		});
+		assert!(failed.is_err());
+		assert!(!cache.contains(path));
+
+		let loaded = cache.get_or_load(path, || Ok(1_u32)).expect("retry loads");
+		assert_eq!(*loaded, 1);
+		assert!(cache.contains(path));
+	}
+
+	#[test]
+	fn test_model_cache_load_does_not_block_other_paths() {
+		let cache = ModelCache::default();
+		let slow = Path::new("slow.bin");
+		let (started_tx, started_rx) = std::sync::mpsc::channel();
+		let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+		std::thread::scope(|scope| {
+			let cache = &cache;
+			let loading = scope.spawn(move || {
+				cache.get_or_load(slow, || {
+					started_tx.send(()).expect("signal start");
+					release_rx.recv().expect("wait for release");
+					Ok(1_u32)
+				})
+			});
+			started_rx.recv().expect("slow load started");
+
+			// The slow model is mid-load, yet another path loads right away
+			assert!(!cache.contains(slow));
+			let fast = cache.get_or_load(Path::new("fast.bin"), || Ok(2_u32));
+			assert_eq!(*fast.expect("fast load"), 2);
+
+			release_tx.send(()).expect("release slow load");
+			assert_eq!(*loading.join().expect("join").expect("slow load"), 1);
+		});
+		assert!(cache.contains(slow));
 	}
 
 	#[test]
@@ -15,6 +15,8 @@ use tokio::process::Command;
 use tracing::{debug, instrument, warn};
 
 use crate::error::{PerceptionError, Result};
+use crate::quality::compute_frame_quality;
+use crate::scene::{DuplicateFilter, SceneConfig};
 
 // ============================================================================
 // Configuration
@@ -26,23 +28,57 @@ pub struct VideoConfig {
 	/// Output directory for extracted frames
 	pub output_dir: PathBuf,
 
-	/// Maximum frames to extract (0 = all)
+	/// Maximum frames to extract (0 = all); with `FixedCount` sampling, the
+	/// number of frames to spread across the video
 	pub max_frames: usize,
 
-	/// Time interval between frames in seconds (0 = use scene detection)
+	/// Time interval between frames in seconds, for `Interval` sampling
 	pub interval_seconds: f64,
 
-	/// Output image quality (1-31, lower is better, 2 is recommended)
+	/// Output image quality (1-31, lower is better, 2 is recommended).
+	/// Mapped onto the encoder's own scale for formats that use a different one.
 	pub quality: u32,
 
 	/// Output image format
 	pub format: ImageFormat,
 
+	/// Which timestamps frames are taken from
+	#[serde(default)]
+	pub sampling: FrameSamplingStrategy,
+
 	/// Whether to extract keyframes only (faster, less frames)
+	///
+	/// Overrides `sampling` when set; see [`VideoConfig::sampling_strategy`].
+	#[deprecated(note = "use `sampling: FrameSamplingStrategy::Keyframes` instead")]
+	#[serde(default)]
 	pub keyframes_only: bool,
+
+	/// Longest allowed side of extracted frames in pixels (`None` = source
+	/// resolution). Larger frames are downscaled, keeping their aspect ratio.
+	pub max_dimension: Option<u32>,
+
+	/// Start of the extracted range in seconds (`None` = start of the video)
+	pub start_seconds: Option<f64>,
+
+	/// End of the extracted range in seconds (`None` = end of the video)
+	pub end_seconds: Option<f64>,
+
+	/// Drop frames that are near-duplicates of the last kept frame as they
+	/// are extracted, using the scene config's duplicate threshold
+	#[serde(default)]
+	pub dedup_during_extraction: bool,
+
+	/// zlib compression level for PNG output, 0-9 (`None` = `FFmpeg`'s
+	/// default). Higher is smaller and slower; the image is identical.
+	pub png_compression: Option<u32>,
+
+	/// Chroma subsampling for JPEG output: "444", "422" or "420", with or
+	/// without colons (`None` or anything else = the encoder's 4:2:0)
+	pub jpeg_subsampling: Option<String>,
 }
 
 impl Default for VideoConfig {
+	#[allow(deprecated)]
 	fn default() -> Self {
 		Self {
 			output_dir: std::env::temp_dir().join("lucid-frames"),
@@ -50,11 +86,100 @@ impl Default for VideoConfig {
 			interval_seconds: 1.0,
 			quality: 2,
 			format: ImageFormat::Jpeg,
+			sampling: FrameSamplingStrategy::default(),
 			keyframes_only: false,
+			max_dimension: None,
+			start_seconds: None,
+			end_seconds: None,
+			dedup_during_extraction: false,
+			png_compression: None,
+			jpeg_subsampling: None,
+		}
+	}
+}
+
+impl VideoConfig {
+	/// The sampling strategy extraction uses: `Keyframes` when the deprecated
+	/// `keyframes_only` is set, `sampling` otherwise.
+	#[must_use]
+	pub const fn sampling_strategy(&self) -> FrameSamplingStrategy {
+		#[allow(deprecated)]
+		if self.keyframes_only {
+			FrameSamplingStrategy::Keyframes
+		} else {
+			self.sampling
+		}
+	}
+
+	/// The `(start, end)` range frames are taken from, clamped to the video.
+	///
+	/// An empty range (`start >= end`) yields no frames.
+	#[must_use]
+	pub fn time_window(&self, duration_seconds: f64) -> (f64, f64) {
+		let duration = duration_seconds.max(0.0);
+		let start = self.start_seconds.unwrap_or(0.0).clamp(0.0, duration);
+		let end = self.end_seconds.unwrap_or(duration).clamp(0.0, duration);
+		(start, end)
+	}
+
+	/// `FFmpeg` output options for the configured format: `-q:v`, plus
+	/// `-compression_level` for PNG and `-pix_fmt` for JPEG when set.
+	#[must_use]
+	pub fn encoder_args(&self) -> Vec<String> {
+		let mut args = vec![
+			"-q:v".to_string(),
+			self.format.quality_arg(self.quality).to_string(),
+		];
+		match self.format {
+			ImageFormat::Png => {
+				if let Some(level) = self.png_compression {
+					args.push("-compression_level".to_string());
+					args.push(level.min(9).to_string());
+				}
+			}
+			ImageFormat::Jpeg => {
+				if let Some(pix_fmt) = self.jpeg_subsampling.as_deref().and_then(jpeg_pix_fmt) {
+					args.push("-pix_fmt".to_string());
+					args.push(pix_fmt.to_string());
+				}
+			}
+			ImageFormat::Webp => {}
 		}
+		args
 	}
 }
 
+/// Full-range `MJPEG` pixel format for a chroma subsampling such as "4:4:4".
+fn jpeg_pix_fmt(subsampling: &str) -> Option<&'static str> {
+	match subsampling.replace(':', "").as_str() {
+		"444" => Some("yuvj444p"),
+		"422" => Some("yuvj422p"),
+		"420" => Some("yuvj420p"),
+		_ => None,
+	}
+}
+
+/// How `extract_frames` chooses which frames to take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FrameSamplingStrategy {
+	/// One frame every `interval_seconds`, up to `max_frames`
+	#[default]
+	Interval,
+	/// `max_frames` frames evenly spaced across the time window, whatever its
+	/// length (falls back to `Interval` when `max_frames` is 0)
+	FixedCount,
+	/// Keyframes only (faster, fewer frames)
+	Keyframes,
+}
+
+/// `FFmpeg` filter that fits frames within a `max_dimension` square, never
+/// upscaling and keeping the aspect ratio.
+fn scale_filter(max_dimension: u32) -> String {
+	format!(
+		"scale='min({max_dimension},iw)':'min({max_dimension},ih)':force_original_aspect_ratio=decrease"
+	)
+}
+
 /// Output image format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum ImageFormat {
@@ -63,6 +188,8 @@ pub enum ImageFormat {
 	Jpeg,
 	/// PNG format (larger files, lossless)
 	Png,
+	/// `WebP` format (smaller than JPEG at similar quality, lossy)
+	Webp,
 }
 
 impl ImageFormat {
@@ -72,6 +199,7 @@ impl ImageFormat {
 		match self {
 			Self::Jpeg => "jpg",
 			Self::Png => "png",
+			Self::Webp => "webp",
 		}
 	}
 
@@ -81,6 +209,36 @@ impl ImageFormat {
 		match self {
 			Self::Jpeg => "mjpeg",
 			Self::Png => "png",
+			Self::Webp => "libwebp",
+		}
+	}
+
+	/// The `-q:v` value for a [`VideoConfig::quality`] of `quality`.
+	///
+	/// JPEG uses the 1-31 scale directly (lower is better). `libwebp` takes
+	/// 0-100 (higher is better), so 1 maps to 100 and 31 to 0. PNG ignores it.
+	#[must_use]
+	pub fn quality_arg(&self, quality: u32) -> u32 {
+		match self {
+			Self::Jpeg | Self::Png => quality,
+			Self::Webp => {
+				let clamped = quality.clamp(1, 31);
+				(100 * (31 - clamped) + 15) / 30
+			}
+		}
+	}
+
+	/// Rough encoded size per pixel, used for extraction size estimates.
+	///
+	/// JPEG at `-q:v 2` lands around 0.25 bytes/pixel for typical footage and
+	/// shrinks roughly in proportion to the quality value. `WebP` comes in around
+	/// a third smaller at the same setting. PNG is lossless and ignores quality.
+	#[must_use]
+	pub fn estimated_bytes_per_pixel(&self, quality: u32) -> f64 {
+		match self {
+			Self::Jpeg => 0.5 / f64::from(quality.max(1)),
+			Self::Webp => 0.33 / f64::from(quality.max(1)),
+			Self::Png => 1.5,
 		}
 	}
 }
@@ -147,6 +305,167 @@ struct FfprobeOutput {
 	format: Option<FfprobeFormat>,
 }
 
+/// Raw `FFprobe` chapter data.
+#[derive(Debug, Deserialize)]
+struct FfprobeChapter {
+	#[serde(default)]
+	start_time: Option<String>,
+	#[serde(default)]
+	end_time: Option<String>,
+	#[serde(default)]
+	tags: Option<FfprobeChapterTags>,
+}
+
+/// Raw `FFprobe` chapter tags.
+#[derive(Debug, Deserialize)]
+struct FfprobeChapterTags {
+	#[serde(default)]
+	title: Option<String>,
+}
+
+/// Raw `FFprobe` chapters output.
+#[derive(Debug, Deserialize)]
+struct FfprobeChapters {
+	#[serde(default)]
+	chapters: Vec<FfprobeChapter>,
+}
+
+/// Raw `FFprobe` frame data.
+#[derive(Debug, Deserialize)]
+struct FfprobeFrame {
+	#[serde(default)]
+	key_frame: Option<u8>,
+	#[serde(default)]
+	pts_time: Option<String>,
+	#[serde(default)]
+	best_effort_timestamp_time: Option<String>,
+}
+
+/// Raw `FFprobe` frames output.
+#[derive(Debug, Deserialize)]
+struct FfprobeFrames {
+	#[serde(default)]
+	frames: Vec<FfprobeFrame>,
+}
+
+/// Parse `ffprobe -show_frames -of json` output into keyframe timestamps.
+///
+/// Frames without a usable timestamp are skipped, as are frames explicitly
+/// marked as non-key.
+fn parse_keyframe_timestamps(json: &str) -> Result<Vec<f64>> {
+	let probe: FfprobeFrames = serde_json::from_str(json)
+		.map_err(|e: serde_json::Error| PerceptionError::JsonParseError(e.to_string()))?;
+
+	Ok(probe
+		.frames
+		.into_iter()
+		.filter(|f| f.key_frame != Some(0))
+		.filter_map(|f| {
+			f.pts_time
+				.or(f.best_effort_timestamp_time)
+				.and_then(|t| t.parse::<f64>().ok())
+		})
+		.collect())
+}
+
+/// Enumerate the presentation timestamps of the video's keyframes.
+async fn probe_keyframe_timestamps(video_path: &Path) -> Result<Vec<f64>> {
+	let output = Command::new("ffprobe")
+		.args([
+			"-v",
+			"error",
+			"-select_streams",
+			"v:0",
+			"-skip_frame",
+			"nokey",
+			"-show_frames",
+			"-show_entries",
+			"frame=key_frame,pts_time,best_effort_timestamp_time",
+			"-of",
+			"json",
+		])
+		.arg(video_path)
+		.kill_on_drop(true)
+		.output()
+		.await
+		.map_err(|_| PerceptionError::FfprobeNotFound)?;
+
+	if !output.status.success() {
+		return Err(PerceptionError::InvalidVideo(video_path.to_path_buf()));
+	}
+
+	parse_keyframe_timestamps(&String::from_utf8_lossy(&output.stdout))
+}
+
+// ============================================================================
+// Chapters
+// ============================================================================
+
+/// A chapter marker embedded in the video container.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+	/// Chapter title ("Chapter N" when the container has none)
+	pub title: String,
+
+	/// Start time in seconds
+	pub start_seconds: f64,
+
+	/// End time in seconds
+	pub end_seconds: f64,
+}
+
+/// Parse `ffprobe -show_chapters -of json` output.
+fn parse_chapters(json: &str) -> Result<Vec<Chapter>> {
+	let probe: FfprobeChapters = serde_json::from_str(json)
+		.map_err(|e: serde_json::Error| PerceptionError::JsonParseError(e.to_string()))?;
+
+	let parse_time = |t: Option<&String>| t.and_then(|t| t.parse::<f64>().ok()).unwrap_or(0.0);
+
+	Ok(probe
+		.chapters
+		.into_iter()
+		.enumerate()
+		.map(|(i, c)| Chapter {
+			title: c
+				.tags
+				.and_then(|t| t.title)
+				.unwrap_or_else(|| format!("Chapter {}", i + 1)),
+			start_seconds: parse_time(c.start_time.as_ref()),
+			end_seconds: parse_time(c.end_time.as_ref()),
+		})
+		.collect())
+}
+
+/// Get the chapter markers of a video, in order.
+///
+/// Videos without chapters return an empty `Vec`.
+///
+/// # Errors
+///
+/// Returns an error if the video file is not found, invalid, or `FFprobe` fails.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
+pub async fn get_video_chapters(video_path: impl AsRef<Path>) -> Result<Vec<Chapter>> {
+	let video_path = video_path.as_ref();
+
+	if !video_path.exists() {
+		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
+	}
+
+	let output = Command::new("ffprobe")
+		.args(["-v", "error", "-show_chapters", "-of", "json"])
+		.arg(video_path)
+		.kill_on_drop(true)
+		.output()
+		.await
+		.map_err(|_| PerceptionError::FfprobeNotFound)?;
+
+	if !output.status.success() {
+		return Err(PerceptionError::InvalidVideo(video_path.to_path_buf()));
+	}
+
+	parse_chapters(&String::from_utf8_lossy(&output.stdout))
+}
+
 // ============================================================================
 // Extracted Frame
 // ============================================================================
@@ -167,6 +486,120 @@ pub struct ExtractedFrame {
 	pub is_keyframe: bool,
 }
 
+// ============================================================================
+// Extraction Planning
+// ============================================================================
+
+/// Assumed keyframe spacing when estimating keyframe-only extraction.
+///
+/// Streaming encoders commonly emit a keyframe every ~2 seconds.
+const ESTIMATED_KEYFRAME_INTERVAL_SECONDS: f64 = 2.0;
+
+/// A preview of what `extract_frames` will produce.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionPlan {
+	/// Timestamps (seconds) that will be sampled; empty for keyframe extraction,
+	/// where keyframe positions aren't known until the video is decoded
+	pub timestamps: Vec<f64>,
+
+	/// Number of frames expected (exact for intervals, best-effort for keyframes)
+	pub estimated_frame_count: usize,
+
+	/// Estimated total size of the extracted images in bytes
+	pub estimated_total_bytes: u64,
+}
+
+/// Progress of a running extraction, reported after each kept frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionProgress {
+	/// Frames extracted so far, duplicates excluded
+	pub frames_extracted: usize,
+
+	/// Frames the extraction expects to produce: the number of sampled
+	/// timestamps, or for keyframes (found in a single `FFmpeg` pass) the
+	/// final count
+	pub total_estimated: usize,
+}
+
+/// Timestamps sampled by interval extraction between `start` and `end`, in
+/// order.
+fn interval_timestamps(start: f64, end: f64, interval_seconds: f64) -> impl Iterator<Item = f64> {
+	let interval = if interval_seconds > 0.0 {
+		interval_seconds
+	} else {
+		1.0
+	};
+	std::iter::successors(Some(start), move |&t| Some(t + interval)).take_while(move |&t| t < end)
+}
+
+/// Timestamps sampled by `Interval` or `FixedCount` extraction within the
+/// configured time window, capped at `max_frames`.
+fn sample_timestamps(duration_seconds: f64, config: &VideoConfig) -> Vec<f64> {
+	let (start, end) = config.time_window(duration_seconds);
+	let count = config.max_frames;
+	if config.sampling_strategy() == FrameSamplingStrategy::FixedCount && count > 0 {
+		if end <= start {
+			return Vec::new();
+		}
+		#[allow(clippy::cast_precision_loss)]
+		let interval = (end - start) / count as f64;
+		#[allow(clippy::cast_precision_loss)]
+		return (0..count)
+			.map(|i| (i as f64).mul_add(interval, start))
+			.collect();
+	}
+
+	let cap = if count > 0 { count } else { usize::MAX };
+	interval_timestamps(start, end, config.interval_seconds)
+		.take(cap)
+		.collect()
+}
+
+/// Preview an extraction without running `FFmpeg`.
+///
+/// Interval and fixed-count extraction sample exactly the returned
+/// timestamps (minus any that fail to decode). Keyframe extraction assumes
+/// one keyframe every two seconds, so its count and size are estimates.
+#[must_use]
+pub fn plan_extraction(metadata: &VideoMetadata, config: &VideoConfig) -> ExtractionPlan {
+	let (timestamps, estimated_frame_count) =
+		if config.sampling_strategy() == FrameSamplingStrategy::Keyframes {
+			let (start, end) = config.time_window(metadata.duration_seconds);
+			#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+			let keyframes = ((end - start).max(0.0) / ESTIMATED_KEYFRAME_INTERVAL_SECONDS).ceil() as usize;
+			let count = if config.max_frames > 0 {
+				keyframes.min(config.max_frames)
+			} else {
+				keyframes
+			};
+			(Vec::new(), count)
+		} else {
+			let timestamps = sample_timestamps(metadata.duration_seconds, config);
+			let count = timestamps.len();
+			(timestamps, count)
+		};
+
+	let longest_side = f64::from(metadata.width.max(metadata.height));
+	let downscale = config
+		.max_dimension
+		.filter(|_| longest_side > 0.0)
+		.map_or(1.0, |max| (f64::from(max) / longest_side).min(1.0));
+	let pixels = f64::from(metadata.width) * f64::from(metadata.height) * downscale * downscale;
+	let bytes_per_frame = pixels * config.format.estimated_bytes_per_pixel(config.quality);
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		clippy::cast_precision_loss
+	)]
+	let estimated_total_bytes = (bytes_per_frame * estimated_frame_count as f64) as u64;
+
+	ExtractionPlan {
+		timestamps,
+		estimated_frame_count,
+		estimated_total_bytes,
+	}
+}
+
 // ============================================================================
 // FFmpeg Detection
 // ============================================================================
@@ -228,12 +661,11 @@ pub async fn get_video_metadata(video_path: impl AsRef<Path>) -> Result<VideoMet
 		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
 	}
 
+	// Probe every stream, not just the video one, so audio can be detected
 	let output = Command::new("ffprobe")
 		.args([
 			"-v",
 			"error",
-			"-select_streams",
-			"v:0",
 			"-show_entries",
 			"stream=duration,r_frame_rate,nb_frames,width,height,codec_name,codec_type",
 			"-show_entries",
@@ -242,6 +674,7 @@ pub async fn get_video_metadata(video_path: impl AsRef<Path>) -> Result<VideoMet
 			"json",
 		])
 		.arg(video_path)
+		.kill_on_drop(true)
 		.output()
 		.await
 		.map_err(|_| PerceptionError::FfprobeNotFound)?;
@@ -324,6 +757,19 @@ pub async fn get_video_metadata(video_path: impl AsRef<Path>) -> Result<VideoMet
 	})
 }
 
+/// Check whether a video file has at least one audio stream.
+///
+/// Reads the stream list from the same probe as [`get_video_metadata`], so
+/// callers can skip audio extraction up front instead of interpreting
+/// `FFmpeg` failures.
+///
+/// # Errors
+///
+/// Returns an error if the video file is not found, invalid, or `FFprobe` fails.
+pub async fn has_audio_stream(video_path: impl AsRef<Path>) -> Result<bool> {
+	Ok(get_video_metadata(video_path).await?.has_audio)
+}
+
 // ============================================================================
 // Frame Extraction
 // ============================================================================
@@ -340,9 +786,51 @@ pub async fn extract_frame_at(
 	output_path: impl AsRef<Path>,
 	quality: u32,
 ) -> Result<ExtractedFrame> {
-	let video_path = video_path.as_ref();
-	let output_path = output_path.as_ref();
+	extract_frame_with_encoder_args(
+		video_path.as_ref(),
+		timestamp_seconds,
+		output_path.as_ref(),
+		&["-q:v".to_string(), quality.to_string()],
+		None,
+	)
+	.await
+}
+
+/// Extract a single frame at a specific timestamp, downscaled so its longest
+/// side is at most `max_dimension` pixels.
+///
+/// Smaller frames are kept at their source resolution.
+///
+/// # Errors
+///
+/// Returns an error if the video is not found or frame extraction fails.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display(), timestamp = timestamp_seconds))]
+pub async fn extract_frame_at_with_max_dimension(
+	video_path: impl AsRef<Path>,
+	timestamp_seconds: f64,
+	output_path: impl AsRef<Path>,
+	quality: u32,
+	max_dimension: u32,
+) -> Result<ExtractedFrame> {
+	extract_frame_with_encoder_args(
+		video_path.as_ref(),
+		timestamp_seconds,
+		output_path.as_ref(),
+		&["-q:v".to_string(), quality.to_string()],
+		Some(max_dimension),
+	)
+	.await
+}
 
+/// [`extract_frame_at`] with the encoder options given as `FFmpeg` arguments,
+/// as built by [`VideoConfig::encoder_args`].
+async fn extract_frame_with_encoder_args(
+	video_path: &Path,
+	timestamp_seconds: f64,
+	output_path: &Path,
+	encoder_args: &[String],
+	max_dimension: Option<u32>,
+) -> Result<ExtractedFrame> {
 	if !video_path.exists() {
 		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
 	}
@@ -352,17 +840,20 @@ pub async fn extract_frame_at(
 		tokio::fs::create_dir_all(parent).await?;
 	}
 
-	let output = Command::new("ffmpeg")
+	let mut command = Command::new("ffmpeg");
+	let _ = command
 		.args(["-ss", &format!("{timestamp_seconds:.3}"), "-i"])
-		.arg(video_path)
-		.args([
-			"-vframes",
-			"1",
-			"-q:v",
-			&quality.to_string(),
-			"-y", // Overwrite output
-		])
+		.arg(video_path);
+	if let Some(max_dimension) = max_dimension {
+		let _ = command.args(["-vf", &scale_filter(max_dimension)]);
+	}
+
+	let output = command
+		.args(["-vframes", "1"])
+		.args(encoder_args)
+		.arg("-y") // Overwrite output
 		.arg(output_path)
+		.kill_on_drop(true)
 		.output()
 		.await
 		.map_err(|_| PerceptionError::FfmpegNotFound)?;
@@ -391,15 +882,55 @@ pub async fn extract_frame_at(
 	})
 }
 
-/// Extract frames at regular intervals.
+/// Extract frames from a video, sampled according to `config.sampling`.
+///
+/// With `dedup_during_extraction`, duplicates are judged by the default
+/// [`SceneConfig`]; use [`extract_frames_with_scene_config`] to tune them.
 ///
 /// # Errors
 ///
 /// Returns an error if the video is not found or frame extraction fails.
-#[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
 pub async fn extract_frames(
 	video_path: impl AsRef<Path>,
 	config: &VideoConfig,
+) -> Result<Vec<ExtractedFrame>> {
+	extract_frames_with_scene_config(video_path, config, &SceneConfig::default()).await
+}
+
+/// Extract frames like [`extract_frames`], judging duplicates for
+/// `dedup_during_extraction` by `scene_config`'s hash size and duplicate
+/// threshold.
+///
+/// Duplicate frames are deleted from `output_dir` as soon as they are
+/// found and are missing from the result, so frame numbers may have gaps.
+///
+/// # Errors
+///
+/// Returns an error if the video is not found or frame extraction fails.
+pub async fn extract_frames_with_scene_config(
+	video_path: impl AsRef<Path>,
+	config: &VideoConfig,
+	scene_config: &SceneConfig,
+) -> Result<Vec<ExtractedFrame>> {
+	extract_frames_with_progress(video_path, config, scene_config, |_| {}).await
+}
+
+/// Extract frames like [`extract_frames_with_scene_config`], calling
+/// `on_progress` after each frame is kept.
+///
+/// Interval and fixed-count sampling report as they go, so long videos give
+/// feedback before the extraction finishes. Keyframes are written in one
+/// `FFmpeg` pass, so their progress is only reported once it completes.
+///
+/// # Errors
+///
+/// Returns an error if the video is not found or frame extraction fails.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
+pub async fn extract_frames_with_progress(
+	video_path: impl AsRef<Path>,
+	config: &VideoConfig,
+	scene_config: &SceneConfig,
+	mut on_progress: impl FnMut(ExtractionProgress) + Send,
 ) -> Result<Vec<ExtractedFrame>> {
 	let video_path = video_path.as_ref();
 
@@ -417,45 +948,65 @@ pub async fn extract_frames(
 	// Generate unique prefix for this extraction
 	let prefix = uuid::Uuid::new_v4();
 
-	#[allow(clippy::if_not_else)]
-	let frames = if config.keyframes_only {
-		// Extract keyframes only using select filter
-		extract_keyframes_internal(video_path, config, &prefix, &metadata).await?
-	} else {
-		// Extract at regular intervals
-		let interval = if config.interval_seconds > 0.0 {
-			config.interval_seconds
-		} else {
-			1.0
+	let mut duplicates = config
+		.dedup_during_extraction
+		.then(|| DuplicateFilter::new(scene_config));
+
+	let frames = if config.sampling_strategy() == FrameSamplingStrategy::Keyframes {
+		// Extract keyframes only using select filter; FFmpeg writes them all
+		// in one pass, so duplicates can only be dropped afterwards
+		let frames = extract_keyframes_internal(video_path, config, &prefix, &metadata).await?;
+		let frames = match duplicates.as_mut() {
+			Some(filter) => drop_duplicate_frames(frames, filter),
+			None => frames,
 		};
-
-		let mut timestamp = 0.0;
-		let mut frame_number = 0u32;
+		for frames_extracted in 1..=frames.len() {
+			on_progress(ExtractionProgress {
+				frames_extracted,
+				total_estimated: frames.len(),
+			});
+		}
+		frames
+	} else {
+		// Extract at the planned timestamps
+		let timestamps = sample_timestamps(metadata.duration_seconds, config);
+		let total_estimated = timestamps.len();
+		let encoder_args = config.encoder_args();
 		let mut extracted = Vec::new();
 
-		#[allow(clippy::while_float)]
-		while timestamp < metadata.duration_seconds {
-			if config.max_frames > 0 && extracted.len() >= config.max_frames {
-				break;
-			}
-
+		for (frame_number, timestamp) in (0u32..).zip(timestamps) {
 			let output_path = config.output_dir.join(format!(
 				"{prefix}-{frame_number:05}.{}",
 				config.format.extension()
 			));
 
-			match extract_frame_at(video_path, timestamp, &output_path, config.quality).await {
+			match extract_frame_with_encoder_args(
+				video_path,
+				timestamp,
+				&output_path,
+				&encoder_args,
+				config.max_dimension,
+			)
+			.await
+			{
 				Ok(mut frame) => {
 					frame.frame_number = frame_number;
+					if duplicates
+						.as_mut()
+						.is_some_and(|filter| is_duplicate_frame(filter, &frame))
+					{
+						continue;
+					}
 					extracted.push(frame);
+					on_progress(ExtractionProgress {
+						frames_extracted: extracted.len(),
+						total_estimated,
+					});
 				}
 				Err(e) => {
 					warn!(?e, timestamp, "Failed to extract frame, skipping");
 				}
 			}
-
-			timestamp += interval;
-			frame_number += 1;
 		}
 		extracted
 	};
@@ -464,6 +1015,167 @@ pub async fn extract_frames(
 	Ok(frames)
 }
 
+/// Check a frame against the running duplicate filter, deleting its image
+/// when it is a duplicate.
+fn is_duplicate_frame(filter: &mut DuplicateFilter, frame: &ExtractedFrame) -> bool {
+	if !filter.is_duplicate(&frame.path) {
+		return false;
+	}
+	debug!(frame = frame.frame_number, "Dropping duplicate frame");
+	if let Err(e) = std::fs::remove_file(&frame.path) {
+		warn!(?e, path = %frame.path.display(), "Failed to remove duplicate frame");
+	}
+	true
+}
+
+/// Drop (and delete) frames that duplicate the last kept frame.
+fn drop_duplicate_frames(
+	frames: Vec<ExtractedFrame>,
+	filter: &mut DuplicateFilter,
+) -> Vec<ExtractedFrame> {
+	frames
+		.into_iter()
+		.filter(|frame| !is_duplicate_frame(filter, frame))
+		.collect()
+}
+
+/// Fractions of the time window at which [`extract_thumbnail`] samples
+/// candidate frames.
+const THUMBNAIL_POSITIONS: [f64; 4] = [0.1, 0.25, 0.5, 0.75];
+
+/// Extract a single representative thumbnail for a video.
+///
+/// Samples a frame at 10%, 25%, 50% and 75% of the configured time window,
+/// scores each with [`compute_frame_quality`], and keeps the sharpest. The
+/// other candidates are deleted from `output_dir`. Only `output_dir`,
+/// `quality`, `format`, `max_dimension` and the time window of `config` are
+/// used.
+///
+/// # Errors
+///
+/// Returns an error if the video is not found or no candidate frame could be
+/// extracted.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
+pub async fn extract_thumbnail(
+	video_path: impl AsRef<Path>,
+	config: &VideoConfig,
+) -> Result<ExtractedFrame> {
+	let video_path = video_path.as_ref();
+
+	if !video_path.exists() {
+		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
+	}
+
+	let metadata = get_video_metadata(video_path).await?;
+	tokio::fs::create_dir_all(&config.output_dir).await?;
+
+	let prefix = uuid::Uuid::new_v4();
+	let (start, end) = config.time_window(metadata.duration_seconds);
+	let encoder_args = config.encoder_args();
+	let mut best: Option<(ExtractedFrame, f64)> = None;
+
+	for (frame_number, position) in (0u32..).zip(THUMBNAIL_POSITIONS) {
+		let timestamp = (end - start).max(0.0).mul_add(position, start);
+		let output_path = config.output_dir.join(format!(
+			"{prefix}-thumb-{frame_number}.{}",
+			config.format.extension()
+		));
+
+		let frame = match extract_frame_with_encoder_args(
+			video_path,
+			timestamp,
+			&output_path,
+			&encoder_args,
+			config.max_dimension,
+		)
+		.await
+		{
+			Ok(frame) => ExtractedFrame {
+				frame_number,
+				..frame
+			},
+			Err(e) => {
+				warn!(
+					?e,
+					timestamp, "Failed to extract thumbnail candidate, skipping"
+				);
+				continue;
+			}
+		};
+
+		let score = compute_frame_quality(&frame.path).unwrap_or(0.0);
+		debug!(timestamp, score, "Scored thumbnail candidate");
+
+		let discarded = if best
+			.as_ref()
+			.is_some_and(|(_, best_score)| *best_score >= score)
+		{
+			Some(frame)
+		} else {
+			best.replace((frame, score)).map(|(previous, _)| previous)
+		};
+		if let Some(discarded) = discarded {
+			let _ = tokio::fs::remove_file(&discarded.path).await;
+		}
+	}
+
+	best.map(|(frame, _)| frame)
+		.ok_or_else(|| PerceptionError::FrameExtractionFailed {
+			timestamp: start,
+			reason: "No thumbnail candidate could be extracted".to_string(),
+		})
+}
+
+/// Extract frames at caller-chosen timestamps, in the order given.
+///
+/// Each frame's `frame_number` is its position in `timestamps`. Only
+/// `output_dir`, `quality`, `format` and `max_dimension` of `config` are
+/// used; sampling, time window and deduplication settings are ignored.
+///
+/// # Errors
+///
+/// Returns an error if the video is not found or any requested frame fails
+/// to extract.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display(), count = timestamps.len()))]
+pub async fn extract_frames_at(
+	video_path: impl AsRef<Path>,
+	timestamps: &[f64],
+	config: &VideoConfig,
+) -> Result<Vec<ExtractedFrame>> {
+	let video_path = video_path.as_ref();
+
+	if !video_path.exists() {
+		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
+	}
+
+	tokio::fs::create_dir_all(&config.output_dir).await?;
+
+	let prefix = uuid::Uuid::new_v4();
+	let encoder_args = config.encoder_args();
+	let mut frames = Vec::with_capacity(timestamps.len());
+
+	for (frame_number, &timestamp) in (0u32..).zip(timestamps) {
+		let output_path = config.output_dir.join(format!(
+			"{prefix}-at-{frame_number:05}.{}",
+			config.format.extension()
+		));
+		let frame = extract_frame_with_encoder_args(
+			video_path,
+			timestamp,
+			&output_path,
+			&encoder_args,
+			config.max_dimension,
+		)
+		.await?;
+		frames.push(ExtractedFrame {
+			frame_number,
+			..frame
+		});
+	}
+
+	Ok(frames)
+}
+
 /// Internal function to extract keyframes.
 async fn extract_keyframes_internal(
 	video_path: &Path,
@@ -478,16 +1190,34 @@ async fn extract_keyframes_internal(
 		config.format.extension()
 	));
 
-	let mut args = vec![
+	let mut filter = "select='eq(pict_type\\,I)'".to_string();
+	if let Some(max_dimension) = config.max_dimension {
+		filter.push(',');
+		filter.push_str(&scale_filter(max_dimension));
+	}
+
+	// Seek on the input so FFmpeg skips decoding outside the window; output
+	// timestamps then restart at 0, so frames are matched to probed
+	// timestamps below rather than read back from the output
+	let (start, end) = config.time_window(metadata.duration_seconds);
+	let mut args = Vec::new();
+	if config.start_seconds.is_some() {
+		args.push("-ss".to_string());
+		args.push(format!("{start:.3}"));
+	}
+	if config.end_seconds.is_some() {
+		args.push("-to".to_string());
+		args.push(format!("{end:.3}"));
+	}
+	args.extend([
 		"-i".to_string(),
 		video_path.display().to_string(),
 		"-vf".to_string(),
-		"select='eq(pict_type\\,I)'".to_string(),
+		filter,
 		"-vsync".to_string(),
 		"vfr".to_string(),
-		"-q:v".to_string(),
-		config.quality.to_string(),
-	];
+	]);
+	args.extend(config.encoder_args());
 
 	// Limit frames if configured
 	if config.max_frames > 0 {
@@ -500,6 +1230,7 @@ async fn extract_keyframes_internal(
 
 	let output = Command::new("ffmpeg")
 		.args(&args)
+		.kill_on_drop(true)
 		.output()
 		.await
 		.map_err(|_| PerceptionError::FfmpegNotFound)?;
@@ -529,21 +1260,10 @@ async fn extract_keyframes_internal(
 				.and_then(|s| s.split('.').next())
 			{
 				if let Ok(frame_number) = num_part.parse::<u32>() {
-					// Estimate timestamp based on frame number
-					// This is approximate since FFmpeg doesn't output timestamps directly
-					#[allow(clippy::cast_precision_loss)]
-					let timestamp = if metadata.frame_rate > 0.0 && metadata.duration_seconds > 0.0 {
-						// Rough estimate: keyframes are roughly evenly distributed
-						let keyframe_interval =
-							metadata.duration_seconds / (frames.len() + 1) as f64;
-						f64::from(frame_number) * keyframe_interval
-					} else {
-						0.0
-					};
-
+					// Timestamps are assigned once all frames are collected
 					frames.push(ExtractedFrame {
 						path: entry.path(),
-						timestamp_seconds: timestamp,
+						timestamp_seconds: 0.0,
 						frame_number,
 						is_keyframe: true,
 					});
@@ -555,24 +1275,60 @@ async fn extract_keyframes_internal(
 	// Sort by frame number
 	frames.sort_by_key(|f| f.frame_number);
 
-	// Update timestamps based on actual count
+	// Map extracted frames to the real keyframe timestamps in order, falling
+	// back to an even spread when ffprobe can't enumerate them
+	let keyframe_timestamps = match probe_keyframe_timestamps(video_path)
+		.await
+		.map(|timestamps| in_window(timestamps, start, end))
+	{
+		Ok(timestamps) if timestamps.len() >= frames.len() => Some(timestamps),
+		Ok(timestamps) => {
+			warn!(
+				probed = timestamps.len(),
+				extracted = frames.len(),
+				"Keyframe count mismatch, estimating timestamps"
+			);
+			None
+		}
+		Err(e) => {
+			warn!(?e, "Failed to probe keyframes, estimating timestamps");
+			None
+		}
+	};
+
 	let count = frames.len();
-	#[allow(clippy::cast_precision_loss)]
-	if count > 0 && metadata.duration_seconds > 0.0 {
-		let interval = metadata.duration_seconds / count as f64;
+	if let Some(timestamps) = keyframe_timestamps {
+		for (frame, timestamp) in frames.iter_mut().zip(timestamps) {
+			frame.timestamp_seconds = timestamp;
+		}
+	} else if count > 0 && end > start {
+		#[allow(clippy::cast_precision_loss)]
+		let interval = (end - start) / count as f64;
 		for (i, frame) in frames.iter_mut().enumerate() {
-			frame.timestamp_seconds = i as f64 * interval;
+			#[allow(clippy::cast_precision_loss)]
+			let index = i as f64;
+			frame.timestamp_seconds = index.mul_add(interval, start);
 		}
 	}
 
 	Ok(frames)
 }
 
+/// Keep the timestamps `FFmpeg` decodes when seeking to `start` and stopping
+/// at `end`.
+fn in_window(timestamps: Vec<f64>, start: f64, end: f64) -> Vec<f64> {
+	timestamps
+		.into_iter()
+		.filter(|&t| t >= start && t < end)
+		.collect()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
 	use super::*;
 
@@ -588,8 +1344,534 @@ mod tests {
 	fn test_image_format() {
 		assert_eq!(ImageFormat::Jpeg.extension(), "jpg");
 		assert_eq!(ImageFormat::Png.extension(), "png");
+		assert_eq!(ImageFormat::Webp.extension(), "webp");
 		assert_eq!(ImageFormat::Jpeg.codec(), "mjpeg");
 		assert_eq!(ImageFormat::Png.codec(), "png");
+		assert_eq!(ImageFormat::Webp.codec(), "libwebp");
+	}
+
+	#[test]
+	fn test_encoder_args_per_format() {
+		let config = |format| VideoConfig {
+			format,
+			png_compression: Some(12),
+			jpeg_subsampling: Some("4:4:4".to_string()),
+			..VideoConfig::default()
+		};
+
+		assert_eq!(
+			config(ImageFormat::Jpeg).encoder_args(),
+			["-q:v", "2", "-pix_fmt", "yuvj444p"]
+		);
+		assert_eq!(
+			config(ImageFormat::Png).encoder_args(),
+			["-q:v", "2", "-compression_level", "9"]
+		);
+		assert_eq!(config(ImageFormat::Webp).encoder_args(), ["-q:v", "97"]);
+
+		let unknown = VideoConfig {
+			jpeg_subsampling: Some("411".to_string()),
+			..VideoConfig::default()
+		};
+		assert_eq!(unknown.encoder_args(), ["-q:v", "2"]);
+		assert_eq!(VideoConfig::default().encoder_args(), ["-q:v", "2"]);
+	}
+
+	#[test]
+	fn test_quality_arg() {
+		assert_eq!(ImageFormat::Jpeg.quality_arg(2), 2);
+		assert_eq!(ImageFormat::Png.quality_arg(2), 2);
+
+		assert_eq!(ImageFormat::Webp.quality_arg(1), 100);
+		assert_eq!(ImageFormat::Webp.quality_arg(2), 97);
+		assert_eq!(ImageFormat::Webp.quality_arg(16), 50);
+		assert_eq!(ImageFormat::Webp.quality_arg(31), 0);
+
+		// Out-of-range values are clamped to the 1-31 scale first
+		assert_eq!(ImageFormat::Webp.quality_arg(0), 100);
+		assert_eq!(ImageFormat::Webp.quality_arg(50), 0);
+	}
+
+	fn metadata(duration_seconds: f64) -> VideoMetadata {
+		VideoMetadata {
+			duration_seconds,
+			frame_rate: 30.0,
+			frame_count: 0,
+			width: 1920,
+			height: 1080,
+			codec: "h264".to_string(),
+			has_audio: true,
+		}
+	}
+
+	#[test]
+	fn test_plan_extraction_intervals() {
+		let config = VideoConfig {
+			interval_seconds: 2.5,
+			max_frames: 0,
+			..Default::default()
+		};
+
+		let plan = plan_extraction(&metadata(10.0), &config);
+
+		assert_eq!(plan.timestamps, vec![0.0, 2.5, 5.0, 7.5]);
+		assert_eq!(plan.estimated_frame_count, 4);
+		assert!(plan.estimated_total_bytes > 0);
+	}
+
+	#[test]
+	fn test_plan_extraction_respects_max_frames() {
+		let config = VideoConfig {
+			max_frames: 3,
+			..Default::default()
+		};
+
+		let plan = plan_extraction(&metadata(240.0), &config);
+
+		assert_eq!(plan.timestamps, vec![0.0, 1.0, 2.0]);
+		assert_eq!(plan.estimated_frame_count, 3);
+	}
+
+	#[test]
+	fn test_plan_extraction_keyframes() {
+		let config = VideoConfig {
+			sampling: FrameSamplingStrategy::Keyframes,
+			max_frames: 0,
+			..Default::default()
+		};
+
+		let plan = plan_extraction(&metadata(61.0), &config);
+
+		assert!(plan.timestamps.is_empty());
+		assert_eq!(plan.estimated_frame_count, 31);
+	}
+
+	#[test]
+	fn test_plan_extraction_fixed_count() {
+		let config = VideoConfig {
+			sampling: FrameSamplingStrategy::FixedCount,
+			max_frames: 10,
+			interval_seconds: 1.0,
+			..Default::default()
+		};
+
+		let plan = plan_extraction(&metadata(60.0), &config);
+
+		assert_eq!(plan.estimated_frame_count, 10);
+		for (i, timestamp) in (0u32..).zip(&plan.timestamps) {
+			assert!((timestamp - f64::from(i) * 6.0).abs() < 1e-9);
+		}
+
+		// Short videos get the same number of frames, just closer together
+		let plan = plan_extraction(&metadata(5.0), &config);
+		assert_eq!(plan.timestamps.len(), 10);
+		assert!(plan.timestamps.iter().all(|&t| t < 5.0));
+
+		// Without a count, falls back to the interval
+		let config = VideoConfig {
+			max_frames: 0,
+			..config
+		};
+		assert_eq!(
+			plan_extraction(&metadata(3.0), &config).timestamps,
+			vec![0.0, 1.0, 2.0]
+		);
+	}
+
+	#[test]
+	fn test_plan_extraction_time_window() {
+		let config = VideoConfig {
+			interval_seconds: 2.0,
+			max_frames: 0,
+			start_seconds: Some(10.0),
+			end_seconds: Some(20.0),
+			..Default::default()
+		};
+
+		let plan = plan_extraction(&metadata(60.0), &config);
+		assert_eq!(plan.timestamps, vec![10.0, 12.0, 14.0, 16.0, 18.0]);
+
+		let fixed = VideoConfig {
+			sampling: FrameSamplingStrategy::FixedCount,
+			max_frames: 4,
+			..config.clone()
+		};
+		assert_eq!(
+			plan_extraction(&metadata(60.0), &fixed).timestamps,
+			vec![10.0, 12.5, 15.0, 17.5]
+		);
+
+		let keyframes = VideoConfig {
+			sampling: FrameSamplingStrategy::Keyframes,
+			..config.clone()
+		};
+		assert_eq!(
+			plan_extraction(&metadata(60.0), &keyframes).estimated_frame_count,
+			5
+		);
+
+		// The window is clamped to the video, and an open end runs to the end
+		let open_end = VideoConfig {
+			end_seconds: None,
+			..config.clone()
+		};
+		assert_eq!(
+			plan_extraction(&metadata(15.0), &open_end).timestamps,
+			vec![10.0, 12.0, 14.0]
+		);
+		let past_end = VideoConfig {
+			start_seconds: Some(90.0),
+			end_seconds: None,
+			..config
+		};
+		assert!(plan_extraction(&metadata(60.0), &past_end)
+			.timestamps
+			.is_empty());
+
+		assert_eq!(
+			in_window(vec![8.0, 10.0, 15.0, 20.0], 10.0, 20.0),
+			vec![10.0, 15.0]
+		);
+	}
+
+	#[test]
+	fn test_drop_duplicate_frames() {
+		use image::{DynamicImage, GrayImage, Luma};
+
+		let dir = tempfile::tempdir().expect("temp dir");
+		#[allow(clippy::cast_possible_truncation)]
+		let horizontal =
+			DynamicImage::ImageLuma8(GrayImage::from_fn(64, 64, |x, _| Luma([(x * 4) as u8])));
+		#[allow(clippy::cast_possible_truncation)]
+		let vertical = DynamicImage::ImageLuma8(GrayImage::from_fn(64, 64, |_, y| Luma([(y * 4) as u8])));
+
+		// A static screen, a cut, then another static screen
+		let frames: Vec<ExtractedFrame> = [&horizontal, &horizontal, &vertical, &vertical]
+			.into_iter()
+			.zip(0u32..)
+			.map(|(image, frame_number)| {
+				let path = dir.path().join(format!("frame-{frame_number}.png"));
+				assert!(image.save(&path).is_ok());
+				ExtractedFrame {
+					path,
+					timestamp_seconds: f64::from(frame_number),
+					frame_number,
+					is_keyframe: false,
+				}
+			})
+			.collect();
+		let paths: Vec<PathBuf> = frames.iter().map(|f| f.path.clone()).collect();
+
+		let mut filter = DuplicateFilter::new(&SceneConfig::default());
+		let kept = drop_duplicate_frames(frames, &mut filter);
+
+		let numbers: Vec<u32> = kept.iter().map(|f| f.frame_number).collect();
+		assert_eq!(numbers, vec![0, 2]);
+		assert!(paths[0].exists() && paths[2].exists());
+		assert!(!paths[1].exists() && !paths[3].exists());
+	}
+
+	#[test]
+	fn test_scale_filter() {
+		assert_eq!(
+			scale_filter(1280),
+			"scale='min(1280,iw)':'min(1280,ih)':force_original_aspect_ratio=decrease"
+		);
+
+		// 1920x1080 capped at 960 → 960x540, a quarter of the pixels
+		let full = plan_extraction(&metadata(10.0), &VideoConfig::default());
+		let scaled = plan_extraction(
+			&metadata(10.0),
+			&VideoConfig {
+				max_dimension: Some(960),
+				..Default::default()
+			},
+		);
+		assert_eq!(scaled.estimated_total_bytes, full.estimated_total_bytes / 4);
+	}
+
+	#[test]
+	fn test_plan_extraction_png_larger_than_jpeg() {
+		let jpeg = plan_extraction(&metadata(10.0), &VideoConfig::default());
+		let png = plan_extraction(
+			&metadata(10.0),
+			&VideoConfig {
+				format: ImageFormat::Png,
+				..Default::default()
+			},
+		);
+
+		assert!(png.estimated_total_bytes > jpeg.estimated_total_bytes);
+	}
+
+	#[test]
+	fn test_parse_chapters() {
+		let json = r#"{
+			"chapters": [
+				{
+					"id": 0,
+					"time_base": "1/1000",
+					"start": 0,
+					"start_time": "0.000000",
+					"end": 2000,
+					"end_time": "2.000000",
+					"tags": { "title": "Intro" }
+				},
+				{
+					"id": 1,
+					"time_base": "1/1000",
+					"start": 2000,
+					"start_time": "2.000000",
+					"end": 5000,
+					"end_time": "5.000000"
+				}
+			]
+		}"#;
+
+		let chapters = parse_chapters(json).expect("valid chapter json");
+
+		assert_eq!(
+			chapters,
+			vec![
+				Chapter {
+					title: "Intro".to_string(),
+					start_seconds: 0.0,
+					end_seconds: 2.0,
+				},
+				Chapter {
+					title: "Chapter 2".to_string(),
+					start_seconds: 2.0,
+					end_seconds: 5.0,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_parse_chapters_none() {
+		assert!(parse_chapters("{}").is_ok_and(|c| c.is_empty()));
+		assert!(parse_chapters(r#"{"chapters": []}"#).is_ok_and(|c| c.is_empty()));
+	}
+
+	#[test]
+	fn test_parse_keyframe_timestamps() {
+		let json = r#"{
+			"frames": [
+				{ "key_frame": 1, "pts_time": "0.000000" },
+				{ "key_frame": 1, "best_effort_timestamp_time": "2.502000" },
+				{ "key_frame": 0, "pts_time": "3.000000" },
+				{ "key_frame": 1 },
+				{ "key_frame": 1, "pts_time": "5.005000" }
+			]
+		}"#;
+
+		let timestamps = parse_keyframe_timestamps(json).expect("valid frame json");
+
+		assert_eq!(timestamps, vec![0.0, 2.502, 5.005]);
+		assert!(parse_keyframe_timestamps("{}").is_ok_and(|t| t.is_empty()));
+		assert!(parse_keyframe_timestamps("not json").is_err());
+	}
+
+	#[tokio::test]
+	async fn test_get_video_chapters_fixture() {
+		if check_ffmpeg().await.is_err() || check_ffprobe().await.is_err() {
+			println!("FFmpeg not available, skipping chapter fixture test");
+			return;
+		}
+
+		let dir = tempfile::tempdir().expect("temp dir");
+		let metadata_path = dir.path().join("chapters.txt");
+		let video_path = dir.path().join("chaptered.mp4");
+		let metadata = ";FFMETADATA1\n\
+			[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=1000\ntitle=First\n\
+			[CHAPTER]\nTIMEBASE=1/1000\nSTART=1000\nEND=2000\ntitle=Second\n";
+		assert!(tokio::fs::write(&metadata_path, metadata).await.is_ok());
+
+		let status = Command::new("ffmpeg")
+			.args([
+				"-v",
+				"error",
+				"-f",
+				"lavfi",
+				"-i",
+				"testsrc=duration=2:size=64x64",
+			])
+			.arg("-i")
+			.arg(&metadata_path)
+			.args(["-map_metadata", "1", "-y"])
+			.arg(&video_path)
+			.status()
+			.await;
+		assert!(status.is_ok_and(|s| s.success()));
+
+		let chapters = get_video_chapters(&video_path)
+			.await
+			.expect("chapters probe");
+		let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+		assert_eq!(titles, vec!["First", "Second"]);
+		assert!((chapters[1].start_seconds - 1.0).abs() < 1e-3);
+	}
+
+	#[tokio::test]
+	async fn test_extract_thumbnail() {
+		if check_ffmpeg().await.is_err() || check_ffprobe().await.is_err() {
+			println!("FFmpeg not available, skipping thumbnail test");
+			return;
+		}
+
+		let dir = tempfile::tempdir().expect("temp dir");
+		let video_path = dir.path().join("thumbnail.mp4");
+		let status = Command::new("ffmpeg")
+			.args([
+				"-v",
+				"error",
+				"-f",
+				"lavfi",
+				"-i",
+				"testsrc=duration=4:size=64x64",
+				"-y",
+			])
+			.arg(&video_path)
+			.status()
+			.await;
+		assert!(status.is_ok_and(|s| s.success()));
+
+		let config = VideoConfig {
+			output_dir: dir.path().join("frames"),
+			..VideoConfig::default()
+		};
+		let thumbnail = extract_thumbnail(&video_path, &config)
+			.await
+			.expect("thumbnail extraction");
+
+		assert!(thumbnail.path.exists());
+		assert!(thumbnail.timestamp_seconds > 0.0 && thumbnail.timestamp_seconds < 4.0);
+		let remaining = std::fs::read_dir(&config.output_dir).map_or(0, Iterator::count);
+		assert_eq!(remaining, 1);
+	}
+
+	#[tokio::test]
+	async fn test_extract_frames_reports_progress() {
+		if check_ffmpeg().await.is_err() || check_ffprobe().await.is_err() {
+			println!("FFmpeg not available, skipping progress test");
+			return;
+		}
+
+		let dir = tempfile::tempdir().expect("temp dir");
+		let video_path = dir.path().join("progress.mp4");
+		let status = Command::new("ffmpeg")
+			.args([
+				"-v",
+				"error",
+				"-f",
+				"lavfi",
+				"-i",
+				"testsrc=duration=4:size=64x64",
+				"-y",
+			])
+			.arg(&video_path)
+			.status()
+			.await;
+		assert!(status.is_ok_and(|s| s.success()));
+
+		let config = VideoConfig {
+			output_dir: dir.path().join("frames"),
+			interval_seconds: 1.0,
+			..VideoConfig::default()
+		};
+		let mut reports = Vec::new();
+		let frames = extract_frames_with_progress(
+			&video_path,
+			&config,
+			&SceneConfig::default(),
+			|progress| reports.push(progress),
+		)
+		.await
+		.expect("frame extraction");
+
+		assert!(!frames.is_empty());
+		assert_eq!(reports.len(), frames.len());
+		for (i, progress) in reports.iter().enumerate() {
+			assert_eq!(progress.frames_extracted, i + 1);
+			assert!(progress.total_estimated >= frames.len());
+		}
+	}
+
+	#[tokio::test]
+	async fn test_extract_frames_at_keeps_requested_order() {
+		if check_ffmpeg().await.is_err() || check_ffprobe().await.is_err() {
+			println!("FFmpeg not available, skipping explicit timestamp test");
+			return;
+		}
+
+		let dir = tempfile::tempdir().expect("temp dir");
+		let video_path = dir.path().join("explicit.mp4");
+		let status = Command::new("ffmpeg")
+			.args([
+				"-v",
+				"error",
+				"-f",
+				"lavfi",
+				"-i",
+				"testsrc=duration=4:size=64x64",
+				"-y",
+			])
+			.arg(&video_path)
+			.status()
+			.await;
+		assert!(status.is_ok_and(|s| s.success()));
+
+		let config = VideoConfig {
+			output_dir: dir.path().join("frames"),
+			..VideoConfig::default()
+		};
+		let timestamps = [3.0, 0.5, 2.0];
+		let frames = extract_frames_at(&video_path, &timestamps, &config)
+			.await
+			.expect("explicit frame extraction");
+
+		assert_eq!(frames.len(), timestamps.len());
+		for ((i, frame), expected) in (0u32..).zip(&frames).zip(timestamps) {
+			assert_eq!(frame.frame_number, i);
+			assert!((frame.timestamp_seconds - expected).abs() < 1e-9);
+			assert!(frame.path.exists());
+		}
+	}
+
+	#[tokio::test]
+	async fn test_has_audio_stream() {
+		assert!(matches!(
+			has_audio_stream("/nonexistent/video.mp4").await,
+			Err(PerceptionError::VideoNotFound(_))
+		));
+
+		if check_ffmpeg().await.is_err() || check_ffprobe().await.is_err() {
+			println!("FFmpeg not available, skipping audio stream test");
+			return;
+		}
+
+		let dir = tempfile::tempdir().expect("temp dir");
+		let silent_path = dir.path().join("silent.mp4");
+		let audible_path = dir.path().join("audible.mp4");
+		let video_source = "testsrc=duration=1:size=64x64";
+
+		let silent = Command::new("ffmpeg")
+			.args(["-v", "error", "-f", "lavfi", "-i", video_source, "-y"])
+			.arg(&silent_path)
+			.status()
+			.await;
+		assert!(silent.is_ok_and(|s| s.success()));
+
+		let audible = Command::new("ffmpeg")
+			.args(["-v", "error", "-f", "lavfi", "-i", video_source])
+			.args(["-f", "lavfi", "-i", "sine=duration=1", "-shortest", "-y"])
+			.arg(&audible_path)
+			.status()
+			.await;
+		assert!(audible.is_ok_and(|s| s.success()));
+
+		assert!(matches!(has_audio_stream(&silent_path).await, Ok(false)));
+		assert!(matches!(has_audio_stream(&audible_path).await, Ok(true)));
 	}
 
 	#[test]
@@ -599,6 +1881,29 @@ mod tests {
 		assert!((config.interval_seconds - 1.0).abs() < f64::EPSILON);
 		assert_eq!(config.quality, 2);
 		assert_eq!(config.format, ImageFormat::Jpeg);
-		assert!(!config.keyframes_only);
+		assert_eq!(config.sampling, FrameSamplingStrategy::Interval);
+		assert_eq!(config.sampling_strategy(), FrameSamplingStrategy::Interval);
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn test_keyframes_only_maps_onto_sampling() {
+		let config = VideoConfig {
+			sampling: FrameSamplingStrategy::FixedCount,
+			keyframes_only: true,
+			..VideoConfig::default()
+		};
+		assert_eq!(config.sampling_strategy(), FrameSamplingStrategy::Keyframes);
+
+		// Configs saved before `sampling` existed still load
+		let mut saved = serde_json::to_value(VideoConfig::default()).expect("serialize");
+		let fields = saved.as_object_mut().expect("config is an object");
+		let _ = fields.remove("sampling");
+		let _ = fields.remove("dedup_during_extraction");
+		let _ = fields.insert("keyframes_only".to_string(), serde_json::Value::Bool(true));
+		let loaded: VideoConfig = serde_json::from_value(saved).expect("deserialize");
+		assert_eq!(loaded.sampling, FrameSamplingStrategy::Interval);
+		assert_eq!(loaded.sampling_strategy(), FrameSamplingStrategy::Keyframes);
+		assert!(!loaded.dedup_during_extraction);
 	}
 }